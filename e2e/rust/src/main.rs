@@ -1,7 +1,8 @@
 //! Simple script to send a currency transaction to a local metagraph.
 //!
 //! Reads configuration from config.json and submits a currency transaction
-//! to the Currency L1 endpoint.
+//! to the Currency L1 endpoint. If the config lists `destinations`, a chained
+//! batch of transfers is sent instead, one per destination.
 //!
 //! Usage:
 //!   cargo run
@@ -15,21 +16,33 @@ use std::path::PathBuf;
 use std::process;
 
 use constellation_sdk::{
-    create_currency_transaction, key_pair_from_private_key, verify_currency_transaction,
-    wallet::generate_key_pair, CurrencyTransaction, TransactionReference, TransferParams,
+    build_fanout, create_currency_transaction, key_pair_from_private_key,
+    verify_currency_transaction, wallet::generate_key_pair, CurrencyTransaction,
+    TransactionReference, TransferParams,
 };
 use constellation_sdk::network::{CurrencyL1Client, NetworkConfig};
 
 #[derive(Deserialize)]
 struct Config {
     private_key: String,
+    #[serde(default)]
     destination: String,
+    #[serde(default)]
     amount: f64,
+    /// Fan-out transfers; when non-empty, `destination` and `amount` are ignored
+    #[serde(default)]
+    destinations: Vec<Destination>,
     #[serde(default)]
     fee: f64,
     currency_l1_url: String,
 }
 
+#[derive(Deserialize)]
+struct Destination {
+    address: String,
+    amount: f64,
+}
+
 #[derive(Parser)]
 #[command(name = "send_currency_tx")]
 #[command(about = "Send a currency transaction to a local metagraph")]
@@ -85,8 +98,14 @@ async fn send_transaction(config: Config) {
     let source_address = &keypair.address;
 
     println!("Source Address: {}", source_address);
-    println!("Destination:    {}", destination);
-    println!("Amount:         {} tokens", amount);
+    if config.destinations.is_empty() {
+        println!("Destination:    {}", destination);
+        println!("Amount:         {} tokens", amount);
+    } else {
+        for d in &config.destinations {
+            println!("Destination:    {} ({} tokens)", d.address, d.amount);
+        }
+    }
     println!("Fee:            {} tokens", fee);
     println!("Currency L1:    {}", currency_l1_url);
     println!();
@@ -126,67 +145,81 @@ async fn send_transaction(config: Config) {
     println!("Last Reference Ordinal: {}", last_ref.ordinal);
     println!();
 
-    // Create transaction
+    let last_ref = TransactionReference {
+        hash: last_ref.hash,
+        ordinal: last_ref.ordinal,
+    };
+
+    // Create transactions
     println!("Creating transaction...");
-    let transfer_params = TransferParams {
-        destination: destination.clone(),
-        amount,
-        fee,
+    let created = if config.destinations.is_empty() {
+        let transfer_params = TransferParams {
+            destination: destination.clone(),
+            amount,
+            fee,
+        };
+        create_currency_transaction(transfer_params, private_key, last_ref).map(|tx| vec![tx])
+    } else {
+        let destinations: Vec<(String, f64)> = config
+            .destinations
+            .iter()
+            .map(|d| (d.address.clone(), d.amount))
+            .collect();
+        build_fanout(private_key, &destinations, fee, last_ref)
     };
-    let tx: CurrencyTransaction = match create_currency_transaction(
-        transfer_params,
-        private_key,
-        TransactionReference {
-            hash: last_ref.hash,
-            ordinal: last_ref.ordinal,
-        },
-    ) {
+    let txs: Vec<CurrencyTransaction> = match created {
         Ok(t) => t,
         Err(e) => {
             eprintln!("Error creating transaction: {}", e);
             process::exit(1);
         }
     };
-    println!("Transaction created successfully!");
-
-    // Verify transaction locally
-    println!("Verifying transaction signature...");
-    let result = verify_currency_transaction(&tx);
-    if !result.is_valid {
+    println!("{} transaction(s) created successfully!", txs.len());
+
+    // Verify transactions locally
+    println!("Verifying transaction signatures...");
+    if !txs
+        .iter()
+        .all(|tx| verify_currency_transaction(tx).is_valid)
+    {
         eprintln!("Error: Transaction signature verification failed!");
         process::exit(1);
     }
-    println!("Signature verified!");
+    println!("Signatures verified!");
     println!();
 
-    // Submit transaction
-    println!("Submitting transaction to network...");
-    let response = match client.post_transaction(&tx).await {
-        Ok(r) => r,
-        Err(e) => {
-            eprintln!("Error submitting transaction: {}", e);
-            process::exit(1);
-        }
-    };
-    println!("Transaction submitted!");
-    println!("Transaction Hash: {}", response.hash);
-    println!();
-
-    // Poll for status (optional)
-    println!("Checking transaction status...");
-    match client.get_pending_transaction(&response.hash).await {
-        Ok(Some(pending)) => {
-            println!("Status: {}", pending.status);
-        }
-        Ok(None) => {
-            println!("Transaction not found in pending pool (may already be confirmed)");
-        }
-        Err(e) => {
-            println!("Could not check status: {}", e);
+    for tx in &txs {
+        // Submit transaction
+        println!(
+            "Submitting transaction to {} to network...",
+            tx.value.destination
+        );
+        let response = match client.post_transaction(tx).await {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("Error submitting transaction: {}", e);
+                process::exit(1);
+            }
+        };
+        println!("Transaction submitted!");
+        println!("Transaction Hash: {}", response.hash);
+
+        // Poll for status (optional)
+        println!("Checking transaction status...");
+        match client.get_pending_transaction(&response.hash).await {
+            Ok(Some(pending)) => {
+                println!("Status: {}", pending.status);
+            }
+            Ok(None) => {
+                println!("Transaction not found in pending pool (may already be confirmed)");
+            }
+            Err(e) => {
+                println!("Could not check status: {}", e);
+            }
         }
+        println!();
     }
 
-    println!();
     println!("Done!");
 }
 
@@ -0,0 +1,78 @@
+//! Throughput benchmarks for the hot signing and verification paths
+//!
+//! Run with `cargo bench --bench throughput`. Each benchmark is warmed up,
+//! then timed over a fixed number of iterations; results are printed as
+//! time per operation and operations per second.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use constellation_sdk::{
+    canonicalize, create_currency_transaction, generate_key_pair, hash_bytes, sign, verify,
+    PreparedSigner, Signed, TransactionReference, TransferParams,
+};
+use serde_json::json;
+
+/// Timed iterations per benchmark
+const ITERATIONS: u32 = 2_000;
+
+/// Untimed iterations before measuring
+const WARMUP: u32 = 200;
+
+fn bench<R>(name: &str, mut f: impl FnMut() -> R) {
+    for _ in 0..WARMUP {
+        black_box(f());
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    let per_op = start.elapsed() / ITERATIONS;
+
+    println!(
+        "{name:<28} {:>10.2} µs/op {:>12.0} ops/s",
+        per_op.as_secs_f64() * 1e6,
+        1.0 / per_op.max(Duration::from_nanos(1)).as_secs_f64()
+    );
+}
+
+fn main() {
+    let key_pair = generate_key_pair();
+    let destination = generate_key_pair().address;
+    let data = json!({
+        "id": "sensor-1",
+        "readings": [21.5, 21.7, 22.0],
+        "meta": {"unit": "C", "location": "lab"}
+    });
+    let canonical = canonicalize(&data).unwrap();
+    let signed = Signed {
+        value: data.clone(),
+        proofs: vec![sign(&data, &key_pair.private_key).unwrap()],
+    };
+    let last_ref = TransactionReference {
+        hash: "a".repeat(64),
+        ordinal: 0,
+    };
+
+    bench("generate_key_pair", generate_key_pair);
+    bench("sign", || sign(&data, &key_pair.private_key).unwrap());
+    bench("PreparedSigner::new", || {
+        PreparedSigner::new(&key_pair.private_key).unwrap()
+    });
+    bench("verify", || verify(&signed, false));
+    bench("canonicalize", || canonicalize(&data).unwrap());
+    bench("hash_bytes", || hash_bytes(canonical.as_bytes()));
+    bench("create_currency_transaction", || {
+        create_currency_transaction(
+            TransferParams {
+                destination: destination.clone(),
+                amount: 10.0,
+                fee: 0.0,
+            },
+            &key_pair.private_key,
+            last_ref.clone(),
+        )
+        .unwrap()
+    });
+}
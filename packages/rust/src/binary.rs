@@ -4,9 +4,10 @@
 
 use base64::Engine;
 use serde::Serialize;
+use serde_json::Value;
 
-use crate::canonicalize::canonicalize_bytes;
-use crate::types::{Result, CONSTELLATION_PREFIX};
+use crate::canonicalize::{canonicalize_bytes, canonicalize_bytes_limited};
+use crate::types::{Result, SdkError, SigningOptions, CONSTELLATION_PREFIX};
 
 /// Convert data to bytes for signing
 ///
@@ -27,19 +28,270 @@ use crate::types::{Result, CONSTELLATION_PREFIX};
 /// ```
 pub fn to_bytes<T: Serialize>(data: &T, is_data_update: bool) -> Result<Vec<u8>> {
     let canonical_json = canonicalize_bytes(data)?;
+    Ok(wrap_canonical(canonical_json, is_data_update))
+}
+
+/// Convert data to bytes for signing, honoring signing options
+///
+/// Like `to_bytes`, but uses `options.is_data_update` and
+/// `options.data_update_prefix`, and rejects payloads whose canonical JSON
+/// exceeds `options.max_bytes`.
+///
+/// # Arguments
+/// * `data` - Any serializable data
+/// * `options` - Signing options
+///
+/// # Returns
+/// UTF-8 bytes ready for hashing
+pub fn to_bytes_with_options<T: Serialize>(data: &T, options: &SigningOptions) -> Result<Vec<u8>> {
+    let canonical_json = match options.max_bytes {
+        Some(max_bytes) => canonicalize_bytes_limited(data, max_bytes)?,
+        None => canonicalize_bytes(data)?,
+    };
+    let prefix = options
+        .data_update_prefix
+        .as_deref()
+        .unwrap_or(CONSTELLATION_PREFIX);
+    let bytes = wrap_with_prefix(canonical_json, options.is_data_update, prefix);
+    apply_domain(bytes, options.domain.as_deref())
+}
+
+/// Convert data to bytes for signing with an optional DataUpdate prefix
+///
+/// Like `to_bytes`, with `prefix` in place of `CONSTELLATION_PREFIX` when
+/// given.
+pub(crate) fn to_bytes_with_prefix<T: Serialize>(
+    data: &T,
+    is_data_update: bool,
+    prefix: Option<&str>,
+) -> Result<Vec<u8>> {
+    let canonical_json = canonicalize_bytes(data)?;
+    Ok(wrap_with_prefix(
+        canonical_json,
+        is_data_update,
+        prefix.unwrap_or(CONSTELLATION_PREFIX),
+    ))
+}
 
+/// Prepend a domain-separation tag to signing bytes
+///
+/// Binds a signature to an application so it cannot be replayed into
+/// another one. With a domain, the bytes that are hashed are:
+///
+/// ```text
+/// 0x19 || UTF-8(domain) || ":" || "\n" || payload
+/// ```
+///
+/// where `payload` is the output of `to_bytes` (canonical JSON, or the
+/// DataUpdate-wrapped form). Without a domain, `payload` is returned as-is.
+/// The rest of the pipeline (SHA-256, SHA-512, truncate, ECDSA) is unchanged.
+///
+/// # Arguments
+/// * `payload` - Bytes produced by `to_bytes`
+/// * `domain` - Optional domain; must be non-empty and contain no `:` or
+///   control characters
+///
+/// # Returns
+/// Domain-tagged bytes ready for hashing
+///
+/// # Example
+/// ```
+/// use constellation_sdk::binary::apply_domain;
+///
+/// let bytes = apply_domain(b"{}".to_vec(), Some("my-app")).unwrap();
+/// assert_eq!(bytes, b"\x19my-app:\n{}");
+///
+/// assert!(apply_domain(b"{}".to_vec(), Some("a:b")).is_err());
+/// ```
+pub fn apply_domain(payload: Vec<u8>, domain: Option<&str>) -> Result<Vec<u8>> {
+    let Some(domain) = domain else {
+        return Ok(payload);
+    };
+    if domain.is_empty() {
+        return Err(SdkError::InvalidDomain(
+            "domain must not be empty".to_string(),
+        ));
+    }
+    if domain.chars().any(|c| c == ':' || c.is_control()) {
+        return Err(SdkError::InvalidDomain(format!(
+            "domain must not contain ':' or control characters: {domain:?}"
+        )));
+    }
+
+    let mut bytes = Vec::with_capacity(domain.len() + 3 + payload.len());
+    bytes.push(0x19);
+    bytes.extend_from_slice(domain.as_bytes());
+    bytes.extend_from_slice(b":\n");
+    bytes.extend_from_slice(&payload);
+    Ok(bytes)
+}
+
+/// Convert data to deterministic MessagePack bytes for signing
+///
+/// Used with `MSGPACK_ALGORITHM`. The data is converted to a JSON value
+/// and encoded so that every value has exactly one encoding: map keys
+/// sorted by their UTF-8 bytes, integers in the smallest format (unsigned
+/// formats for non-negative values), non-integers always as float 64, and
+/// the smallest length headers. With `is_data_update`, the bytes get the
+/// same Constellation prefix and base64 wrapping as canonical JSON.
+///
+/// # Arguments
+/// * `data` - Any serializable data
+/// * `is_data_update` - Whether to encode as a DataUpdate (with Constellation prefix)
+///
+/// # Returns
+/// MessagePack bytes ready for hashing
+///
+/// # Example
+/// ```
+/// use constellation_sdk::binary::to_bytes_msgpack;
+/// use serde_json::json;
+///
+/// let bytes = to_bytes_msgpack(&json!({"b": 1, "a": true}), false).unwrap();
+/// assert_eq!(bytes, [0x82, 0xa1, b'a', 0xc3, 0xa1, b'b', 0x01]);
+/// ```
+#[cfg(feature = "msgpack")]
+pub fn to_bytes_msgpack<T: Serialize>(data: &T, is_data_update: bool) -> Result<Vec<u8>> {
+    let encoded = crate::msgpack::encode(&serde_json::to_value(data)?)?;
+    Ok(wrap_canonical(encoded, is_data_update))
+}
+
+/// Convert data to bytes using Python `json.dumps(sort_keys=True)` formatting
+///
+/// **Not canonical.** This is an interop shim for verifying signatures from
+/// a legacy producer that signed `json.dumps(data, sort_keys=True)` output
+/// instead of RFC 8785. New code must use `to_bytes`.
+///
+/// Reproduces Python's defaults: `", "` and `": "` separators, keys sorted
+/// by code point, non-ASCII and control characters escaped as `\uXXXX`
+/// (`ensure_ascii=True`), and floats written like Python's `repr`
+/// (`1.0`, `1e-05`, `1e+16`).
+///
+/// # Arguments
+/// * `data` - Any serializable data
+/// * `is_data_update` - Whether to encode as a DataUpdate (with Constellation prefix)
+///
+/// # Returns
+/// UTF-8 bytes ready for hashing
+///
+/// # Example
+/// ```
+/// use constellation_sdk::binary::to_bytes_python_compat;
+/// use serde_json::json;
+///
+/// let data = json!({"value": 1.0, "id": "caf\u{e9}"});
+/// let bytes = to_bytes_python_compat(&data, false).unwrap();
+/// assert_eq!(bytes, br#"{"id": "caf\u00e9", "value": 1.0}"#);
+/// ```
+pub fn to_bytes_python_compat<T: Serialize>(data: &T, is_data_update: bool) -> Result<Vec<u8>> {
+    let value = serde_json::to_value(data)?;
+    let mut out = String::new();
+    write_python_json(&value, &mut out);
+    Ok(wrap_canonical(out.into_bytes(), is_data_update))
+}
+
+fn write_python_json(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => match n.as_f64() {
+            Some(f) if n.is_f64() => out.push_str(&python_float_repr(f)),
+            _ => out.push_str(&n.to_string()),
+        },
+        Value::String(s) => write_python_string(s, out),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_python_json(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            // Rust string ordering is by code point, like Python's
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_python_string(key, out);
+                out.push_str(": ");
+                write_python_json(item, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+/// Escape a string like Python's `json.dumps` with `ensure_ascii=True`
+fn write_python_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            ' '..='~' => out.push(c),
+            _ => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    out.push_str(&format!("\\u{unit:04x}"));
+                }
+            }
+        }
+    }
+    out.push('"');
+}
+
+/// Format a float like Python's `repr`
+///
+/// Both use the shortest round-tripping digits; Python switches to
+/// exponent notation outside `1e-4 <= |f| < 1e16`.
+fn python_float_repr(f: f64) -> String {
+    let sci = format!("{:e}", f.abs());
+    let (mantissa, exponent) = sci.split_once('e').unwrap_or((&sci, "0"));
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+    let digits = mantissa.replace('.', "");
+    let sign = if f.is_sign_negative() { "-" } else { "" };
+
+    let body = if (-4..16).contains(&exponent) {
+        let point = exponent + 1;
+        if point <= 0 {
+            format!("0.{}{digits}", "0".repeat(-point as usize))
+        } else if point as usize >= digits.len() {
+            format!("{digits}{}.0", "0".repeat(point as usize - digits.len()))
+        } else {
+            let (int, frac) = digits.split_at(point as usize);
+            format!("{int}.{frac}")
+        }
+    } else {
+        let exp_sign = if exponent < 0 { '-' } else { '+' };
+        format!("{mantissa}e{exp_sign}{:02}", exponent.abs())
+    };
+    format!("{sign}{body}")
+}
+
+/// Apply the DataUpdate wrapping to canonical JSON if requested
+pub(crate) fn wrap_canonical(canonical_json: Vec<u8>, is_data_update: bool) -> Vec<u8> {
+    wrap_with_prefix(canonical_json, is_data_update, CONSTELLATION_PREFIX)
+}
+
+fn wrap_with_prefix(canonical_json: Vec<u8>, is_data_update: bool, prefix: &str) -> Vec<u8> {
     if is_data_update {
         // Add Constellation prefix for DataUpdate
         let base64_string = base64::engine::general_purpose::STANDARD.encode(&canonical_json);
-        let wrapped_string = format!(
-            "{}{}\n{}",
-            CONSTELLATION_PREFIX,
-            base64_string.len(),
-            base64_string
-        );
-        Ok(wrapped_string.into_bytes())
+        let wrapped_string = format!("{}{}\n{}", prefix, base64_string.len(), base64_string);
+        wrapped_string.into_bytes()
     } else {
-        Ok(canonical_json)
+        canonical_json
     }
 }
 
@@ -61,6 +313,18 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn test_wrap_canonical_matches_to_bytes() {
+        let data = json!({"id": "test"});
+        let canonical = canonicalize_bytes(&data).unwrap();
+        for is_data_update in [false, true] {
+            assert_eq!(
+                wrap_canonical(canonical.clone(), is_data_update),
+                to_bytes(&data, is_data_update).unwrap()
+            );
+        }
+    }
+
     #[test]
     fn test_to_bytes_regular() {
         let data = json!({"id": "test", "value": 42});
@@ -85,4 +349,94 @@ mod tests {
         let s = String::from_utf8(bytes).unwrap();
         assert!(s.starts_with("\x19Constellation Signed Data:\n"));
     }
+
+    #[test]
+    fn test_to_bytes_with_options_max_bytes() {
+        let data = json!({"id": "test"});
+        let options = SigningOptions {
+            is_data_update: true,
+            max_bytes: Some(13),
+            ..Default::default()
+        };
+        assert_eq!(
+            to_bytes_with_options(&data, &options).unwrap(),
+            to_bytes(&data, true).unwrap()
+        );
+
+        let options = SigningOptions {
+            max_bytes: Some(5),
+            ..Default::default()
+        };
+        assert!(to_bytes_with_options(&data, &options).is_err());
+    }
+
+    #[test]
+    fn test_to_bytes_with_options_domain() {
+        let data = json!({"id": "test"});
+        let options = SigningOptions {
+            domain: Some("app-a".to_string()),
+            ..Default::default()
+        };
+        let bytes = to_bytes_with_options(&data, &options).unwrap();
+        assert_eq!(bytes, b"\x19app-a:\n{\"id\":\"test\"}");
+
+        let options = SigningOptions {
+            is_data_update: true,
+            domain: Some("app-a".to_string()),
+            ..Default::default()
+        };
+        let bytes = to_bytes_with_options(&data, &options).unwrap();
+        let wrapped = to_bytes(&data, true).unwrap();
+        assert_eq!(&bytes[..8], b"\x19app-a:\n");
+        assert_eq!(&bytes[8..], wrapped.as_slice());
+    }
+
+    #[test]
+    fn test_to_bytes_python_compat_matches_json_dumps() {
+        // Expected values from Python 3 `json.dumps(data, sort_keys=True)`
+        let data = json!({
+            "z": [1, 2.5, null, true],
+            "a": {"nested": {}, "list": []},
+            "\u{e9}": "caf\u{e9} \u{1f600}\n\"\\\u{7f}",
+            "B": -0.0,
+        });
+        let bytes = to_bytes_python_compat(&data, false).unwrap();
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            r#"{"B": -0.0, "a": {"list": [], "nested": {}}, "z": [1, 2.5, null, true], "\u00e9": "caf\u00e9 \ud83d\ude00\n\"\\\u007f"}"#
+        );
+
+        let bytes = to_bytes_python_compat(&data, true).unwrap();
+        assert!(bytes.starts_with(CONSTELLATION_PREFIX.as_bytes()));
+    }
+
+    #[test]
+    fn test_python_float_repr() {
+        for (f, expected) in [
+            (1.0, "1.0"),
+            (0.1, "0.1"),
+            (123.456, "123.456"),
+            (0.0001, "0.0001"),
+            (0.00001, "1e-05"),
+            (1.5e-7, "1.5e-07"),
+            (1e15, "1000000000000000.0"),
+            (1e16, "1e+16"),
+            (1.2345e20, "1.2345e+20"),
+            (-2.5e100, "-2.5e+100"),
+            (0.0, "0.0"),
+        ] {
+            assert_eq!(python_float_repr(f), expected);
+        }
+    }
+
+    #[test]
+    fn test_apply_domain_rejects_invalid_domains() {
+        assert_eq!(apply_domain(b"x".to_vec(), None).unwrap(), b"x");
+        for domain in ["", "a:b", "a\nb", "\x19"] {
+            assert!(matches!(
+                apply_domain(b"x".to_vec(), Some(domain)),
+                Err(SdkError::InvalidDomain(_))
+            ));
+        }
+    }
 }
@@ -2,10 +2,16 @@
 //!
 //! Provides deterministic JSON serialization according to RFC 8785.
 
+use std::io;
+
 use serde::Serialize;
-use serde_json_canonicalizer::to_vec as canonicalize_to_vec;
+use serde_json::Value;
+use serde_json_canonicalizer::{
+    to_vec as canonicalize_to_vec, to_writer as canonicalize_to_writer,
+};
 
-use crate::types::{Result, SdkError};
+use crate::hash::hash_bytes;
+use crate::types::{Hash, Result, SdkError};
 
 /// Canonicalize data to a JSON string according to RFC 8785
 ///
@@ -41,6 +47,137 @@ pub fn canonicalize_bytes<T: Serialize>(data: &T) -> Result<Vec<u8>> {
     canonicalize_to_vec(data).map_err(|e| SdkError::SerializationError(e.to_string()))
 }
 
+/// Canonicalize data and hash the canonical JSON in one pass
+///
+/// The hash is the SHA-256 of the returned string's bytes, the same as
+/// `hash_data(data, false)`, without canonicalizing twice.
+///
+/// # Arguments
+/// * `data` - Any serializable data
+///
+/// # Returns
+/// Canonical JSON string and its hash
+///
+/// # Example
+/// ```
+/// use constellation_sdk::canonicalize::canonicalize_and_hash;
+/// use constellation_sdk::hash::hash_data;
+/// use serde_json::json;
+///
+/// let data = json!({"b": 2, "a": 1});
+/// let (canonical, hash) = canonicalize_and_hash(&data).unwrap();
+/// assert_eq!(canonical, r#"{"a":1,"b":2}"#);
+/// assert_eq!(hash, hash_data(&data, false).unwrap());
+/// ```
+pub fn canonicalize_and_hash<T: Serialize>(data: &T) -> Result<(String, Hash)> {
+    let canonical = canonicalize(data)?;
+    let hash = hash_bytes(canonical.as_bytes());
+    Ok((canonical, hash))
+}
+
+/// Canonicalize data to a JSON string, rejecting any floating-point number
+///
+/// Floats are formatted differently across languages, so a payload holding
+/// amounts should carry them as integers or strings. Any number that is not
+/// an integer in the serialized value, including integral floats like `1.0`,
+/// is rejected.
+///
+/// # Arguments
+/// * `data` - Any serializable data
+///
+/// # Returns
+/// Canonical JSON string, or `SdkError::SerializationError` naming the JSON
+/// pointer of the first float found
+///
+/// # Example
+/// ```
+/// use constellation_sdk::canonicalize::canonicalize_no_floats;
+/// use serde_json::json;
+///
+/// assert!(canonicalize_no_floats(&json!({"amount": 100})).is_ok());
+/// let err = canonicalize_no_floats(&json!({"items": [{"amount": 1.0}]})).unwrap_err();
+/// assert!(err.to_string().contains("/items/0/amount"));
+/// ```
+pub fn canonicalize_no_floats<T: Serialize>(data: &T) -> Result<String> {
+    let value = serde_json::to_value(data)?;
+    if let Some(pointer) = find_float("", &value) {
+        return Err(SdkError::SerializationError(format!(
+            "Float value at JSON pointer \"{}\"",
+            pointer
+        )));
+    }
+    canonicalize(&value)
+}
+
+/// JSON pointer of the first float in `value`, if any
+fn find_float(pointer: &str, value: &Value) -> Option<String> {
+    match value {
+        Value::Number(n) if n.is_f64() => Some(pointer.to_string()),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .find_map(|(i, item)| find_float(&format!("{pointer}/{i}"), item)),
+        Value::Object(map) => map.iter().find_map(|(key, item)| {
+            find_float(&format!("{pointer}/{}", escape_pointer_token(key)), item)
+        }),
+        _ => None,
+    }
+}
+
+/// Escape a key for use in a JSON pointer (RFC 6901)
+pub(crate) fn escape_pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Canonicalize data to UTF-8 bytes, rejecting payloads larger than `max_bytes`
+///
+/// The canonical JSON is streamed into a buffer that fails as soon as it
+/// grows past the limit, so the check measures the exact RFC 8785 output
+/// and oversized input is not written out in full. Object members are
+/// sorted before they are written, so one object is still held in memory
+/// while it is serialized.
+///
+/// # Arguments
+/// * `data` - Any serializable data
+/// * `max_bytes` - Maximum canonical JSON size in bytes
+///
+/// # Returns
+/// Canonical JSON as UTF-8 bytes, or `SdkError::PayloadTooLarge`
+pub fn canonicalize_bytes_limited<T: Serialize>(data: &T, max_bytes: usize) -> Result<Vec<u8>> {
+    let mut writer = LimitedWriter {
+        bytes: Vec::new(),
+        max: max_bytes,
+        exceeded: false,
+    };
+    match canonicalize_to_writer(data, &mut writer) {
+        Ok(()) => Ok(writer.bytes),
+        Err(_) if writer.exceeded => Err(SdkError::PayloadTooLarge(max_bytes)),
+        Err(e) => Err(SdkError::SerializationError(e.to_string())),
+    }
+}
+
+/// Writer that buffers output, failing once more than `max` bytes are written
+struct LimitedWriter {
+    bytes: Vec<u8>,
+    max: usize,
+    exceeded: bool,
+}
+
+impl io::Write for LimitedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.bytes.len() + buf.len() > self.max {
+            self.exceeded = true;
+            return Err(io::Error::other("size limit exceeded"));
+        }
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,10 +221,68 @@ mod tests {
         assert_eq!(canonical, "[3,1,2]");
     }
 
+    #[test]
+    fn test_canonicalize_no_floats() {
+        let data = json!({"amount": 100, "memo": "1.5", "tags": [1, 2]});
+        assert_eq!(
+            canonicalize_no_floats(&data).unwrap(),
+            canonicalize(&data).unwrap()
+        );
+
+        for (data, pointer) in [
+            (json!({"amount": 1.0}), "/amount"),
+            (json!({"a/b": {"c": [0, 0.5]}}), "/a~1b/c/1"),
+            (json!(2.5), ""),
+        ] {
+            match canonicalize_no_floats(&data) {
+                Err(SdkError::SerializationError(msg)) => {
+                    assert!(msg.contains(&format!("\"{pointer}\"")), "{msg}")
+                }
+                other => panic!("expected float error, got {other:?}"),
+            }
+        }
+    }
+
     #[test]
     fn test_canonicalize_bytes() {
         let data = json!({"id": "test"});
         let bytes = canonicalize_bytes(&data).unwrap();
         assert_eq!(bytes, br#"{"id":"test"}"#);
     }
+
+    #[test]
+    fn test_canonicalize_and_hash() {
+        let data = json!({"z": [1, 2], "a": {"c": true, "b": null}});
+        let (canonical, hash) = canonicalize_and_hash(&data).unwrap();
+        assert_eq!(canonical, canonicalize(&data).unwrap());
+        assert_eq!(hash, hash_bytes(canonical.as_bytes()));
+        assert_eq!(hash, crate::hash::hash_data(&data, false).unwrap());
+    }
+
+    #[test]
+    fn test_canonicalize_bytes_limited() {
+        let data = json!({"id": "test"});
+        assert_eq!(
+            canonicalize_bytes_limited(&data, 13).unwrap(),
+            br#"{"id":"test"}"#
+        );
+        assert!(matches!(
+            canonicalize_bytes_limited(&data, 12),
+            Err(SdkError::PayloadTooLarge(12))
+        ));
+    }
+
+    #[test]
+    fn test_canonicalize_bytes_limited_measures_canonical_floats() {
+        // serde_json writes 1.0 where RFC 8785 writes 1
+        let data = json!({"a": 1.0, "b": 2.0, "c": 3.0});
+        assert_eq!(
+            canonicalize_bytes_limited(&data, 19).unwrap(),
+            br#"{"a":1,"b":2,"c":3}"#
+        );
+        assert!(matches!(
+            canonicalize_bytes_limited(&data, 18),
+            Err(SdkError::PayloadTooLarge(18))
+        ));
+    }
 }
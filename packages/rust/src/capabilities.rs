@@ -0,0 +1,98 @@
+//! Capability Discovery
+//!
+//! Report the algorithms, formats and features supported by this build,
+//! for negotiation with peers.
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{ALGORITHM, MSGPACK_ALGORITHM};
+
+/// Supported currency transaction encoding version
+pub const CURRENCY_TRANSACTION_VERSION: u32 = 2;
+
+/// Structured description of what this SDK build supports
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// SDK crate version
+    pub sdk_version: String,
+    /// Supported signature algorithm identifiers
+    pub algorithms: Vec<String>,
+    /// Supported currency transaction encoding versions
+    pub transaction_versions: Vec<u32>,
+    /// Supported DataUpdate compression algorithms
+    pub compression: Vec<String>,
+    /// Enabled cargo features
+    pub features: Vec<String>,
+}
+
+impl Capabilities {
+    /// Whether the given signature algorithm is supported
+    pub fn supports_algorithm(&self, algorithm: &str) -> bool {
+        self.algorithms.iter().any(|a| a == algorithm)
+    }
+
+    /// Whether the given cargo feature is enabled
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|f| f == feature)
+    }
+}
+
+/// Report the capabilities of this SDK build
+///
+/// # Example
+/// ```
+/// use constellation_sdk::{capabilities, ALGORITHM};
+///
+/// let caps = capabilities();
+/// assert!(caps.supports_algorithm(ALGORITHM));
+/// println!("{}", serde_json::to_string(&caps).unwrap());
+/// ```
+pub fn capabilities() -> Capabilities {
+    let enabled = [
+        ("std", cfg!(feature = "std")),
+        ("network", cfg!(feature = "network")),
+        ("recovery", cfg!(feature = "recovery")),
+        ("parallel", cfg!(feature = "parallel")),
+        ("msgpack", cfg!(feature = "msgpack")),
+    ];
+    let features = enabled
+        .iter()
+        .filter(|(_, on)| *on)
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+    let mut algorithms = vec![ALGORITHM.to_string()];
+    if cfg!(feature = "msgpack") {
+        algorithms.push(MSGPACK_ALGORITHM.to_string());
+    }
+
+    Capabilities {
+        sdk_version: env!("CARGO_PKG_VERSION").to_string(),
+        algorithms,
+        transaction_versions: vec![CURRENCY_TRANSACTION_VERSION],
+        compression: vec!["gzip".to_string()],
+        features,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_reports_algorithm() {
+        let caps = capabilities();
+        assert!(caps.supports_algorithm("SECP256K1_RFC8785_V1"));
+        assert!(!caps.supports_algorithm("ED25519"));
+        assert_eq!(caps.transaction_versions, vec![2]);
+        assert_eq!(caps.compression, vec!["gzip"]);
+        assert_eq!(caps.has_feature("network"), cfg!(feature = "network"));
+        assert_eq!(caps.has_feature("recovery"), cfg!(feature = "recovery"));
+        assert_eq!(caps.has_feature("parallel"), cfg!(feature = "parallel"));
+        assert_eq!(caps.has_feature("msgpack"), cfg!(feature = "msgpack"));
+        assert_eq!(
+            caps.supports_algorithm("SECP256K1_MSGPACK_V1"),
+            cfg!(feature = "msgpack")
+        );
+    }
+}
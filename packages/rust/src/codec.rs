@@ -4,12 +4,44 @@
 
 use base64::Engine;
 use serde::de::DeserializeOwned;
+use serde::Serialize;
 
+use crate::deflate::{gzip_compress, gzip_decompress};
 use crate::types::{Result, SdkError, CONSTELLATION_PREFIX};
 
 // Re-export binary encoding functions
 pub use crate::binary::{encode_data_update, to_bytes};
 
+/// Default limit on the decompressed size of a compressed DataUpdate
+pub const MAX_DECOMPRESSED_SIZE: usize = 16 * 1024 * 1024;
+
+/// Compression algorithm for stored/transmitted DataUpdates
+///
+/// Compressed output starts with a single header byte identifying the
+/// algorithm, so decoding can detect it automatically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// Gzip (DEFLATE), header byte `0x01`
+    Gzip,
+}
+
+impl Compression {
+    /// Header byte written before the compressed payload
+    pub fn header_byte(&self) -> u8 {
+        match self {
+            Compression::Gzip => 0x01,
+        }
+    }
+
+    /// Look up the algorithm for a header byte
+    pub fn from_header_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x01 => Some(Compression::Gzip),
+            _ => None,
+        }
+    }
+}
+
 /// Decode a DataUpdate back to JSON
 ///
 /// # Arguments
@@ -50,11 +82,17 @@ pub fn decode_data_update<T: DeserializeOwned>(data: &[u8]) -> Result<T> {
         ));
     }
 
-    let _length: usize = parts[0]
+    let length: usize = parts[0]
         .parse()
         .map_err(|_| SdkError::SerializationError("Invalid length in DataUpdate".to_string()))?;
 
     let base64_data = parts[1];
+    if base64_data.len() != length {
+        return Err(SdkError::SerializationError(format!(
+            "Invalid DataUpdate format: length {length} does not match payload length {}",
+            base64_data.len()
+        )));
+    }
 
     // Decode base64
     let decoded_bytes = base64::engine::general_purpose::STANDARD
@@ -65,6 +103,86 @@ pub fn decode_data_update<T: DeserializeOwned>(data: &[u8]) -> Result<T> {
     serde_json::from_slice(&decoded_bytes).map_err(|e| e.into())
 }
 
+/// Encode data as a DataUpdate and compress it
+///
+/// Compression is purely a storage/transport concern: signatures are still
+/// computed over the uncompressed encoding, so decompress before verifying.
+///
+/// # Arguments
+/// * `data` - Any serializable data
+/// * `algo` - Compression algorithm
+///
+/// # Returns
+/// Header byte followed by the compressed DataUpdate bytes
+///
+/// # Example
+/// ```
+/// use constellation_sdk::codec::{
+///     decode_data_update_compressed, encode_data_update_compressed, Compression,
+/// };
+/// use serde_json::{json, Value};
+///
+/// let data = json!({"id": "test", "readings": [1, 1, 1, 1, 1, 1, 1, 1]});
+/// let compressed = encode_data_update_compressed(&data, Compression::Gzip).unwrap();
+/// let decoded: Value = decode_data_update_compressed(&compressed).unwrap();
+/// assert_eq!(decoded, data);
+/// ```
+pub fn encode_data_update_compressed<T: Serialize>(data: &T, algo: Compression) -> Result<Vec<u8>> {
+    let encoded = encode_data_update(data)?;
+
+    let mut result = vec![algo.header_byte()];
+    match algo {
+        Compression::Gzip => result.extend(gzip_compress(&encoded)),
+    }
+    Ok(result)
+}
+
+/// Decompress and decode a DataUpdate produced by `encode_data_update_compressed`
+///
+/// Decompresses at most `MAX_DECOMPRESSED_SIZE` bytes; use
+/// `decode_data_update_compressed_limited` for a different limit.
+///
+/// # Arguments
+/// * `data` - Header byte followed by compressed DataUpdate bytes
+///
+/// # Returns
+/// Decoded data
+pub fn decode_data_update_compressed<T: DeserializeOwned>(data: &[u8]) -> Result<T> {
+    decode_data_update_compressed_limited(data, MAX_DECOMPRESSED_SIZE)
+}
+
+/// Decompress and decode a compressed DataUpdate of bounded size
+///
+/// Compressed input from untrusted sources can expand enormously.
+/// Decompression stops as soon as the output would exceed `max_len` bytes.
+///
+/// # Arguments
+/// * `data` - Header byte followed by compressed DataUpdate bytes
+/// * `max_len` - Maximum decompressed size in bytes
+///
+/// # Returns
+/// Decoded data, or `SdkError::PayloadTooLarge` if the decompressed
+/// DataUpdate is longer than `max_len`
+pub fn decode_data_update_compressed_limited<T: DeserializeOwned>(
+    data: &[u8],
+    max_len: usize,
+) -> Result<T> {
+    let (&header, payload) = data.split_first().ok_or_else(|| {
+        SdkError::SerializationError("Invalid compressed DataUpdate: empty input".to_string())
+    })?;
+
+    let algo = Compression::from_header_byte(header).ok_or_else(|| {
+        SdkError::SerializationError(format!(
+            "Invalid compressed DataUpdate: unknown compression header 0x{header:02x}"
+        ))
+    })?;
+
+    let decompressed = match algo {
+        Compression::Gzip => gzip_decompress(payload, max_len)?,
+    };
+    decode_data_update(&decompressed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,4 +209,52 @@ mod tests {
         let result: Result<Value> = decode_data_update(data.as_bytes());
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_compressed_roundtrip() {
+        let data = json!({"id": "test", "payload": "abc".repeat(200)});
+        let compressed = encode_data_update_compressed(&data, Compression::Gzip).unwrap();
+        assert_eq!(compressed[0], 0x01);
+        assert!(compressed.len() < encode_data_update(&data).unwrap().len());
+
+        let decoded: Value = decode_data_update_compressed(&compressed).unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn test_compressed_does_not_affect_signature() {
+        use crate::sign::sign_data_update;
+        use crate::verify::verify_signature;
+        use crate::wallet::generate_key_pair;
+
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test", "value": 42});
+        let proof = sign_data_update(&data, &key_pair.private_key).unwrap();
+
+        let compressed = encode_data_update_compressed(&data, Compression::Gzip).unwrap();
+        let decoded: Value = decode_data_update_compressed(&compressed).unwrap();
+        assert!(verify_signature(&decoded, &proof, true).unwrap());
+    }
+
+    #[test]
+    fn test_compressed_size_limit() {
+        let data = json!({"blob": "x".repeat(100_000)});
+        let compressed = encode_data_update_compressed(&data, Compression::Gzip).unwrap();
+        let encoded_len = encode_data_update(&data).unwrap().len();
+
+        let decoded: Value =
+            decode_data_update_compressed_limited(&compressed, encoded_len).unwrap();
+        assert_eq!(decoded, data);
+        let result: Result<Value> =
+            decode_data_update_compressed_limited(&compressed, encoded_len - 1);
+        assert!(matches!(result, Err(SdkError::PayloadTooLarge(n)) if n == encoded_len - 1));
+    }
+
+    #[test]
+    fn test_compressed_unknown_header() {
+        let result: Result<Value> = decode_data_update_compressed(&[0x7f, 0x00]);
+        assert!(result.is_err());
+        let result: Result<Value> = decode_data_update_compressed(&[]);
+        assert!(result.is_err());
+    }
 }
@@ -1,26 +1,87 @@
 //! Currency transaction operations for metagraph token transfers
 
+use std::collections::HashSet;
+
 use num_bigint::BigUint;
 use rand::Rng;
 use regex::Regex;
-use secp256k1::{Message, Secp256k1, SecretKey};
+use secp256k1::{Message, SecretKey, SECP256K1};
 use sha2::{Digest, Sha256, Sha512};
 
 use crate::currency_types::{
-    CurrencyTransaction, CurrencyTransactionValue, TransactionReference, TransferParams,
-    TOKEN_DECIMALS,
+    CurrencyTransaction, CurrencyTransactionValue, PartialTransactionValue, TokenAmount,
+    TransactionReference, TransactionVersion, TransferParams, TOKEN_DECIMALS, TOKEN_DECIMAL_PLACES,
+};
+use crate::sign::parse_der_signature;
+use crate::types::{
+    Hash, InvalidReason, Result, SdkError, SignatureProof, Signed, VerificationResult,
 };
-use crate::types::{Hash, Result, SdkError, SignatureProof, Signed, VerificationResult};
-use crate::wallet::get_address;
+use crate::verify::{is_high_s, parse_proof_key, VerifyOptions};
+use crate::wallet::{get_address, key_pair_from_private_key, signer_key};
 
 /// Minimum salt complexity (from dag4.js)
 const MIN_SALT: u64 = (1u64 << 53) - (1u64 << 48);
 
 /// Convert token amount to smallest units
+///
+/// The result is floored, so precision beyond 8 decimals is dropped, and so
+/// can the last unit of amounts like `0.29` that have no exact binary
+/// representation. Use `truncation_loss` to measure what was dropped.
 pub fn token_to_units(amount: f64) -> i64 {
     (amount * 1e8).floor() as i64
 }
 
+/// Token amount that `token_to_units` discards when flooring `amount`
+///
+/// # Example
+/// ```
+/// use constellation_sdk::currency_transaction::truncation_loss;
+///
+/// assert_eq!(truncation_loss(1.5), 0.0);
+/// assert!((truncation_loss(0.123456789) - 9e-9).abs() < 1e-12);
+/// ```
+pub fn truncation_loss(amount: f64) -> f64 {
+    amount - units_to_token(token_to_units(amount))
+}
+
+/// Convert a token amount to smallest units, failing instead of truncating
+///
+/// Uses the shortest decimal form of `amount` (the one it prints as), so
+/// `0.29` is exactly 29000000 units rather than the floored 28999999.
+fn exact_units(amount: f64) -> Result<i64> {
+    if !amount.is_finite() {
+        return Err(SdkError::InvalidAmount(format!(
+            "Amount {amount} is not finite"
+        )));
+    }
+
+    let decimal = amount.abs().to_string();
+    let (whole, fraction) = decimal.split_once('.').unwrap_or((&decimal, ""));
+    if fraction.len() > 8 {
+        return Err(SdkError::InvalidAmount(format!(
+            "Amount {amount} has precision beyond 8 decimals"
+        )));
+    }
+
+    let units = format!("{whole}{fraction:0<8}")
+        .parse::<i64>()
+        .map_err(|_| SdkError::InvalidAmount(format!("Amount {amount} is out of range")))?;
+    Ok(if amount < 0.0 { -units } else { units })
+}
+
+/// Smallest non-zero amount of a token with the given decimal places
+///
+/// # Example
+/// ```
+/// use constellation_sdk::currency_transaction::minimum_transfer;
+///
+/// assert_eq!(minimum_transfer(8), 1e-8);
+/// assert_eq!(minimum_transfer(6), 1e-6);
+/// ```
+pub fn minimum_transfer(decimals: u32) -> f64 {
+    10f64.powi(-(decimals as i32))
+}
+
 /// Convert smallest units to token amount
 pub fn units_to_token(units: i64) -> f64 {
     units as f64 * TOKEN_DECIMALS
@@ -47,6 +108,27 @@ pub fn is_valid_dag_address(address: &str) -> bool {
     re.is_match(&address[4..])
 }
 
+/// Compare two DAG addresses, tolerating surrounding whitespace
+///
+/// Both addresses are trimmed and must be valid DAG addresses. Case is
+/// preserved: base58 is case-sensitive, so differently-cased addresses are
+/// different addresses.
+///
+/// # Example
+/// ```
+/// use constellation_sdk::currency_transaction::addresses_equal;
+/// use constellation_sdk::wallet::generate_key_pair;
+///
+/// let address = generate_key_pair().address;
+/// assert!(addresses_equal(&address, &format!(" {address}\n")));
+/// assert!(!addresses_equal(&address, &address.to_lowercase()));
+/// assert!(!addresses_equal("invalid", "invalid"));
+/// ```
+pub fn addresses_equal(a: &str, b: &str) -> bool {
+    let (a, b) = (a.trim(), b.trim());
+    is_valid_dag_address(a) && is_valid_dag_address(b) && a == b
+}
+
 /// Generate a random salt for transaction uniqueness
 fn generate_salt() -> String {
     let mut rng = rand::thread_rng();
@@ -65,22 +147,71 @@ fn generate_salt() -> String {
     salt.to_string()
 }
 
+/// Derive a reproducible salt from the transaction fields and a nonce
+///
+/// Hashes source, destination, amount, fee, parent reference and `nonce`
+/// (the existing `salt` field is ignored) and maps the result into the same
+/// range as random salts, at or above the `MIN_SALT` complexity floor. The
+/// same logical transfer with the same nonce always gets the same salt, and
+/// so the same transaction hash; bump the nonce to send an identical
+/// transfer again.
+///
+/// # Arguments
+/// * `value` - Transaction fields to derive the salt from
+/// * `nonce` - Caller-chosen counter distinguishing repeated transfers
+///
+/// # Returns
+/// Salt value, at least `2^53 - 2^48`
+pub fn deterministic_salt(value: &CurrencyTransactionValue, nonce: u64) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(
+        format!(
+            "{}\n{}\n{}\n{}\n{}\n{}\n{}",
+            value.source,
+            value.destination,
+            value.amount,
+            value.fee,
+            value.parent.hash,
+            value.parent.ordinal,
+            nonce
+        )
+        .as_bytes(),
+    );
+    let digest = hasher.finalize();
+
+    let mut bytes = [0u8; 8];
+    bytes[2..].copy_from_slice(&digest[..6]);
+    MIN_SALT + u64::from_be_bytes(bytes)
+}
+
 /// Encode a currency transaction for hashing
-fn encode_transaction(tx: &CurrencyTransaction) -> String {
+fn encode_transaction(tx: &CurrencyTransaction) -> Result<String> {
+    encode_value(&tx.value)
+}
+
+fn encode_value(value: &CurrencyTransactionValue) -> Result<String> {
+    // A negative amount has no valid hex form; `{:x}` would print its
+    // two's complement and silently hash a different amount
+    if value.amount < 0 {
+        return Err(SdkError::InvalidAmount(format!(
+            "amount must not be negative, got {}",
+            value.amount
+        )));
+    }
+
     let parent_count = "2"; // Always 2 parents for v2
-    let source = &tx.value.source;
-    let destination = &tx.value.destination;
-    let amount_hex = format!("{:x}", tx.value.amount);
-    let parent_hash = &tx.value.parent.hash;
-    let ordinal = tx.value.parent.ordinal.to_string();
-    let fee = tx.value.fee.to_string();
+    let source = &value.source;
+    let destination = &value.destination;
+    let amount_hex = format!("{:x}", value.amount);
+    let parent_hash = &value.parent.hash;
+    let ordinal = value.parent.ordinal.to_string();
+    let fee = value.fee.to_string();
 
     // Convert salt to hex
-    let salt_int = tx.value.salt.parse::<BigUint>().unwrap();
-    let salt_hex = format!("{salt_int:x}");
+    let salt_hex = format!("{:x}", parse_salt(&value.salt)?);
 
     // Build encoded string (length-prefixed format)
-    format!(
+    Ok(format!(
         "{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}",
         parent_count,
         source.len(),
@@ -97,9 +228,29 @@ fn encode_transaction(tx: &CurrencyTransaction) -> String {
         fee,
         salt_hex.len(),
         salt_hex
-    )
+    ))
 }
 
+/// Parse a salt as a decimal integer
+///
+/// Salts may come from untrusted JSON, so only ASCII digits are accepted
+/// (BigUint's parser would also accept "+" and "_").
+fn parse_salt(salt: &str) -> Result<BigUint> {
+    if salt.is_empty() || !salt.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(SdkError::EncodingError(format!(
+            "salt `{salt}` is not a decimal integer"
+        )));
+    }
+    salt.parse::<BigUint>()
+        .map_err(|e| SdkError::EncodingError(format!("invalid salt: {e}")))
+}
+
+/// Kryo class/string marker that starts every serialized transaction
+const KRYO_STRING_MARKER: u8 = 0x03;
+
+/// Kryo flag byte present when references are enabled (v1 encoding)
+const KRYO_REFERENCES_FLAG: u8 = 0x01;
+
 /// Kryo serialization for transaction encoding
 fn kryo_serialize(msg: &str, set_references: bool) -> Vec<u8> {
     fn utf8_length(value: usize) -> Vec<u8> {
@@ -131,9 +282,9 @@ fn kryo_serialize(msg: &str, set_references: bool) -> Vec<u8> {
         }
     }
 
-    let mut result = vec![0x03];
+    let mut result = vec![KRYO_STRING_MARKER];
     if set_references {
-        result.push(0x01);
+        result.push(KRYO_REFERENCES_FLAG);
     }
 
     let length = msg.len() + 1;
@@ -153,7 +304,7 @@ fn sign_hash_internal(hash_hex: &str, private_key_hex: &str) -> Result<String> {
     let digest = &sha512_hash[..32];
 
     // Sign with ECDSA
-    let secp = Secp256k1::new();
+    let secp = SECP256K1;
     let secret_key = SecretKey::from_slice(&hex::decode(private_key_hex)?)?;
     let message = Message::from_digest_slice(digest)?;
     let signature = secp.sign_ecdsa(&message, &secret_key);
@@ -163,6 +314,24 @@ fn sign_hash_internal(hash_hex: &str, private_key_hex: &str) -> Result<String> {
 
 /// Verify a signature on a hash
 fn verify_hash_internal(public_key_hex: &str, hash_hex: &str, signature_hex: &str) -> bool {
+    check_hash_internal(
+        public_key_hex,
+        hash_hex,
+        signature_hex,
+        &VerifyOptions::default(),
+    )
+    .is_ok()
+}
+
+/// Verify a signature on a hash, classifying any failure
+fn check_hash_internal(
+    public_key_hex: &str,
+    hash_hex: &str,
+    signature_hex: &str,
+    options: &VerifyOptions,
+) -> std::result::Result<(), InvalidReason> {
+    let malformed = |e: SdkError| InvalidReason::Malformed(e.to_string());
+
     // Hash hex as UTF-8 -> SHA-512 -> truncate 32 bytes
     let hash_utf8 = hash_hex.as_bytes();
     let mut sha512_hasher = Sha512::new();
@@ -171,51 +340,97 @@ fn verify_hash_internal(public_key_hex: &str, hash_hex: &str, signature_hex: &st
     let digest = &sha512_hash[..32];
 
     // Parse public key and signature
-    let public_key_bytes = match hex::decode(public_key_hex) {
-        Ok(bytes) => bytes,
-        Err(_) => return false,
-    };
+    let public_key = parse_proof_key(public_key_hex)?;
+    let signature_bytes = hex::decode(signature_hex).map_err(|e| malformed(e.into()))?;
+    let mut signature =
+        secp256k1::ecdsa::Signature::from_der(&signature_bytes).map_err(|e| malformed(e.into()))?;
 
-    let public_key = match secp256k1::PublicKey::from_slice(&public_key_bytes) {
-        Ok(pk) => pk,
-        Err(_) => return false,
-    };
+    // Normalize signature to low-S to accept high-S signatures (BIP 62 compatibility)
+    // This ensures we accept signatures from other SDKs that may not normalize to low-S,
+    // unless the caller explicitly asked for high-S to be rejected
+    if is_high_s(&signature) {
+        if options.reject_high_s {
+            return Err(InvalidReason::HighS);
+        }
+        signature.normalize_s();
+    }
 
-    let signature_bytes = match hex::decode(signature_hex) {
-        Ok(bytes) => bytes,
-        Err(_) => return false,
-    };
+    let message = Message::from_digest_slice(digest).map_err(|e| malformed(e.into()))?;
 
-    let mut signature = match secp256k1::ecdsa::Signature::from_der(&signature_bytes) {
-        Ok(sig) => sig,
-        Err(_) => return false,
-    };
+    let secp = SECP256K1;
+    secp.verify_ecdsa(&message, &signature, &public_key)
+        .map_err(|_| InvalidReason::SignatureMismatch)
+}
 
-    // Normalize signature to low-S to accept high-S signatures (BIP 62 compatibility)
-    // This ensures we accept signatures from other SDKs that may not normalize to low-S
-    signature.normalize_s();
+/// Create a metagraph token transaction
+///
+/// `params` may carry `f64` token values or [`TokenAmount`]s.
+pub fn create_currency_transaction<A: Into<TokenAmount>>(
+    params: TransferParams<A>,
+    private_key: &str,
+    last_ref: TransactionReference,
+) -> Result<CurrencyTransaction> {
+    build_currency_transaction(None, params, private_key, last_ref, None)
+}
 
-    let message = match Message::from_digest_slice(digest) {
-        Ok(msg) => msg,
-        Err(_) => return false,
-    };
+/// Create a metagraph token transaction with a deterministic salt
+///
+/// Like `create_currency_transaction`, but the salt comes from
+/// `deterministic_salt(value, nonce)` instead of randomness, so the same
+/// transfer and nonce always produce the same transaction hash. Useful for
+/// reproducible tests and for deduplicating idempotent transfers.
+pub fn create_currency_transaction_with_nonce<A: Into<TokenAmount>>(
+    params: TransferParams<A>,
+    private_key: &str,
+    last_ref: TransactionReference,
+    nonce: u64,
+) -> Result<CurrencyTransaction> {
+    build_currency_transaction(None, params, private_key, last_ref, Some(nonce))
+}
 
-    let secp = Secp256k1::new();
-    secp.verify_ecdsa(&message, &signature, &public_key).is_ok()
+/// Create a token transaction from one address, signed by a delegate key
+///
+/// For delegated custody: `source` is the asset owner and is encoded as
+/// the transaction source, while the proof comes from `signer_private_key`.
+/// Whether the delegate may spend for `source` is decided by the node's
+/// delegation rules, which the SDK does not know, so such transactions fail
+/// the "proof from the source address" rule of `preflight` and are not
+/// considered valid by `verify_multisig` unless the delegate is authorized.
+/// `verify_currency_transaction` only checks that the proof is a valid
+/// signature.
+///
+/// # Arguments
+/// * `source` - DAG address of the asset owner
+/// * `params` - Destination, amount and fee
+/// * `signer_private_key` - Delegate's private key in hex format
+/// * `last_ref` - Last accepted transaction reference of `source`
+pub fn create_delegated_transaction<A: Into<TokenAmount>>(
+    source: &str,
+    params: TransferParams<A>,
+    signer_private_key: &str,
+    last_ref: TransactionReference,
+) -> Result<CurrencyTransaction> {
+    build_currency_transaction(Some(source), params, signer_private_key, last_ref, None)
 }
 
-/// Create a metagraph token transaction
-pub fn create_currency_transaction(
-    params: TransferParams,
+/// Build and sign a transaction, with a random salt unless a nonce is given
+/// and a source derived from the signing key unless one is given
+fn build_currency_transaction<A: Into<TokenAmount>>(
+    source: Option<&str>,
+    params: TransferParams<A>,
     private_key: &str,
     last_ref: TransactionReference,
+    nonce: Option<u64>,
 ) -> Result<CurrencyTransaction> {
-    // Get source address from private key
+    // Get source address from private key, unless signing for another address
     let secret_key = SecretKey::from_slice(&hex::decode(private_key)?)?;
-    let secp = Secp256k1::new();
-    let public_key = secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
+    let secp = SECP256K1;
+    let public_key = secp256k1::PublicKey::from_secret_key(secp, &secret_key);
     let public_key_hex = hex::encode(public_key.serialize_uncompressed());
-    let source = get_address(&public_key_hex);
+    let source = match source {
+        Some(source) => source.to_string(),
+        None => get_address(&public_key_hex),
+    };
 
     // Validate addresses
     if !is_valid_dag_address(&source) {
@@ -235,14 +450,15 @@ pub fn create_currency_transaction(
     }
 
     // Convert amounts to smallest units
-    let amount = token_to_units(params.amount);
-    let fee = token_to_units(params.fee);
+    let amount = params.amount.into().units();
+    let fee = params.fee.into().units();
 
     // Validate amounts
     if amount < 1 {
-        return Err(SdkError::InvalidAmount(
-            "Transfer amount must be greater than 1e-8".to_string(),
-        ));
+        return Err(SdkError::InvalidAmount(format!(
+            "Transfer amount must be greater than {:e}",
+            minimum_transfer(TOKEN_DECIMAL_PLACES)
+        )));
     }
     if fee < 0 {
         return Err(SdkError::InvalidAmount(
@@ -250,17 +466,20 @@ pub fn create_currency_transaction(
         ));
     }
 
-    // Generate salt
-    let salt = generate_salt();
-
     // Create transaction value
-    let tx_value = CurrencyTransactionValue {
+    let mut tx_value = CurrencyTransactionValue {
         source,
         destination: params.destination,
         amount,
         fee,
         parent: last_ref,
-        salt,
+        salt: String::new(),
+    };
+
+    // Generate salt
+    tx_value.salt = match nonce {
+        Some(nonce) => deterministic_salt(&tx_value, nonce).to_string(),
+        None => generate_salt(),
     };
 
     // Create signed transaction
@@ -270,7 +489,7 @@ pub fn create_currency_transaction(
     };
 
     // Encode and hash
-    let encoded = encode_transaction(&tx);
+    let encoded = encode_transaction(&tx)?;
     let serialized = kryo_serialize(&encoded, false);
     let mut hasher = Sha256::new();
     hasher.update(&serialized);
@@ -293,9 +512,70 @@ pub fn create_currency_transaction(
     Ok(tx)
 }
 
+/// Create a metagraph token transaction, rejecting a fee larger than the amount
+///
+/// A fee above the transfer amount is valid on-chain but is almost always a
+/// typo. This behaves like `create_currency_transaction` but returns
+/// `SdkError::InvalidAmount` when `fee > amount`; a fee equal to the amount
+/// is allowed.
+pub fn create_currency_transaction_safe<A: Into<TokenAmount>>(
+    params: TransferParams<A>,
+    private_key: &str,
+    last_ref: TransactionReference,
+) -> Result<CurrencyTransaction> {
+    let params = TransferParams {
+        destination: params.destination,
+        amount: params.amount.into(),
+        fee: params.fee.into(),
+    };
+
+    if params.fee > params.amount {
+        return Err(SdkError::InvalidAmount(format!(
+            "Fee {} exceeds transfer amount {}",
+            params.fee, params.amount
+        )));
+    }
+
+    create_currency_transaction(params, private_key, last_ref)
+}
+
+/// Create a metagraph token transaction, rejecting sub-unit precision
+///
+/// Behaves like `create_currency_transaction`, but returns
+/// `SdkError::InvalidAmount` if the amount or fee has more than 8 decimals
+/// instead of silently flooring it. Amounts are converted from their
+/// shortest decimal form, so e.g. `0.29` is exactly 29000000 units.
+///
+/// # Example
+/// ```
+/// use constellation_sdk::currency_transaction::create_currency_transaction_exact;
+/// use constellation_sdk::currency_types::{TransactionReference, TransferParams};
+/// use constellation_sdk::wallet::generate_key_pair;
+///
+/// let key_pair = generate_key_pair();
+/// let last_ref = TransactionReference { hash: "0".repeat(64), ordinal: 0 };
+/// let params = |amount| TransferParams { destination: generate_key_pair().address, amount, fee: 0.0 };
+///
+/// let tx = create_currency_transaction_exact(params(0.29), &key_pair.private_key, last_ref.clone()).unwrap();
+/// assert_eq!(tx.value.amount, 29_000_000);
+/// assert!(create_currency_transaction_exact(params(0.123456789), &key_pair.private_key, last_ref).is_err());
+/// ```
+pub fn create_currency_transaction_exact(
+    params: TransferParams<f64>,
+    private_key: &str,
+    last_ref: TransactionReference,
+) -> Result<CurrencyTransaction> {
+    let params = TransferParams {
+        destination: params.destination,
+        amount: TokenAmount::from_units(exact_units(params.amount)?),
+        fee: TokenAmount::from_units(exact_units(params.fee)?),
+    };
+    create_currency_transaction(params, private_key, last_ref)
+}
+
 /// Create multiple metagraph token transactions (batch)
-pub fn create_currency_transaction_batch(
-    transfers: Vec<TransferParams>,
+pub fn create_currency_transaction_batch<A: Into<TokenAmount>>(
+    transfers: Vec<TransferParams<A>>,
     private_key: &str,
     last_ref: TransactionReference,
 ) -> Result<Vec<CurrencyTransaction>> {
@@ -306,7 +586,7 @@ pub fn create_currency_transaction_batch(
         let tx = create_currency_transaction(transfer, private_key, current_ref.clone())?;
 
         // Calculate hash for next transaction's parent reference
-        let hash_result = hash_currency_transaction(&tx);
+        let hash_result = hash_currency_transaction(&tx)?;
 
         // Update reference for next transaction
         current_ref = TransactionReference {
@@ -320,13 +600,153 @@ pub fn create_currency_transaction_batch(
     Ok(transactions)
 }
 
+/// Check that transactions form one unbroken chain from a single source
+///
+/// Each transaction after the first must reference the previous one: its
+/// parent hash is the previous transaction's hash, and its parent ordinal
+/// is one more than the previous parent ordinal. Signatures are not checked;
+/// use `verify_currency_transaction` for that.
+///
+/// # Arguments
+/// * `transactions` - Transactions in chain order, e.g. from
+///   `create_currency_transaction_batch`
+///
+/// # Returns
+/// `SdkError::ChainBroken` with the index of the first transaction that
+/// doesn't follow from its predecessor
+///
+/// # Example
+/// ```
+/// use constellation_sdk::currency_transaction::{create_currency_transaction_batch, verify_chain};
+/// use constellation_sdk::currency_types::{TransactionReference, TransferParams};
+/// use constellation_sdk::wallet::generate_key_pair;
+///
+/// let key_pair = generate_key_pair();
+/// let transfers = (1..=3)
+///     .map(|i| TransferParams { destination: generate_key_pair().address, amount: i as f64, fee: 0.0 })
+///     .collect();
+/// let last_ref = TransactionReference { hash: "a".repeat(64), ordinal: 5 };
+///
+/// let mut txns = create_currency_transaction_batch(transfers, &key_pair.private_key, last_ref).unwrap();
+/// assert!(verify_chain(&txns).is_ok());
+///
+/// txns.swap(1, 2);
+/// assert!(verify_chain(&txns).is_err());
+/// ```
+pub fn verify_chain(transactions: &[CurrencyTransaction]) -> Result<()> {
+    let broken = |index, reason: String| SdkError::ChainBroken { index, reason };
+
+    for (i, pair) in transactions.windows(2).enumerate() {
+        let (prev, next) = (&pair[0], &pair[1]);
+        let index = i + 1;
+
+        if next.value.source != transactions[0].value.source {
+            return Err(broken(
+                index,
+                format!(
+                    "source {} differs from {}",
+                    next.value.source, transactions[0].value.source
+                ),
+            ));
+        }
+
+        let prev_hash = hash_currency_transaction(prev).map_err(|e| broken(i, e.to_string()))?;
+        if !next
+            .value
+            .parent
+            .hash
+            .eq_ignore_ascii_case(&prev_hash.value)
+        {
+            return Err(broken(
+                index,
+                format!(
+                    "parent hash {} does not match previous hash {}",
+                    next.value.parent.hash, prev_hash.value
+                ),
+            ));
+        }
+
+        let expected_ordinal = prev.value.parent.ordinal + 1;
+        if next.value.parent.ordinal != expected_ordinal {
+            return Err(broken(
+                index,
+                format!(
+                    "parent ordinal {} does not follow previous ordinal {}",
+                    next.value.parent.ordinal, expected_ordinal
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Create a chained batch of transfers from one source to many destinations
+///
+/// Every destination is validated before anything is signed, so a single bad
+/// entry fails the whole batch and no transactions are returned.
+///
+/// # Arguments
+/// * `source_private_key` - Private key of the paying address
+/// * `destinations` - `(address, amount)` pairs, in the order they should be chained
+/// * `fee` - Fee applied to each transfer
+/// * `last_ref` - Reference to the source's last accepted transaction
+///
+/// # Returns
+/// One signed transaction per destination, each referencing the previous one
+pub fn build_fanout(
+    source_private_key: &str,
+    destinations: &[(String, f64)],
+    fee: f64,
+    last_ref: TransactionReference,
+) -> Result<Vec<CurrencyTransaction>> {
+    let key_pair = key_pair_from_private_key(source_private_key)?;
+
+    if token_to_units(fee) < 0 {
+        return Err(SdkError::InvalidAmount(
+            "Fee must be greater than or equal to zero".to_string(),
+        ));
+    }
+    for (index, (destination, amount)) in destinations.iter().enumerate() {
+        if !is_valid_dag_address(destination) {
+            return Err(SdkError::InvalidAddress(format!(
+                "Invalid destination address at index {}: {}",
+                index, destination
+            )));
+        }
+        if addresses_equal(destination, &key_pair.address) {
+            return Err(SdkError::InvalidAddress(format!(
+                "Destination at index {} is the source address",
+                index
+            )));
+        }
+        if token_to_units(*amount) < 1 {
+            return Err(SdkError::InvalidAmount(format!(
+                "Transfer amount at index {} must be greater than {:e}",
+                index,
+                minimum_transfer(TOKEN_DECIMAL_PLACES)
+            )));
+        }
+    }
+
+    let transfers = destinations
+        .iter()
+        .map(|(destination, amount)| TransferParams {
+            destination: destination.clone(),
+            amount: *amount,
+            fee,
+        })
+        .collect();
+    create_currency_transaction_batch(transfers, source_private_key, last_ref)
+}
+
 /// Add a signature to an existing currency transaction (for multi-sig)
 pub fn sign_currency_transaction(
     transaction: &CurrencyTransaction,
     private_key: &str,
 ) -> Result<CurrencyTransaction> {
     // Encode and hash
-    let encoded = encode_transaction(transaction);
+    let encoded = encode_transaction(transaction)?;
     let serialized = kryo_serialize(&encoded, false);
     let mut hasher = Sha256::new();
     hasher.update(&serialized);
@@ -338,8 +758,8 @@ pub fn sign_currency_transaction(
 
     // Get public key
     let secret_key = SecretKey::from_slice(&hex::decode(private_key)?)?;
-    let secp = Secp256k1::new();
-    let public_key = secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
+    let secp = SECP256K1;
+    let public_key = secp256k1::PublicKey::from_secret_key(secp, &secret_key);
     let public_key_hex = hex::encode(public_key.serialize_uncompressed());
 
     // Verify signature
@@ -364,10 +784,86 @@ pub fn sign_currency_transaction(
     })
 }
 
+/// Sign a pre-computed currency transaction hash
+///
+/// Decouples hashing from signing: the hash can be computed elsewhere (e.g.
+/// with `hash_currency_transaction` on an online machine) and signed offline.
+///
+/// # Arguments
+/// * `hash_hex` - Transaction hash as 64-character hex string
+/// * `private_key` - Private key in hex format
+///
+/// # Returns
+/// SignatureProof with public key ID (without 04 prefix) and signature
+///
+/// # Example
+/// ```
+/// use constellation_sdk::currency_transaction::{sign_transaction_hash, verify_transaction_hash};
+/// use constellation_sdk::wallet::generate_key_pair;
+///
+/// let key_pair = generate_key_pair();
+/// let hash_hex = "a".repeat(64);
+/// let proof = sign_transaction_hash(&hash_hex, &key_pair.private_key).unwrap();
+/// assert!(verify_transaction_hash(&hash_hex, &proof));
+/// ```
+pub fn sign_transaction_hash(hash_hex: &str, private_key: &str) -> Result<SignatureProof> {
+    let signature = sign_hash_internal(hash_hex, private_key)?;
+
+    let secret_key = SecretKey::from_slice(&hex::decode(private_key)?)?;
+    let secp = SECP256K1;
+    let public_key = secp256k1::PublicKey::from_secret_key(secp, &secret_key);
+    let public_key_hex = hex::encode(public_key.serialize_uncompressed());
+
+    Ok(SignatureProof {
+        id: public_key_hex[2..].to_string(), // Remove '04' prefix
+        signature,
+    })
+}
+
+/// Verify a proof against a pre-computed currency transaction hash
+///
+/// # Arguments
+/// * `hash_hex` - Transaction hash as 64-character hex string
+/// * `proof` - Signature proof with public key ID (without 04 prefix)
+///
+/// # Returns
+/// true if the signature is valid for the hash
+pub fn verify_transaction_hash(hash_hex: &str, proof: &SignatureProof) -> bool {
+    let public_key = format!("04{}", proof.id); // Add back '04' prefix
+    verify_hash_internal(&public_key, hash_hex, &proof.signature)
+}
+
 /// Verify all signatures on a currency transaction
+///
+/// Each key counts once: a valid proof from a key that already has a valid
+/// proof is reported as `InvalidReason::DuplicateSigner` and makes the
+/// transaction invalid, so `valid_proofs.len()` is the number of distinct
+/// signers.
 pub fn verify_currency_transaction(transaction: &CurrencyTransaction) -> VerificationResult {
+    verify_currency_transaction_with_options(transaction, &VerifyOptions::default())
+}
+
+/// Verify all signatures on a currency transaction with explicit verification options
+pub fn verify_currency_transaction_with_options(
+    transaction: &CurrencyTransaction,
+    options: &VerifyOptions,
+) -> VerificationResult {
     // Encode and hash
-    let encoded = encode_transaction(transaction);
+    let encoded = match encode_transaction(transaction) {
+        Ok(encoded) => encoded,
+        Err(e) => {
+            return VerificationResult {
+                is_valid: false,
+                valid_proofs: vec![],
+                invalid_proofs: transaction.proofs.clone(),
+                invalid_reasons: vec![
+                    InvalidReason::Serialization(e.to_string());
+                    transaction.proofs.len()
+                ],
+                matched_mode: None,
+            }
+        }
+    };
     let serialized = kryo_serialize(&encoded, false);
     let mut hasher = Sha256::new();
     hasher.update(&serialized);
@@ -376,16 +872,29 @@ pub fn verify_currency_transaction(transaction: &CurrencyTransaction) -> Verific
 
     let mut valid_proofs = Vec::new();
     let mut invalid_proofs = Vec::new();
+    let mut invalid_reasons = Vec::new();
+    let mut signers: Vec<String> = Vec::new();
 
     // Verify each proof
     for proof in &transaction.proofs {
         let public_key = format!("04{}", proof.id); // Add back '04' prefix
-        let is_valid = verify_hash_internal(&public_key, &hash_hex, &proof.signature);
 
-        if is_valid {
-            valid_proofs.push(proof.clone());
-        } else {
-            invalid_proofs.push(proof.clone());
+        // Normalized like verify_multisig, so any encoding of a key counts once
+        let signer = signer_key(&proof.id);
+
+        match check_hash_internal(&public_key, &hash_hex, &proof.signature, options) {
+            Ok(()) if signer.as_ref().is_some_and(|key| signers.contains(key)) => {
+                invalid_proofs.push(proof.clone());
+                invalid_reasons.push(InvalidReason::DuplicateSigner);
+            }
+            Ok(()) => {
+                signers.extend(signer);
+                valid_proofs.push(proof.clone());
+            }
+            Err(reason) => {
+                invalid_proofs.push(proof.clone());
+                invalid_reasons.push(reason);
+            }
         }
     }
 
@@ -393,36 +902,551 @@ pub fn verify_currency_transaction(transaction: &CurrencyTransaction) -> Verific
         is_valid: invalid_proofs.is_empty() && !valid_proofs.is_empty(),
         valid_proofs,
         invalid_proofs,
+        invalid_reasons,
+        matched_mode: None,
+    }
+}
+
+/// Verify an m-of-n multi-signature currency transaction
+///
+/// Unlike `verify_currency_transaction`, not every proof has to be valid:
+/// the transaction is valid once at least `required` distinct authorized
+/// signers have valid proofs. Proofs with valid signatures from signers
+/// outside `authorized_ids` are reported as `InvalidReason::Unauthorized`.
+///
+/// # Arguments
+/// * `transaction` - Signed currency transaction
+/// * `required` - Minimum number of distinct authorized signers (at least 1)
+/// * `authorized_ids` - Authorized public keys (hex, compressed or
+///   uncompressed, with or without 04 prefix)
+///
+/// # Returns
+/// VerificationResult whose `valid_proofs` are the authorized valid proofs
+pub fn verify_multisig(
+    transaction: &CurrencyTransaction,
+    required: usize,
+    authorized_ids: &[&str],
+) -> VerificationResult {
+    let authorized: Vec<String> = authorized_ids
+        .iter()
+        .filter_map(|id| signer_key(id))
+        .collect();
+    let result = verify_currency_transaction(transaction);
+
+    let mut valid_proofs = Vec::new();
+    let mut invalid_proofs = result.invalid_proofs;
+    let mut invalid_reasons = result.invalid_reasons;
+    let mut signers: Vec<String> = Vec::new();
+
+    for proof in result.valid_proofs {
+        match signer_key(&proof.id).filter(|key| authorized.contains(key)) {
+            Some(key) => {
+                if !signers.contains(&key) {
+                    signers.push(key);
+                }
+                valid_proofs.push(proof);
+            }
+            None => {
+                invalid_proofs.push(proof);
+                invalid_reasons.push(InvalidReason::Unauthorized);
+            }
+        }
+    }
+
+    VerificationResult {
+        is_valid: signers.len() >= required.max(1),
+        valid_proofs,
+        invalid_proofs,
+        invalid_reasons,
+        matched_mode: None,
     }
 }
 
 /// Encode a currency transaction for hashing
-pub fn encode_currency_transaction(transaction: &CurrencyTransaction) -> String {
+///
+/// # Errors
+/// Returns `SdkError::InvalidAmount` if the amount is negative, or
+/// `SdkError::EncodingError` if the salt is not a decimal integer
+pub fn encode_currency_transaction(transaction: &CurrencyTransaction) -> Result<String> {
     encode_transaction(transaction)
 }
 
+/// Decode the length-prefixed encoded string of a transaction value
+///
+/// Reverses `encode_currency_transaction`: after the parent count `2`, each
+/// field (source, destination, amount hex, parent hash, ordinal, fee, salt
+/// hex) is its decimal byte length followed by its content. Lengths are not
+/// delimited, so a field starting with a digit could in principle be split
+/// more than one way; every split is tried and each field must be in the
+/// exact form the encoder writes. The input must decode exactly one way.
+///
+/// # Arguments
+/// * `encoded` - Encoded string, e.g. from `encode_currency_transaction`
+///
+/// # Returns
+/// The transaction value, or `SdkError::EncodingError` if the input is
+/// malformed or ambiguous
+///
+/// # Example
+/// ```
+/// use constellation_sdk::currency_transaction::{
+///     create_currency_transaction, decode_encoded_string, encode_currency_transaction,
+/// };
+/// use constellation_sdk::currency_types::{TransactionReference, TransferParams};
+/// use constellation_sdk::wallet::generate_key_pair;
+///
+/// let key_pair = generate_key_pair();
+/// let tx = create_currency_transaction(
+///     TransferParams { destination: generate_key_pair().address, amount: 1.5, fee: 0.0 },
+///     &key_pair.private_key,
+///     TransactionReference { hash: "a".repeat(64), ordinal: 7 },
+/// )
+/// .unwrap();
+///
+/// let encoded = encode_currency_transaction(&tx).unwrap();
+/// assert_eq!(decode_encoded_string(&encoded).unwrap(), tx.value);
+/// ```
+pub fn decode_encoded_string(encoded: &str) -> Result<CurrencyTransactionValue> {
+    let fields = encoded.strip_prefix('2').ok_or_else(|| {
+        SdkError::EncodingError("encoded string must start with parent count 2".to_string())
+    })?;
+
+    let mut parses = Vec::new();
+    split_encoded_fields(fields, &mut Vec::new(), &mut parses);
+    let fields = match parses.len() {
+        1 => parses.remove(0),
+        0 => {
+            return Err(SdkError::EncodingError(
+                "encoded string does not split into 7 well-formed length-prefixed fields"
+                    .to_string(),
+            ))
+        }
+        _ => {
+            return Err(SdkError::EncodingError(
+                "encoded string splits into fields in more than one way".to_string(),
+            ))
+        }
+    };
+
+    let number = |field: &str| -> Result<i64> {
+        field
+            .parse()
+            .map_err(|e| SdkError::EncodingError(format!("invalid number {field:?}: {e}")))
+    };
+    let amount = u64::from_str_radix(fields[2], 16)
+        .map_err(|e| SdkError::EncodingError(format!("invalid amount hex: {e}")))?;
+    let salt = BigUint::parse_bytes(fields[6].as_bytes(), 16)
+        .ok_or_else(|| SdkError::EncodingError("invalid salt hex".to_string()))?;
+
+    Ok(CurrencyTransactionValue {
+        source: fields[0].to_string(),
+        destination: fields[1].to_string(),
+        amount: i64::try_from(amount)
+            .map_err(|_| SdkError::EncodingError(format!("amount {amount} out of range")))?,
+        fee: number(fields[5])?,
+        parent: TransactionReference {
+            hash: fields[3].to_string(),
+            ordinal: number(fields[4])?,
+        },
+        salt: salt.to_string(),
+    })
+}
+
+/// Collect every split of `rest` into the remaining encoded fields
+///
+/// Stops after two complete splits, which is enough to detect ambiguity.
+fn split_encoded_fields<'a>(rest: &'a str, fields: &mut Vec<&'a str>, out: &mut Vec<Vec<&'a str>>) {
+    if fields.len() == 7 {
+        if rest.is_empty() {
+            out.push(fields.clone());
+        }
+        return;
+    }
+
+    // Lengths are positive and written without leading zeros
+    if rest.starts_with('0') {
+        return;
+    }
+    let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+    for prefix in 1..=digits.min(6) {
+        if out.len() > 1 {
+            return;
+        }
+        let Ok(len) = rest[..prefix].parse::<usize>() else {
+            continue;
+        };
+        let Some(field) = rest.get(prefix..prefix + len) else {
+            continue;
+        };
+        if is_encoded_field(fields.len(), field) {
+            fields.push(field);
+            split_encoded_fields(&rest[prefix + len..], fields, out);
+            fields.pop();
+        }
+    }
+}
+
+/// Whether `field` is in the exact form `encode_value` writes at `index`
+fn is_encoded_field(index: usize, field: &str) -> bool {
+    let lower_hex = |s: &str| s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'));
+    let canonical_hex = |s: &str| lower_hex(s) && (s == "0" || !s.starts_with('0'));
+    match index {
+        // Addresses
+        0 | 1 => !field.is_empty(),
+        // Amount, as i64 in hex
+        2 => canonical_hex(field) && field.len() <= 16,
+        // Parent hash
+        3 => lower_hex(field),
+        // Ordinal and fee
+        4 | 5 => field.parse::<i64>().is_ok_and(|n| n.to_string() == field),
+        // Salt
+        _ => canonical_hex(field),
+    }
+}
+
+/// Estimate the serialized byte size of a currency transaction
+///
+/// Returns the length of the kryo-serialized transaction value plus the raw
+/// (hex-decoded) bytes of each proof's public key id and DER signature.
+/// Multiply by a metagraph's fee-per-byte rate to estimate fees client-side.
+///
+/// # Errors
+/// Returns `SdkError::InvalidAmount` if the amount is negative, or
+/// `SdkError::EncodingError` if the salt is not a decimal integer
+pub fn encoded_size(transaction: &CurrencyTransaction) -> Result<usize> {
+    let encoded = encode_transaction(transaction)?;
+    let serialized = kryo_serialize(&encoded, false);
+
+    let proofs_size: usize = transaction
+        .proofs
+        .iter()
+        .map(|proof| proof.id.len() / 2 + proof.signature.len() / 2)
+        .sum();
+
+    Ok(serialized.len() + proofs_size)
+}
+
+/// Check the kryo header of a serialized transaction and report its version
+///
+/// Inspects the leading bytes of the kryo wire form: `0x03` followed by a
+/// length byte (high bit set) is v2, `0x03 0x01` is v1 (references enabled).
+/// Use this to detect version mismatches before verifying against the v2 scheme.
+///
+/// # Errors
+/// Returns an error if the bytes do not start with a known kryo header
+pub fn check_encoding_compatibility(tx_wire: &[u8]) -> Result<TransactionVersion> {
+    match tx_wire {
+        [KRYO_STRING_MARKER, KRYO_REFERENCES_FLAG, length, ..] if length & 0x80 != 0 => {
+            Ok(TransactionVersion::V1)
+        }
+        [KRYO_STRING_MARKER, length, ..] if length & 0x80 != 0 => Ok(TransactionVersion::V2),
+        [] => Err(SdkError::SerializationError(
+            "Empty transaction encoding".to_string(),
+        )),
+        _ => Err(SdkError::SerializationError(format!(
+            "Unknown kryo header: {}",
+            hex::encode(&tx_wire[..tx_wire.len().min(2)])
+        ))),
+    }
+}
+
 /// Hash a currency transaction
-pub fn hash_currency_transaction(transaction: &CurrencyTransaction) -> Hash {
-    let encoded = encode_transaction(transaction);
+///
+/// # Errors
+/// Returns `SdkError::InvalidAmount` if the amount is negative, or
+/// `SdkError::EncodingError` if the salt is not a decimal integer
+pub fn hash_currency_transaction(transaction: &CurrencyTransaction) -> Result<Hash> {
+    hash_value(&transaction.value)
+}
+
+/// Hash a transaction value as if its parent were `reference`
+///
+/// Only the hash is recomputed; nothing is signed. Since each transaction's
+/// parent is the previous one's hash, this lets a chain's hashes be
+/// precomputed as a function of a head reference that isn't known yet.
+///
+/// # Arguments
+/// * `value` - Transaction value (its own `parent` is ignored)
+/// * `reference` - Parent reference to hash with
+///
+/// # Returns
+/// The hash the transaction would have with that parent, or
+/// `SdkError::InvalidAmount` if the amount is negative
+///
+/// # Example
+/// ```
+/// use constellation_sdk::currency_transaction::{
+///     create_currency_transaction, hash_currency_transaction, hash_with_reference,
+/// };
+/// use constellation_sdk::currency_types::{TransactionReference, TransferParams};
+/// use constellation_sdk::wallet::generate_key_pair;
+///
+/// let key_pair = generate_key_pair();
+/// let params = TransferParams { destination: generate_key_pair().address, amount: 1.0, fee: 0.0 };
+/// let reference = TransactionReference { hash: "a".repeat(64), ordinal: 3 };
+/// let tx = create_currency_transaction(params, &key_pair.private_key, reference.clone()).unwrap();
+///
+/// assert_eq!(
+///     hash_with_reference(&tx.value, &reference).unwrap(),
+///     hash_currency_transaction(&tx).unwrap()
+/// );
+/// ```
+pub fn hash_with_reference(
+    value: &CurrencyTransactionValue,
+    reference: &TransactionReference,
+) -> Result<Hash> {
+    hash_value(&CurrencyTransactionValue {
+        parent: reference.clone(),
+        ..value.clone()
+    })
+}
+
+fn hash_value(value: &CurrencyTransactionValue) -> Result<Hash> {
+    let encoded = encode_value(value)?;
     let serialized = kryo_serialize(&encoded, false);
     let mut hasher = Sha256::new();
     hasher.update(&serialized);
     let hash_bytes = hasher.finalize();
 
-    Hash {
+    Ok(Hash {
         value: hex::encode(hash_bytes),
         bytes: hash_bytes.to_vec(),
+    })
+}
+
+/// Run the node's pre-acceptance checks on a transaction locally
+///
+/// Checks, in one pass:
+/// * source and destination are valid, distinct DAG addresses
+/// * amount is at least 1 unit and fee is not negative
+/// * parent hash is 64 hex characters and the parent ordinal is not negative
+/// * salt is a decimal integer of at least `2^53 - 2^48` (the `MIN_SALT` floor)
+/// * there is at least one proof, every proof has a 128-character public key
+///   id and a well-formed DER signature, and one proof is from the source
+///
+/// Signatures are not verified; use `verify_currency_transaction` for that.
+///
+/// # Arguments
+/// * `tx` - Transaction to check
+///
+/// # Returns
+/// `SdkError::PreflightFailed` listing every failed rule
+///
+/// # Example
+/// ```
+/// use constellation_sdk::currency_transaction::{create_currency_transaction, preflight};
+/// use constellation_sdk::currency_types::{TransactionReference, TransferParams};
+/// use constellation_sdk::wallet::generate_key_pair;
+///
+/// let key_pair = generate_key_pair();
+/// let params = TransferParams {
+///     destination: generate_key_pair().address,
+///     amount: 1.5,
+///     fee: 0.0,
+/// };
+/// let last_ref = TransactionReference { hash: "0".repeat(64), ordinal: 0 };
+/// let mut tx = create_currency_transaction(params, &key_pair.private_key, last_ref).unwrap();
+/// assert!(preflight(&tx).is_ok());
+///
+/// tx.value.salt = "1".to_string();
+/// assert!(preflight(&tx).unwrap_err().to_string().contains("salt"));
+/// ```
+pub fn preflight(tx: &CurrencyTransaction) -> Result<()> {
+    let value = &tx.value;
+    let mut failures = Vec::new();
+
+    if !is_valid_dag_address(&value.source) {
+        failures.push(format!("invalid source address `{}`", value.source));
+    }
+    if !is_valid_dag_address(&value.destination) {
+        failures.push(format!(
+            "invalid destination address `{}`",
+            value.destination
+        ));
+    }
+    if value.source == value.destination {
+        failures.push("source and destination are the same".to_string());
+    }
+    if value.amount < 1 {
+        failures.push(format!("amount {} is below 1 unit", value.amount));
+    }
+    if value.fee < 0 {
+        failures.push(format!("fee {} is negative", value.fee));
+    }
+    if value.parent.hash.len() != 64 || !value.parent.hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        failures.push("parent hash is not 64 hex characters".to_string());
     }
+    if value.parent.ordinal < 0 {
+        failures.push(format!(
+            "parent ordinal {} is negative",
+            value.parent.ordinal
+        ));
+    }
+    // Same rule as encoding, so a transaction that passes can be hashed
+    match parse_salt(&value.salt) {
+        Ok(salt) if salt >= BigUint::from(MIN_SALT) => {}
+        Ok(_) => failures.push(format!(
+            "salt {} is below the minimum complexity",
+            value.salt
+        )),
+        Err(_) => failures.push(format!("salt `{}` is not a decimal integer", value.salt)),
+    }
+
+    if tx.proofs.is_empty() {
+        failures.push("transaction has no proofs".to_string());
+    }
+    let mut signed_by_source = false;
+    for (i, proof) in tx.proofs.iter().enumerate() {
+        match parse_proof_key(&proof.id) {
+            Ok(_) if proof.id.len() == 128 => {
+                signed_by_source |= get_address(&proof.id) == value.source;
+            }
+            _ => failures.push(format!("proof {i} id is not a 128-character public key")),
+        }
+        if let Err(e) = parse_der_signature(&proof.signature) {
+            failures.push(format!("proof {i}: {e}"));
+        }
+    }
+    if !tx.proofs.is_empty() && !signed_by_source {
+        failures.push("no proof is from the source address".to_string());
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(SdkError::PreflightFailed(failures))
+    }
+}
+
+/// Identifying fields of a transaction: `(source, parent hash, salt)`
+pub type ReplayKey = (String, String, String);
+
+/// Storage for the transaction keys a `ReplayGuard` has seen
+///
+/// Implement this to back the guard with a shared or persistent store.
+/// `HashSet<ReplayKey>` is the in-memory default.
+pub trait ReplayStore {
+    /// Record `key`, returning false if it was already recorded
+    fn insert(&mut self, key: ReplayKey) -> Result<bool>;
+}
+
+impl ReplayStore for HashSet<ReplayKey> {
+    fn insert(&mut self, key: ReplayKey) -> Result<bool> {
+        Ok(HashSet::insert(self, key))
+    }
+}
+
+/// Guard against submitting the same transaction twice
+///
+/// A transaction is identified by its source, parent hash and salt, so a
+/// re-signed copy of a seen transaction is still caught, while a new
+/// transaction reusing the parent reference is not.
+///
+/// # Example
+/// ```
+/// use constellation_sdk::currency_transaction::{create_currency_transaction, ReplayGuard};
+/// use constellation_sdk::currency_types::{TransactionReference, TransferParams};
+/// use constellation_sdk::wallet::generate_key_pair;
+///
+/// let key_pair = generate_key_pair();
+/// let params = TransferParams { destination: generate_key_pair().address, amount: 1.0, fee: 0.0 };
+/// let last_ref = TransactionReference { hash: "0".repeat(64), ordinal: 0 };
+/// let tx = create_currency_transaction(params, &key_pair.private_key, last_ref).unwrap();
+///
+/// let mut guard = ReplayGuard::new();
+/// assert!(guard.check_and_record(&tx).is_ok());
+/// assert!(guard.check_and_record(&tx).is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ReplayGuard<S = HashSet<ReplayKey>> {
+    store: S,
+}
+
+impl ReplayGuard {
+    /// Create a guard backed by an in-memory set
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S: ReplayStore> ReplayGuard<S> {
+    /// Create a guard backed by a custom store
+    pub fn with_store(store: S) -> Self {
+        Self { store }
+    }
+
+    /// Record a transaction, failing if it was recorded before
+    ///
+    /// # Returns
+    /// `SdkError::ReplayDetected` if the transaction was already seen, or the
+    /// store's error if recording failed
+    pub fn check_and_record(&mut self, tx: &CurrencyTransaction) -> Result<()> {
+        let key = (
+            tx.value.source.clone(),
+            tx.value.parent.hash.clone(),
+            tx.value.salt.clone(),
+        );
+        if self.store.insert(key)? {
+            Ok(())
+        } else {
+            Err(SdkError::ReplayDetected(format!(
+                "transaction from {} with parent {} and salt {} was already seen",
+                tx.value.source, tx.value.parent.hash, tx.value.salt
+            )))
+        }
+    }
+
+    /// The underlying store
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+}
+
+/// Check that a claimed transaction hash matches the given transaction fields
+///
+/// Rebuilds the v2 encoding from the component fields and salt, hashes it,
+/// and compares against the claimed hash (case-insensitive hex). Useful for
+/// confirming a hash reported by an indexer before trusting it.
+///
+/// # Returns
+/// true if the hash matches; false on mismatch or if the salt is not a decimal integer
+pub fn verify_claimed_hash(
+    partial_value: &PartialTransactionValue,
+    salt: &str,
+    claimed_hash: &str,
+) -> bool {
+    if salt.is_empty() || salt.parse::<BigUint>().is_err() {
+        return false;
+    }
+
+    let transaction = Signed {
+        value: CurrencyTransactionValue {
+            source: partial_value.source.clone(),
+            destination: partial_value.destination.clone(),
+            amount: partial_value.amount,
+            fee: partial_value.fee,
+            parent: partial_value.parent.clone(),
+            salt: salt.to_string(),
+        },
+        proofs: vec![],
+    };
+
+    hash_currency_transaction(&transaction)
+        .is_ok_and(|hash| hash.value.eq_ignore_ascii_case(claimed_hash))
 }
 
 /// Get transaction reference from a currency transaction
+///
+/// # Errors
+/// Returns `SdkError::InvalidAmount` if the amount is negative, or
+/// `SdkError::EncodingError` if the salt is not a decimal integer
 pub fn get_transaction_reference(
     transaction: &CurrencyTransaction,
     ordinal: i64,
-) -> TransactionReference {
-    let hash_result = hash_currency_transaction(transaction);
-    TransactionReference {
+) -> Result<TransactionReference> {
+    let hash_result = hash_currency_transaction(transaction)?;
+    Ok(TransactionReference {
         hash: hash_result.value,
         ordinal,
-    }
+    })
 }
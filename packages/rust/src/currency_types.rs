@@ -2,10 +2,13 @@
 
 use serde::{Deserialize, Deserializer, Serialize};
 
-use crate::types::Signed;
+use std::fmt;
+
+use crate::currency_transaction::{token_to_units, units_to_token};
+use crate::types::{Result, SdkError, Signed};
 
 /// Custom deserializer for salt field that accepts both number and string
-fn deserialize_salt<'de, D>(deserializer: D) -> Result<String, D::Error>
+fn deserialize_salt<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -26,6 +29,18 @@ where
 /// Same as DAG_DECIMALS from dag4.js
 pub const TOKEN_DECIMALS: f64 = 1e-8;
 
+/// Number of decimal places in a token amount, matching `TOKEN_DECIMALS`
+pub const TOKEN_DECIMAL_PLACES: u32 = 8;
+
+/// Currency transaction encoding version, as indicated by the kryo header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionVersion {
+    /// v1 encoding (kryo header `0x03 0x01`, references enabled)
+    V1,
+    /// v2 encoding (kryo header `0x03`, no references)
+    V2,
+}
+
 /// Reference to a previous transaction for chaining
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TransactionReference {
@@ -35,6 +50,76 @@ pub struct TransactionReference {
     pub ordinal: i64,
 }
 
+/// How deep `from_node_value` searches envelopes for a reference
+const MAX_REFERENCE_DEPTH: usize = 4;
+
+impl TransactionReference {
+    /// Parse a node's last-reference response
+    ///
+    /// Accepts the bare `{"hash": ..., "ordinal": ...}` shape as well as
+    /// responses that wrap it in an envelope (e.g. `{"data": {...}}`), use
+    /// different key casing (`Hash`, `ORDINAL`), or encode the ordinal as a
+    /// string.
+    ///
+    /// # Arguments
+    /// * `json` - Response body text
+    ///
+    /// # Returns
+    /// The transaction reference, or `SdkError::SerializationError`
+    ///
+    /// # Example
+    /// ```
+    /// use constellation_sdk::TransactionReference;
+    ///
+    /// let hash = "a".repeat(64);
+    /// let body = format!(r#"{{"data": {{"Hash": "{hash}", "Ordinal": "5"}}}}"#);
+    /// let reference = TransactionReference::from_node_json(&body).unwrap();
+    /// assert_eq!(reference, TransactionReference { hash, ordinal: 5 });
+    /// ```
+    pub fn from_node_json(json: &str) -> Result<Self> {
+        Self::from_node_value(&serde_json::from_str(json)?)
+    }
+
+    /// Parse a node's last-reference response from a JSON value
+    ///
+    /// See `from_node_json` for the accepted shapes.
+    pub fn from_node_value(value: &serde_json::Value) -> Result<Self> {
+        find_reference(value, MAX_REFERENCE_DEPTH).ok_or_else(|| {
+            SdkError::SerializationError(
+                "Response does not contain a transaction reference".to_string(),
+            )
+        })
+    }
+}
+
+/// Find an object with `hash` and `ordinal` keys in `value` or its nested envelopes
+fn find_reference(value: &serde_json::Value, depth: usize) -> Option<TransactionReference> {
+    let object = value.as_object()?;
+
+    let field = |name: &str| {
+        object
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v)
+    };
+    if let (Some(hash), Some(ordinal)) = (field("hash"), field("ordinal")) {
+        let hash = hash.as_str()?.to_string();
+        let ordinal = match ordinal {
+            serde_json::Value::Number(n) => n.as_i64()?,
+            serde_json::Value::String(s) => s.parse().ok()?,
+            _ => return None,
+        };
+        return Some(TransactionReference { hash, ordinal });
+    }
+
+    if depth == 0 {
+        return None;
+    }
+    object
+        .values()
+        .find_map(|nested| find_reference(nested, depth - 1))
+}
+
 /// Currency transaction value structure (v2)
 /// Contains the actual transaction data before signing
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -54,18 +139,131 @@ pub struct CurrencyTransactionValue {
     pub salt: String,
 }
 
+/// Transaction fields without the salt
+///
+/// Used to check a claimed transaction hash from its component fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialTransactionValue {
+    /// Source DAG address
+    pub source: String,
+    /// Destination DAG address
+    pub destination: String,
+    /// Amount in smallest units (1e-8)
+    pub amount: i64,
+    /// Fee in smallest units (1e-8)
+    pub fee: i64,
+    /// Reference to parent transaction
+    pub parent: TransactionReference,
+}
+
 /// Currency transaction structure (v2)
 /// A signed currency transaction value
 /// Used for metagraph token transfers
 pub type CurrencyTransaction = Signed<CurrencyTransactionValue>;
 
+/// A token amount stored exactly in smallest units (1e-8)
+///
+/// Construct with [`TokenAmount::from_tokens`] or [`TokenAmount::from_units`]
+/// so the unit is always explicit at the call site.
+///
+/// # Example
+/// ```
+/// use constellation_sdk::TokenAmount;
+///
+/// let amount = TokenAmount::from_tokens(100.5);
+/// assert_eq!(amount.units(), 10_050_000_000);
+/// assert_eq!(amount, TokenAmount::from_units(10_050_000_000));
+/// assert_eq!(amount.to_string(), "100.5");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TokenAmount(i64);
+
+impl TokenAmount {
+    /// Zero tokens
+    pub const ZERO: TokenAmount = TokenAmount(0);
+
+    /// Create an amount from whole tokens (e.g. `100.5`)
+    ///
+    /// Fractions below 1e-8 are floored, matching [`token_to_units`].
+    pub fn from_tokens(tokens: f64) -> Self {
+        TokenAmount(token_to_units(tokens))
+    }
+
+    /// Create an amount from smallest units (1e-8 tokens)
+    pub const fn from_units(units: i64) -> Self {
+        TokenAmount(units)
+    }
+
+    /// The amount in smallest units
+    pub const fn units(self) -> i64 {
+        self.0
+    }
+
+    /// The amount in tokens (may lose precision for very large amounts)
+    pub fn to_tokens(self) -> f64 {
+        units_to_token(self.0)
+    }
+
+    /// Add two amounts, returning `None` on overflow
+    pub fn checked_add(self, other: TokenAmount) -> Option<TokenAmount> {
+        self.0.checked_add(other.0).map(TokenAmount)
+    }
+
+    /// Subtract an amount, returning `None` on overflow
+    pub fn checked_sub(self, other: TokenAmount) -> Option<TokenAmount> {
+        self.0.checked_sub(other.0).map(TokenAmount)
+    }
+
+    /// Multiply by an integer factor, returning `None` on overflow
+    pub fn checked_mul(self, factor: i64) -> Option<TokenAmount> {
+        self.0.checked_mul(factor).map(TokenAmount)
+    }
+}
+
+/// Source-compatibility shim: `f64` values are interpreted as tokens
+impl From<f64> for TokenAmount {
+    fn from(tokens: f64) -> Self {
+        TokenAmount::from_tokens(tokens)
+    }
+}
+
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let units = self.0.unsigned_abs();
+        let whole = units / 100_000_000;
+        let frac = units % 100_000_000;
+        if frac == 0 {
+            write!(f, "{sign}{whole}")
+        } else {
+            let frac = format!("{frac:08}");
+            write!(f, "{sign}{whole}.{}", frac.trim_end_matches('0'))
+        }
+    }
+}
+
 /// Parameters for creating a token transfer
+///
+/// `amount` and `fee` default to `f64` token values for source compatibility;
+/// use [`TokenAmount`] to make the unit explicit.
+///
+/// # Example
+/// ```
+/// use constellation_sdk::{TokenAmount, TransferParams};
+///
+/// let params = TransferParams {
+///     destination: "DAG...".to_string(),
+///     amount: TokenAmount::from_units(29_000_000),
+///     fee: TokenAmount::ZERO,
+/// };
+/// assert_eq!(params.amount.to_string(), "0.29");
+/// ```
 #[derive(Debug, Clone)]
-pub struct TransferParams {
+pub struct TransferParams<A = f64> {
     /// Destination DAG address
     pub destination: String,
-    /// Amount in token units (e.g., 100.5 tokens)
-    pub amount: f64,
-    /// Fee in token units (defaults to 0)
-    pub fee: f64,
+    /// Amount (e.g., 100.5 tokens, or a [`TokenAmount`])
+    pub amount: A,
+    /// Fee (defaults to 0)
+    pub fee: A,
 }
@@ -0,0 +1,631 @@
+//! Gzip / DEFLATE (RFC 1951, RFC 1952)
+//!
+//! Minimal in-crate implementation used by the codec compression helpers.
+//! Compression uses LZ77 with fixed Huffman codes; decompression supports
+//! stored, fixed and dynamic Huffman blocks so any standard gzip stream decodes.
+
+use crate::types::{Result, SdkError};
+
+const WINDOW_SIZE: usize = 32768;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+const MAX_CHAIN: usize = 64;
+const HASH_SIZE: usize = 1 << 15;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const GZIP_METHOD_DEFLATE: u8 = 8;
+const FHCRC: u8 = 0x02;
+const FEXTRA: u8 = 0x04;
+const FNAME: u8 = 0x08;
+const FCOMMENT: u8 = 0x10;
+
+fn error(message: &str) -> SdkError {
+    SdkError::SerializationError(format!("Invalid gzip data: {message}"))
+}
+
+/// Compress bytes into a gzip stream
+pub(crate) fn gzip_compress(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() / 2 + 32);
+    out.extend_from_slice(&GZIP_MAGIC);
+    out.push(GZIP_METHOD_DEFLATE);
+    out.push(0); // flags
+    out.extend_from_slice(&[0, 0, 0, 0]); // mtime
+    out.push(0); // extra flags
+    out.push(0xff); // OS: unknown
+
+    out.extend(deflate(data));
+    out.extend_from_slice(&crc32(data).to_le_bytes());
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out
+}
+
+/// Decompress a gzip stream, checking its CRC and length trailer
+///
+/// Fails with `SdkError::PayloadTooLarge` as soon as the output would exceed
+/// `max_len` bytes, so a small hostile stream can't expand without bound.
+pub(crate) fn gzip_decompress(data: &[u8], max_len: usize) -> Result<Vec<u8>> {
+    if data.len() < 18 || data[..2] != GZIP_MAGIC {
+        return Err(error("missing gzip header"));
+    }
+    if data[2] != GZIP_METHOD_DEFLATE {
+        return Err(error("unsupported compression method"));
+    }
+    let flags = data[3];
+    let mut pos = 10;
+
+    if flags & FEXTRA != 0 {
+        let len = *data.get(pos).ok_or_else(|| error("truncated header"))? as usize
+            | (*data.get(pos + 1).ok_or_else(|| error("truncated header"))? as usize) << 8;
+        pos += 2 + len;
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            let end = data
+                .get(pos..)
+                .and_then(|rest| rest.iter().position(|&b| b == 0))
+                .ok_or_else(|| error("truncated header"))?;
+            pos += end + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        pos += 2;
+    }
+    if pos + 8 > data.len() {
+        return Err(error("truncated stream"));
+    }
+
+    let trailer = &data[data.len() - 8..];
+    let crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    let size = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+    // ISIZE is the length modulo 2^32, so it can only understate the output
+    if size as usize > max_len {
+        return Err(SdkError::PayloadTooLarge(max_len));
+    }
+
+    let (output, consumed) = inflate(&data[pos..data.len() - 8], max_len)?;
+    if pos + consumed + 8 != data.len() {
+        return Err(error("trailing data after stream"));
+    }
+    if crc != crc32(&output) || size != output.len() as u32 {
+        return Err(error("checksum mismatch"));
+    }
+
+    Ok(output)
+}
+
+/// CRC-32 (IEEE) as used by gzip
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+// ============================================================================
+// Compression
+// ============================================================================
+
+struct BitWriter {
+    out: Vec<u8>,
+    bit_buffer: u32,
+    bit_count: u32,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self {
+            out: Vec::new(),
+            bit_buffer: 0,
+            bit_count: 0,
+        }
+    }
+
+    /// Write `count` bits of `value`, least significant bit first
+    fn write_bits(&mut self, value: u32, count: u32) {
+        self.bit_buffer |= value << self.bit_count;
+        self.bit_count += count;
+        while self.bit_count >= 8 {
+            self.out.push(self.bit_buffer as u8);
+            self.bit_buffer >>= 8;
+            self.bit_count -= 8;
+        }
+    }
+
+    /// Write a Huffman code, most significant bit first
+    fn write_code(&mut self, code: u32, length: u32) {
+        let mut reversed = 0;
+        for i in 0..length {
+            reversed |= ((code >> i) & 1) << (length - 1 - i);
+        }
+        self.write_bits(reversed, length);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bit_count > 0 {
+            self.out.push(self.bit_buffer as u8);
+        }
+        self.out
+    }
+}
+
+/// Write a literal/length symbol using the fixed Huffman code
+fn write_fixed_literal(writer: &mut BitWriter, symbol: u16) {
+    let symbol = symbol as u32;
+    match symbol {
+        0..=143 => writer.write_code(0x30 + symbol, 8),
+        144..=255 => writer.write_code(0x190 + symbol - 144, 9),
+        256..=279 => writer.write_code(symbol - 256, 7),
+        _ => writer.write_code(0xc0 + symbol - 280, 8),
+    }
+}
+
+fn write_match(writer: &mut BitWriter, length: usize, distance: usize) {
+    let length_index = LENGTH_BASE
+        .iter()
+        .rposition(|&base| base as usize <= length)
+        .unwrap_or(0);
+    write_fixed_literal(writer, 257 + length_index as u16);
+    writer.write_bits(
+        (length - LENGTH_BASE[length_index] as usize) as u32,
+        LENGTH_EXTRA[length_index] as u32,
+    );
+
+    let dist_index = DIST_BASE
+        .iter()
+        .rposition(|&base| base as usize <= distance)
+        .unwrap_or(0);
+    writer.write_code(dist_index as u32, 5);
+    writer.write_bits(
+        (distance - DIST_BASE[dist_index] as usize) as u32,
+        DIST_EXTRA[dist_index] as u32,
+    );
+}
+
+fn hash3(data: &[u8], pos: usize) -> usize {
+    let value = (data[pos] as usize) << 16 | (data[pos + 1] as usize) << 8 | data[pos + 2] as usize;
+    (value.wrapping_mul(2654435761) >> 7) & (HASH_SIZE - 1)
+}
+
+/// Record `pos` in the hash chains used for match finding
+fn insert_hash(data: &[u8], pos: usize, head: &mut [usize], prev: &mut [usize]) {
+    if pos + MIN_MATCH <= data.len() {
+        let h = hash3(data, pos);
+        prev[pos] = head[h];
+        head[h] = pos;
+    }
+}
+
+/// Compress into a single final fixed-Huffman DEFLATE block
+fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter::new();
+    writer.write_bits(1, 1); // BFINAL
+    writer.write_bits(1, 2); // BTYPE = fixed Huffman
+
+    let mut head = vec![usize::MAX; HASH_SIZE];
+    let mut prev = vec![usize::MAX; data.len()];
+    let mut pos = 0;
+
+    while pos < data.len() {
+        let mut best_length = 0;
+        let mut best_distance = 0;
+
+        if pos + MIN_MATCH <= data.len() {
+            let max_length = MAX_MATCH.min(data.len() - pos);
+            let mut candidate = head[hash3(data, pos)];
+            let mut steps = 0;
+            while candidate != usize::MAX && pos - candidate <= WINDOW_SIZE && steps < MAX_CHAIN {
+                let length = data[candidate..]
+                    .iter()
+                    .zip(&data[pos..pos + max_length])
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                if length > best_length {
+                    best_length = length;
+                    best_distance = pos - candidate;
+                    if length == max_length {
+                        break;
+                    }
+                }
+                candidate = prev[candidate];
+                steps += 1;
+            }
+        }
+
+        if best_length >= MIN_MATCH {
+            write_match(&mut writer, best_length, best_distance);
+            for p in pos..pos + best_length {
+                insert_hash(data, p, &mut head, &mut prev);
+            }
+            pos += best_length;
+        } else {
+            write_fixed_literal(&mut writer, data[pos] as u16);
+            insert_hash(data, pos, &mut head, &mut prev);
+            pos += 1;
+        }
+    }
+
+    write_fixed_literal(&mut writer, 256);
+    writer.finish()
+}
+
+// ============================================================================
+// Decompression
+// ============================================================================
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    bit_buffer: u32,
+    bit_count: u32,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            bit_buffer: 0,
+            bit_count: 0,
+        }
+    }
+
+    fn bits(&mut self, count: u32) -> Result<u32> {
+        while self.bit_count < count {
+            let byte = *self
+                .data
+                .get(self.pos)
+                .ok_or_else(|| error("unexpected end of stream"))?;
+            self.pos += 1;
+            self.bit_buffer |= (byte as u32) << self.bit_count;
+            self.bit_count += 8;
+        }
+        let value = self.bit_buffer & ((1u32 << count) - 1);
+        self.bit_buffer >>= count;
+        self.bit_count -= count;
+        Ok(value)
+    }
+
+    fn align_to_byte(&mut self) {
+        self.bit_buffer = 0;
+        self.bit_count = 0;
+    }
+}
+
+/// Canonical Huffman decoding table
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Result<Self> {
+        let mut counts = [0u16; 16];
+        for &length in lengths {
+            counts[length as usize] += 1;
+        }
+        counts[0] = 0;
+
+        let mut offsets = [0u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+
+        let mut symbols = vec![0u16; lengths.len()];
+        for (symbol, &length) in lengths.iter().enumerate() {
+            if length != 0 {
+                symbols[offsets[length as usize] as usize] = symbol as u16;
+                offsets[length as usize] += 1;
+            }
+        }
+
+        Ok(Self { counts, symbols })
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u16> {
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+        for len in 1..16 {
+            code |= reader.bits(1)? as i32;
+            let count = self.counts[len] as i32;
+            if code - count < first {
+                return self
+                    .symbols
+                    .get((index + code - first) as usize)
+                    .copied()
+                    .ok_or_else(|| error("invalid Huffman code"));
+            }
+            index += count;
+            first += count;
+            first <<= 1;
+            code <<= 1;
+        }
+        Err(error("invalid Huffman code"))
+    }
+}
+
+fn fixed_tables() -> Result<(Huffman, Huffman)> {
+    let mut lengths = [0u8; 288];
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+    Ok((Huffman::new(&lengths)?, Huffman::new(&[5u8; 30])?))
+}
+
+fn dynamic_tables(reader: &mut BitReader) -> Result<(Huffman, Huffman)> {
+    let literal_count = reader.bits(5)? as usize + 257;
+    let distance_count = reader.bits(5)? as usize + 1;
+    let code_length_count = reader.bits(4)? as usize + 4;
+    if literal_count > 286 || distance_count > 30 {
+        return Err(error("too many Huffman codes"));
+    }
+
+    let mut code_lengths = [0u8; 19];
+    for &index in CODE_LENGTH_ORDER.iter().take(code_length_count) {
+        code_lengths[index] = reader.bits(3)? as u8;
+    }
+    let code_length_table = Huffman::new(&code_lengths)?;
+
+    let mut lengths = vec![0u8; literal_count + distance_count];
+    let mut i = 0;
+    while i < lengths.len() {
+        let symbol = code_length_table.decode(reader)?;
+        let (value, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => {
+                let previous = *lengths
+                    .get(i.wrapping_sub(1))
+                    .ok_or_else(|| error("repeat with no previous length"))?;
+                (previous, 3 + reader.bits(2)? as usize)
+            }
+            17 => (0, 3 + reader.bits(3)? as usize),
+            _ => (0, 11 + reader.bits(7)? as usize),
+        };
+        if i + repeat > lengths.len() {
+            return Err(error("too many code lengths"));
+        }
+        lengths[i..i + repeat].fill(value);
+        i += repeat;
+    }
+
+    Ok((
+        Huffman::new(&lengths[..literal_count])?,
+        Huffman::new(&lengths[literal_count..])?,
+    ))
+}
+
+/// Fail if `additional` more output bytes would exceed `limit`
+fn check_limit(out: &[u8], additional: usize, limit: usize) -> Result<()> {
+    if out.len() + additional > limit {
+        return Err(SdkError::PayloadTooLarge(limit));
+    }
+    Ok(())
+}
+
+/// Decompress a raw DEFLATE stream of at most `limit` output bytes,
+/// returning the output and bytes consumed
+fn inflate(data: &[u8], limit: usize) -> Result<(Vec<u8>, usize)> {
+    let mut reader = BitReader::new(data);
+    let mut out = Vec::new();
+
+    loop {
+        let is_final = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => {
+                reader.align_to_byte();
+                let header = data
+                    .get(reader.pos..reader.pos + 4)
+                    .ok_or_else(|| error("truncated stored block"))?;
+                let len = u16::from_le_bytes([header[0], header[1]]);
+                let nlen = u16::from_le_bytes([header[2], header[3]]);
+                if len != !nlen {
+                    return Err(error("stored block length mismatch"));
+                }
+                let start = reader.pos + 4;
+                let block = data
+                    .get(start..start + len as usize)
+                    .ok_or_else(|| error("truncated stored block"))?;
+                check_limit(&out, block.len(), limit)?;
+                out.extend_from_slice(block);
+                reader.pos = start + len as usize;
+            }
+            1 => {
+                let (literals, distances) = fixed_tables()?;
+                inflate_block(&mut reader, &mut out, &literals, &distances, limit)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_tables(&mut reader)?;
+                inflate_block(&mut reader, &mut out, &literals, &distances, limit)?;
+            }
+            _ => return Err(error("invalid block type")),
+        }
+        if is_final {
+            return Ok((out, reader.pos));
+        }
+    }
+}
+
+fn inflate_block(
+    reader: &mut BitReader,
+    out: &mut Vec<u8>,
+    literals: &Huffman,
+    distances: &Huffman,
+    limit: usize,
+) -> Result<()> {
+    loop {
+        let symbol = literals.decode(reader)? as usize;
+        match symbol {
+            0..=255 => {
+                check_limit(out, 1, limit)?;
+                out.push(symbol as u8);
+            }
+            256 => return Ok(()),
+            _ => {
+                let index = symbol - 257;
+                if index >= LENGTH_BASE.len() {
+                    return Err(error("invalid length symbol"));
+                }
+                let length =
+                    LENGTH_BASE[index] as usize + reader.bits(LENGTH_EXTRA[index] as u32)? as usize;
+
+                let dist_index = distances.decode(reader)? as usize;
+                if dist_index >= DIST_BASE.len() {
+                    return Err(error("invalid distance symbol"));
+                }
+                let distance = DIST_BASE[dist_index] as usize
+                    + reader.bits(DIST_EXTRA[dist_index] as u32)? as usize;
+                if distance > out.len() {
+                    return Err(error("distance too far back"));
+                }
+                check_limit(out, length, limit)?;
+
+                let start = out.len() - distance;
+                for i in 0..length {
+                    out.push(out[start + i]);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::hash_bytes;
+
+    const LIMIT: usize = 1 << 20;
+
+    #[test]
+    fn test_roundtrip() {
+        let data = br#"{"id":"test","values":[1,2,3,1,2,3,1,2,3,1,2,3]}"#.repeat(20);
+        let compressed = gzip_compress(&data);
+        assert!(compressed.len() < data.len() / 4);
+        assert_eq!(gzip_decompress(&compressed, LIMIT).unwrap(), data);
+    }
+
+    #[test]
+    fn test_roundtrip_empty_and_binary() {
+        assert_eq!(gzip_decompress(&gzip_compress(b""), LIMIT).unwrap(), b"");
+
+        let binary: Vec<u8> = (0..5000u32).map(|i| (i * 7919 % 251) as u8).collect();
+        assert_eq!(
+            gzip_decompress(&gzip_compress(&binary), LIMIT).unwrap(),
+            binary
+        );
+    }
+
+    #[test]
+    fn test_decompress_dynamic_huffman_stream() {
+        // Produced by the reference gzip implementation (dynamic Huffman block, FNAME set)
+        let compressed = hex::decode(concat!(
+            "1f8b08080000000002ff746573742e6a736f6e009595316ec3300c45efa239054c52922d5fa5e8a0",
+            "a2461a20060ad4ed12f4ee6d27fd6c799307e38122f9f4754b97b7b4a6cbb1ed4f533aa5ef7efdda",
+            "d23a9dd2d1cf9f697d4efdfaf1defffebc6ec7ffe7dcf7bda7979f9b80364043a00f30233006d810",
+            "98e5a8159165905e10590719ace62cf3617d2e83ac6cb46d900bdba68940364185d42187754523cb",
+            "ac5b0b1582cdd854a6c6366baa13f4c9aaaa08eb8a52bec07e45aa7038e7a69700a6847895a1572e",
+            "5ee50ceb6a3c2dac5f17af8ab339bb785566b65f17af2af4cac5ab4aeb8a5733ed57bc9ae99c35ae",
+            "e07e43bc6ad0ab10af1af43934af26789142036b82373834b10c4647148d68e8565485615886c865",
+            "01533a96bbd7014e5bf4b2f2c0bbf40b5b32543550090000",
+        ))
+        .unwrap();
+        let expected: Vec<u8> = (0..40)
+            .flat_map(|i| {
+                format!(
+                    r#"{{"id":"item-{i}","value":{},"tags":["alpha","beta","gamma"]}}"#,
+                    i * i
+                )
+                .into_bytes()
+            })
+            .collect();
+        assert_eq!(gzip_decompress(&compressed, LIMIT).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_decompress_rejects_corruption() {
+        let mut compressed = gzip_compress(b"hello hello hello");
+        let last = compressed.len() - 5;
+        compressed[last] ^= 0xff;
+        assert!(gzip_decompress(&compressed, LIMIT).is_err());
+        assert!(gzip_decompress(b"not gzip", LIMIT).is_err());
+    }
+
+    #[test]
+    fn test_decompress_stops_at_limit() {
+        let bomb = gzip_compress(&vec![0u8; LIMIT]);
+        assert!(bomb.len() < LIMIT / 100);
+        assert!(matches!(
+            gzip_decompress(&bomb, 4096),
+            Err(SdkError::PayloadTooLarge(4096))
+        ));
+        assert_eq!(gzip_decompress(&bomb, LIMIT).unwrap().len(), LIMIT);
+
+        // Understating ISIZE doesn't lift the limit; inflation still stops
+        let mut lying = bomb.clone();
+        let size = lying.len() - 4;
+        lying[size..].copy_from_slice(&16u32.to_le_bytes());
+        assert!(matches!(
+            gzip_decompress(&lying, 4096),
+            Err(SdkError::PayloadTooLarge(4096))
+        ));
+    }
+
+    #[test]
+    fn test_decompress_reference_vectors() {
+        // Streams from the reference zlib; see tests/fixtures/gzip_vectors.py
+        let vectors: serde_json::Value =
+            serde_json::from_str(include_str!("../tests/fixtures/gzip_vectors.json")).unwrap();
+        let vectors = vectors.as_array().unwrap();
+        assert!(!vectors.is_empty());
+
+        for vector in vectors {
+            let description = vector["description"].as_str().unwrap();
+            let compressed = hex::decode(vector["gzip_hex"].as_str().unwrap()).unwrap();
+            let output = gzip_decompress(&compressed, LIMIT)
+                .unwrap_or_else(|e| panic!("{description}: {e}"));
+
+            assert_eq!(
+                output.len() as u64,
+                vector["size"].as_u64().unwrap(),
+                "{description}"
+            );
+            assert_eq!(
+                hash_bytes(&output).value,
+                vector["sha256"].as_str().unwrap(),
+                "{description}"
+            );
+            assert_eq!(
+                gzip_decompress(&gzip_compress(&output), LIMIT).unwrap(),
+                output,
+                "{description}"
+            );
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! Base58 Encoding
+//!
+//! Base58 encoding and decoding with a configurable alphabet. Addresses use
+//! the Bitcoin/Constellation alphabet; other alphabets (e.g. Ripple) can be
+//! supplied for interop with other networks.
+
+use crate::types::{Result, SdkError};
+
+/// Bitcoin/Constellation base58 alphabet
+pub const BITCOIN_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Ripple base58 alphabet
+pub const RIPPLE_ALPHABET: &str = "rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz";
+
+const INVALID: u8 = 0xff;
+
+/// A validated base58 alphabet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Base58Alphabet {
+    chars: [u8; 58],
+    decode_map: [u8; 128],
+}
+
+impl Base58Alphabet {
+    /// Create an alphabet from exactly 58 unique ASCII characters
+    ///
+    /// # Example
+    /// ```
+    /// use constellation_sdk::encoding::{Base58Alphabet, RIPPLE_ALPHABET};
+    ///
+    /// let ripple = Base58Alphabet::new(RIPPLE_ALPHABET).unwrap();
+    /// assert_eq!(ripple.encode(&[0, 1, 2, 3, 255]), "rpVCYi");
+    ///
+    /// assert!(Base58Alphabet::new("too short").is_err());
+    /// ```
+    pub fn new(alphabet: &str) -> Result<Self> {
+        let bytes = alphabet.as_bytes();
+        if bytes.len() != 58 || !alphabet.is_ascii() {
+            return Err(SdkError::EncodingError(format!(
+                "Base58 alphabet must be 58 ASCII characters, got {}",
+                alphabet.chars().count()
+            )));
+        }
+
+        let mut chars = [0u8; 58];
+        let mut decode_map = [INVALID; 128];
+        for (i, &c) in bytes.iter().enumerate() {
+            if decode_map[c as usize] != INVALID {
+                return Err(SdkError::EncodingError(format!(
+                    "Base58 alphabet contains duplicate character '{}'",
+                    c as char
+                )));
+            }
+            chars[i] = c;
+            decode_map[c as usize] = i as u8;
+        }
+
+        Ok(Self { chars, decode_map })
+    }
+
+    /// The Bitcoin/Constellation alphabet
+    pub fn bitcoin() -> Self {
+        Self::new(BITCOIN_ALPHABET).expect("Bitcoin alphabet is valid")
+    }
+
+    /// The Ripple alphabet
+    pub fn ripple() -> Self {
+        Self::new(RIPPLE_ALPHABET).expect("Ripple alphabet is valid")
+    }
+
+    /// Encode bytes as base58
+    pub fn encode(&self, data: &[u8]) -> String {
+        if data.is_empty() {
+            return String::new();
+        }
+
+        // Count leading zeros
+        let leading_zeros = data.iter().take_while(|&&b| b == 0).count();
+
+        // Convert to big integer representation
+        let mut num: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
+        for &byte in data {
+            let mut carry = byte as u32;
+            for digit in num.iter_mut() {
+                carry += (*digit as u32) << 8;
+                *digit = (carry % 58) as u8;
+                carry /= 58;
+            }
+            while carry > 0 {
+                num.push((carry % 58) as u8);
+                carry /= 58;
+            }
+        }
+
+        // Build result string
+        let mut result = String::with_capacity(leading_zeros + num.len());
+
+        // Add the zero character for each leading zero byte
+        for _ in 0..leading_zeros {
+            result.push(self.chars[0] as char);
+        }
+
+        // Convert digits to characters
+        for &digit in num.iter().rev() {
+            result.push(self.chars[digit as usize] as char);
+        }
+
+        result
+    }
+
+    /// Decode a base58 string to bytes
+    pub fn decode(&self, encoded: &str) -> Result<Vec<u8>> {
+        let zero = self.chars[0];
+        let leading_zeros = encoded.bytes().take_while(|&c| c == zero).count();
+
+        // Convert base58 digits to a little-endian base256 number
+        let mut num: Vec<u8> = Vec::with_capacity(encoded.len() * 733 / 1000 + 1);
+        for c in encoded.chars() {
+            let digit = if c.is_ascii() {
+                self.decode_map[c as usize]
+            } else {
+                INVALID
+            };
+            if digit == INVALID {
+                return Err(SdkError::EncodingError(format!(
+                    "Invalid base58 character '{c}'"
+                )));
+            }
+
+            let mut carry = digit as u32;
+            for byte in num.iter_mut() {
+                carry += (*byte as u32) * 58;
+                *byte = carry as u8;
+                carry >>= 8;
+            }
+            while carry > 0 {
+                num.push(carry as u8);
+                carry >>= 8;
+            }
+        }
+
+        let mut result = vec![0u8; leading_zeros];
+        result.extend(num.iter().rev());
+        Ok(result)
+    }
+}
+
+impl Default for Base58Alphabet {
+    fn default() -> Self {
+        Self::bitcoin()
+    }
+}
+
+/// Base58 encode bytes using the Bitcoin/Constellation alphabet
+pub fn base58_encode(data: &[u8]) -> String {
+    Base58Alphabet::bitcoin().encode(data)
+}
+
+/// Base58 decode a string using the Bitcoin/Constellation alphabet
+pub fn base58_decode(encoded: &str) -> Result<Vec<u8>> {
+    Base58Alphabet::bitcoin().decode(encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitcoin_roundtrip() {
+        let data = [0u8, 1, 2, 3, 255];
+        let encoded = base58_encode(&data);
+        assert_eq!(encoded, "12VfYr");
+        assert_eq!(base58_decode(&encoded).unwrap(), data);
+        assert!(base58_encode(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_ripple_alphabet() {
+        // Ripple's "account zero": version byte 0, 20 zero bytes, checksum
+        let ripple = Base58Alphabet::ripple();
+        let decoded = ripple.decode("rrrrrrrrrrrrrrrrrrrrrhoLvTp").unwrap();
+        assert_eq!(decoded.len(), 25);
+        assert_eq!(&decoded[..21], &[0u8; 21]);
+        assert_eq!(ripple.encode(&decoded), "rrrrrrrrrrrrrrrrrrrrrhoLvTp");
+    }
+
+    #[test]
+    fn test_invalid_alphabets() {
+        assert!(Base58Alphabet::new("abc").is_err());
+        let duplicate = format!("1{}", &BITCOIN_ALPHABET[..57]);
+        assert!(Base58Alphabet::new(&duplicate).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_invalid_characters() {
+        assert!(base58_decode("0OIl").is_err());
+        assert!(Base58Alphabet::ripple().decode("0").is_err());
+    }
+}
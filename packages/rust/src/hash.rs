@@ -2,11 +2,15 @@
 //!
 //! SHA-256 and SHA-512 hashing functions for the Constellation protocol.
 
+use std::io;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::write::EncoderWriter;
 use serde::Serialize;
 use sha2::{Digest, Sha256, Sha512};
 
 use crate::binary::to_bytes;
-use crate::types::{Hash, Result};
+use crate::types::{Hash, Result, SdkError, CONSTELLATION_PREFIX};
 
 /// Hash data using SHA-256
 ///
@@ -31,6 +35,86 @@ pub fn hash_data<T: Serialize>(data: &T, is_data_update: bool) -> Result<Hash> {
     Ok(hash_bytes(&bytes))
 }
 
+/// Hash data using SHA-256 without buffering its encoding
+///
+/// Produces the same hash as `hash_data`, but streams the canonical JSON
+/// (and, for DataUpdates, its base64 form) straight into the hasher instead
+/// of materializing the encoded bytes. DataUpdates are canonicalized twice:
+/// once to measure the length that goes in the prefix, once to hash.
+///
+/// # Arguments
+/// * `data` - Any serializable data
+/// * `is_data_update` - Whether to encode as DataUpdate before hashing
+///
+/// # Returns
+/// Hash struct with value (hex) and bytes
+///
+/// # Example
+/// ```
+/// use constellation_sdk::hash::{hash_data, hash_data_streaming};
+/// use serde_json::json;
+///
+/// let data = json!({"records": vec!["entry"; 1000]});
+/// assert_eq!(
+///     hash_data_streaming(&data, true).unwrap(),
+///     hash_data(&data, true).unwrap()
+/// );
+/// ```
+pub fn hash_data_streaming<T: Serialize>(data: &T, is_data_update: bool) -> Result<Hash> {
+    let mut hasher = HashWriter(Sha256::new());
+
+    if is_data_update {
+        let mut counter = ByteCounter(0);
+        serde_json_canonicalizer::to_writer(data, &mut counter)?;
+        let base64_len = counter.0.div_ceil(3) * 4;
+        hasher
+            .0
+            .update(format!("{CONSTELLATION_PREFIX}{base64_len}\n").as_bytes());
+
+        let mut encoder = EncoderWriter::new(&mut hasher, &STANDARD);
+        serde_json_canonicalizer::to_writer(data, &mut encoder)?;
+        encoder
+            .finish()
+            .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+    } else {
+        serde_json_canonicalizer::to_writer(data, &mut hasher)?;
+    }
+
+    let hash_bytes = hasher.0.finalize().to_vec();
+    Ok(Hash {
+        value: hex::encode(&hash_bytes),
+        bytes: hash_bytes,
+    })
+}
+
+/// Writer that feeds everything written into a SHA-256 hasher
+struct HashWriter(Sha256);
+
+impl io::Write for HashWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Writer that discards output, counting the bytes written
+struct ByteCounter(usize);
+
+impl io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 /// Hash raw bytes using SHA-256
 ///
 /// # Arguments
@@ -118,6 +202,100 @@ pub fn compute_digest_from_hash(hash_hex: &str) -> [u8; 32] {
     digest
 }
 
+/// Compute a signing digest truncated to a custom length
+///
+/// For prototyping against protocol variants that truncate the SHA-512
+/// differently. The Constellation protocol uses 32 bytes, which is what
+/// every signing and verification path in this crate uses;
+/// `compute_digest_with_len(h, 32)` equals `compute_digest_from_hash(h)`.
+///
+/// # Arguments
+/// * `hash_hex` - 64-character hex string of SHA-256 hash
+/// * `len` - Number of leading SHA-512 bytes to keep
+///
+/// # Returns
+/// The first `len` bytes of the SHA-512 of `hash_hex` as UTF-8
+///
+/// # Panics
+/// If `len` is greater than 64, the SHA-512 output size
+///
+/// # Example
+/// ```
+/// use constellation_sdk::hash::{compute_digest_from_hash, compute_digest_with_len};
+///
+/// let hash_hex = "a".repeat(64);
+/// assert_eq!(compute_digest_with_len(&hash_hex, 32), compute_digest_from_hash(&hash_hex));
+/// assert_eq!(compute_digest_with_len(&hash_hex, 20).len(), 20);
+/// ```
+pub fn compute_digest_with_len(hash_hex: &str, len: usize) -> Vec<u8> {
+    assert!(
+        len <= 64,
+        "digest length {len} exceeds SHA-512 output (64 bytes)"
+    );
+
+    let mut sha512_hasher = Sha512::new();
+    sha512_hasher.update(hash_hex.as_bytes());
+    sha512_hasher.finalize()[..len].to_vec()
+}
+
+/// Compute the 32-byte ECDSA message digest from a hash hex string
+///
+/// Decouples the digest derivation from the hash algorithm so alternative
+/// hashing schemes (e.g. Keccak-256) can reuse the signing core.
+///
+/// With `apply_sha512 = true` (the Constellation protocol, as in
+/// `compute_digest_from_hash`):
+/// 1. Take `hash_hex` as-is, lowercase hex expected
+/// 2. Treat the hex string as UTF-8 bytes
+/// 3. SHA-512 those bytes
+/// 4. Truncate to the first 32 bytes
+///
+/// With `apply_sha512 = false`:
+/// 1. Hex-decode `hash_hex`, which must be exactly 32 bytes
+/// 2. Use the decoded bytes directly as the digest
+///
+/// # Arguments
+/// * `hash_hex` - Hash in hex format
+/// * `apply_sha512` - Whether to apply the UTF-8-of-hex SHA-512 step
+///
+/// # Returns
+/// 32-byte digest ready for signing
+///
+/// # Example
+/// ```
+/// use constellation_sdk::hash::{compute_digest_from_hash, compute_signing_digest};
+///
+/// let hash_hex = "a".repeat(64);
+/// assert_eq!(
+///     compute_signing_digest(&hash_hex, true).unwrap(),
+///     compute_digest_from_hash(&hash_hex)
+/// );
+/// assert_eq!(compute_signing_digest(&hash_hex, false).unwrap(), [0xaa; 32]);
+/// assert!(compute_signing_digest("abcd", false).is_err());
+/// ```
+pub fn compute_signing_digest(hash_hex: &str, apply_sha512: bool) -> Result<[u8; 32]> {
+    if apply_sha512 {
+        return Ok(compute_digest_from_hash(hash_hex));
+    }
+
+    let bytes = hex::decode(hash_hex)?;
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        SdkError::CryptoError(format!("Digest must be 32 bytes, got {}", bytes.len()))
+    })
+}
+
+/// Compute the Keccak-256 digest used by Ethereum
+///
+/// # Arguments
+/// * `data` - Raw bytes to hash
+///
+/// # Returns
+/// 32-byte Keccak-256 digest
+#[cfg(feature = "recovery")]
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    crate::keccak::keccak256(data)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,6 +333,57 @@ mod tests {
         assert_ne!(regular_digest, update_digest);
     }
 
+    #[test]
+    fn test_compute_digest_with_len() {
+        let hash_hex = hash_bytes(b"variant").value;
+        let full = compute_digest_with_len(&hash_hex, 64);
+        assert_eq!(
+            compute_digest_with_len(&hash_hex, 32),
+            compute_digest_from_hash(&hash_hex)
+        );
+        assert_eq!(compute_digest_with_len(&hash_hex, 48), full[..48]);
+        assert!(compute_digest_with_len(&hash_hex, 0).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds SHA-512 output")]
+    fn test_compute_digest_with_len_too_long() {
+        compute_digest_with_len("00", 65);
+    }
+
+    #[test]
+    fn test_compute_signing_digest() {
+        let hash = hash_bytes(b"test data");
+        assert_eq!(
+            compute_signing_digest(&hash.value, true).unwrap(),
+            compute_digest_from_bytes(b"test data")
+        );
+        assert_eq!(
+            compute_signing_digest(&hash.value, false).unwrap().to_vec(),
+            hash.bytes
+        );
+        assert!(compute_signing_digest("zz", false).is_err());
+        assert!(compute_signing_digest(&"ab".repeat(33), false).is_err());
+    }
+
+    #[test]
+    fn test_hash_data_streaming_matches_hash_data() {
+        // Cover each base64 padding case
+        for data in [
+            json!({}),
+            json!({"a": 1}),
+            json!({"ab": 1}),
+            json!({"id": "test", "nested": {"z": [1, 2.5, "é"], "a": null}}),
+        ] {
+            for is_data_update in [false, true] {
+                assert_eq!(
+                    hash_data_streaming(&data, is_data_update).unwrap(),
+                    hash_data(&data, is_data_update).unwrap()
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_deterministic_hashing() {
         let data = json!({"id": "test", "value": 42});
@@ -61,45 +61,91 @@
 
 pub mod binary;
 pub mod canonicalize;
+pub mod capabilities;
 pub mod codec;
 pub mod currency_transaction;
 pub mod currency_types;
+pub mod encoding;
 pub mod hash;
+pub mod http_auth;
+pub mod merkle;
 pub mod sign;
 pub mod signed_object;
 pub mod types;
 pub mod verify;
 pub mod wallet;
 
+mod deflate;
+#[cfg(feature = "recovery")]
+mod keccak;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+mod rfc3339;
+
 #[cfg(feature = "network")]
 pub mod network;
 
 // Re-export commonly used items at the crate root
 pub use types::{
-    Hash, KeyPair, Result, SdkError, SignatureProof, Signed, SigningOptions, VerificationResult,
-    ALGORITHM, CONSTELLATION_PREFIX,
+    Hash, IdFormat, InvalidReason, JsonDiff, KeyPair, Result, SdkError, SignatureError,
+    SignatureProof, Signed, SigningMode, SigningOptions, TaggedProof, VerificationResult,
+    ALGORITHM, CONSTELLATION_PREFIX, MSGPACK_ALGORITHM,
 };
 
 // Re-export main functions
-pub use binary::{encode_data_update, to_bytes};
-pub use canonicalize::{canonicalize, canonicalize_bytes};
-pub use codec::decode_data_update;
-pub use hash::{compute_digest, hash_bytes, hash_data};
-pub use sign::{sign, sign_data_update, sign_hash};
-pub use signed_object::{add_signature, batch_sign, create_signed_object};
-pub use verify::{verify, verify_hash, verify_signature};
+pub use binary::{encode_data_update, to_bytes, to_bytes_python_compat, to_bytes_with_options};
+pub use canonicalize::{
+    canonicalize, canonicalize_and_hash, canonicalize_bytes, canonicalize_bytes_limited,
+    canonicalize_no_floats,
+};
+pub use capabilities::{capabilities, Capabilities};
+pub use codec::{
+    decode_data_update, decode_data_update_compressed, decode_data_update_compressed_limited,
+    encode_data_update_compressed, Compression, MAX_DECOMPRESSED_SIZE,
+};
+pub use hash::{
+    compute_digest, compute_signing_digest, hash_bytes, hash_data, hash_data_streaming,
+};
+pub use http_auth::{canonical_request, sign_request, verify_request, HTTP_AUTH_VERSION};
+pub use merkle::{merkle_proof, merkle_root, verify_merkle_proof, MerkleProof, MerkleSibling};
+pub use sign::{
+    compact_to_der, der_to_compact, dual_encode_signature, parse_der_signature, sign,
+    sign_data_update, sign_hash, sign_with_options, ParsedSignature, PreparedSigner,
+    SigningSession,
+};
+pub use signed_object::{
+    add_signature, batch_sign, create_signed_object, create_timestamped, envelope_hash,
+    parse_signed, split_proofs, validate_structure, SIGNED_AT_FIELD,
+};
+pub use verify::{
+    check_freshness, diff_against, probe_mode, same_signer, validate_proof_set, verify,
+    verify_auto, verify_cached, verify_canonical, verify_hash, verify_ignoring_unknown,
+    verify_message, verify_migrated, verify_ordered, verify_projection, verify_signature,
+    verify_signature_against, verify_tagged, verify_value, verify_with_max_age,
+    verify_with_options, verify_with_version, VerifyCache, VerifyOptions,
+};
 pub use wallet::{
-    generate_key_pair, get_address, get_public_key_hex, get_public_key_id, is_valid_private_key,
-    is_valid_public_key, key_pair_from_private_key,
+    address_from_bytes, address_from_proof, generate_key_pair, generate_key_pair_with_scheme,
+    generate_matching, get_address, get_public_key_hex, get_public_key_id, is_valid_private_key,
+    is_valid_public_key, key_pair_from_private_key, key_pair_from_private_key_with_scheme,
+    normalize_proof_ids, public_key_from_spki, self_check, AddressScheme, DagAddressScheme,
+    DerivationScanner,
 };
 
 // Re-export currency transaction types and functions
 pub use currency_transaction::{
-    create_currency_transaction, create_currency_transaction_batch, encode_currency_transaction,
-    get_transaction_reference, hash_currency_transaction, is_valid_dag_address,
-    sign_currency_transaction, token_to_units, units_to_token, verify_currency_transaction,
+    addresses_equal, build_fanout, check_encoding_compatibility, create_currency_transaction,
+    create_currency_transaction_batch, create_currency_transaction_exact,
+    create_currency_transaction_safe, create_currency_transaction_with_nonce,
+    create_delegated_transaction, decode_encoded_string, deterministic_salt,
+    encode_currency_transaction, encoded_size, get_transaction_reference,
+    hash_currency_transaction, hash_with_reference, is_valid_dag_address, minimum_transfer,
+    preflight, sign_currency_transaction, sign_transaction_hash, token_to_units, truncation_loss,
+    units_to_token, verify_chain, verify_claimed_hash, verify_currency_transaction,
+    verify_currency_transaction_with_options, verify_multisig, verify_transaction_hash,
+    ReplayGuard, ReplayKey, ReplayStore,
 };
 pub use currency_types::{
-    CurrencyTransaction, CurrencyTransactionValue, TransactionReference, TransferParams,
-    TOKEN_DECIMALS,
+    CurrencyTransaction, CurrencyTransactionValue, PartialTransactionValue, TokenAmount,
+    TransactionReference, TransactionVersion, TransferParams, TOKEN_DECIMALS, TOKEN_DECIMAL_PLACES,
 };
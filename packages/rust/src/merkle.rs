@@ -0,0 +1,313 @@
+//! Merkle Trees
+//!
+//! Binary Merkle trees over SHA-256 hashes, for anchoring many objects
+//! with one signature over the root.
+//!
+//! Leaves and interior nodes are domain-separated as in RFC 6962, so a
+//! leaf can never be passed off as an interior node:
+//!
+//! ```text
+//! leaf = SHA-256(0x00 || hash)
+//! node = SHA-256(0x01 || left || right)
+//! ```
+//!
+//! A level with an odd number of nodes promotes its last node unchanged
+//! to the next level instead of duplicating it, so `[a, b, c]` and
+//! `[a, b, c, c]` have different roots.
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::hash_bytes;
+use crate::types::Hash;
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Proof that a leaf is in a Merkle tree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// Position of the leaf in the hashes the tree was built from
+    pub index: usize,
+    /// Number of hashes the tree was built from
+    pub leaf_count: usize,
+    /// Sibling hashes from the leaf up to the root
+    pub siblings: Vec<MerkleSibling>,
+}
+
+/// One step of a Merkle proof
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleSibling {
+    /// Sibling node hash in hex format
+    pub hash: String,
+    /// Whether the sibling is the left child
+    pub left: bool,
+}
+
+/// Compute the Merkle root of a list of hashes
+///
+/// # Arguments
+/// * `hashes` - Leaf hashes, e.g. from `hash_data` on each object
+///
+/// # Returns
+/// The root hash; for no hashes, the SHA-256 of empty input
+///
+/// # Example
+/// ```
+/// use constellation_sdk::hash::hash_data;
+/// use constellation_sdk::merkle::merkle_root;
+/// use constellation_sdk::sign::sign_hash;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let hashes: Vec<_> = (0..5).map(|i| hash_data(&json!({"n": i}), false).unwrap()).collect();
+/// let root = merkle_root(&hashes);
+///
+/// // One signature anchors the whole batch
+/// let key_pair = generate_key_pair();
+/// let signature = sign_hash(&root.value, &key_pair.private_key).unwrap();
+/// ```
+pub fn merkle_root(hashes: &[Hash]) -> Hash {
+    if hashes.is_empty() {
+        return hash_bytes(&[]);
+    }
+
+    let mut level: Vec<Hash> = hashes.iter().map(leaf_hash).collect();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level.remove(0)
+}
+
+/// Build a membership proof for one leaf
+///
+/// # Arguments
+/// * `hashes` - Leaf hashes the tree is built from
+/// * `index` - Position of the leaf to prove
+///
+/// # Returns
+/// The proof, or None if `index` is out of range
+///
+/// # Example
+/// ```
+/// use constellation_sdk::hash::hash_data;
+/// use constellation_sdk::merkle::{merkle_proof, merkle_root, verify_merkle_proof};
+/// use serde_json::json;
+///
+/// let hashes: Vec<_> = (0..5).map(|i| hash_data(&json!({"n": i}), false).unwrap()).collect();
+/// let root = merkle_root(&hashes);
+///
+/// let proof = merkle_proof(&hashes, 3).unwrap();
+/// assert!(verify_merkle_proof(&hashes[3], &proof, &root));
+/// assert!(!verify_merkle_proof(&hashes[2], &proof, &root));
+/// assert!(merkle_proof(&hashes, 5).is_none());
+/// ```
+pub fn merkle_proof(hashes: &[Hash], index: usize) -> Option<MerkleProof> {
+    if index >= hashes.len() {
+        return None;
+    }
+
+    let mut siblings = Vec::new();
+    let mut level: Vec<Hash> = hashes.iter().map(leaf_hash).collect();
+    let mut position = index;
+    while level.len() > 1 {
+        let sibling = position ^ 1;
+        if let Some(hash) = level.get(sibling) {
+            siblings.push(MerkleSibling {
+                hash: hash.value.clone(),
+                left: sibling < position,
+            });
+        }
+        level = next_level(&level);
+        position /= 2;
+    }
+
+    Some(MerkleProof {
+        index,
+        leaf_count: hashes.len(),
+        siblings,
+    })
+}
+
+/// Verify that a leaf is in the tree with the given root
+///
+/// # Arguments
+/// * `leaf` - Leaf hash to check
+/// * `proof` - Proof from `merkle_proof`
+/// * `root` - Expected root, e.g. one whose signature was verified
+///
+/// # Returns
+/// true if the proof leads from `leaf` to `root`. The path is replayed
+/// from `index` and `leaf_count`, so a proof whose siblings or `left`
+/// flags do not match that position is rejected.
+pub fn verify_merkle_proof(leaf: &Hash, proof: &MerkleProof, root: &Hash) -> bool {
+    if proof.index >= proof.leaf_count {
+        return false;
+    }
+
+    let mut siblings = proof.siblings.iter();
+    let mut current = leaf_hash(leaf);
+    let mut position = proof.index;
+    let mut width = proof.leaf_count;
+    while width > 1 {
+        // The last node of an odd level is promoted without a sibling
+        if position ^ 1 < width {
+            let Some(sibling) = siblings.next() else {
+                return false;
+            };
+            if sibling.left != (position % 2 == 1) {
+                return false;
+            }
+            let Ok(bytes) = hex::decode(&sibling.hash) else {
+                return false;
+            };
+            let sibling_hash = Hash {
+                value: sibling.hash.to_ascii_lowercase(),
+                bytes,
+            };
+            current = if sibling.left {
+                node_hash(&sibling_hash, &current)
+            } else {
+                node_hash(&current, &sibling_hash)
+            };
+        }
+        position /= 2;
+        width = width.div_ceil(2);
+    }
+    siblings.next().is_none() && current.bytes == root.bytes
+}
+
+/// Hash pairs of nodes, promoting an odd last node unchanged
+fn next_level(level: &[Hash]) -> Vec<Hash> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node_hash(left, right),
+            [single] => single.clone(),
+            _ => unreachable!("chunks of two"),
+        })
+        .collect()
+}
+
+fn leaf_hash(hash: &Hash) -> Hash {
+    let mut data = Vec::with_capacity(1 + hash.bytes.len());
+    data.push(LEAF_PREFIX);
+    data.extend_from_slice(&hash.bytes);
+    hash_bytes(&data)
+}
+
+fn node_hash(left: &Hash, right: &Hash) -> Hash {
+    let mut data = Vec::with_capacity(1 + left.bytes.len() + right.bytes.len());
+    data.push(NODE_PREFIX);
+    data.extend_from_slice(&left.bytes);
+    data.extend_from_slice(&right.bytes);
+    hash_bytes(&data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(n: usize) -> Vec<Hash> {
+        (0..n).map(|i| hash_bytes(&[i as u8])).collect()
+    }
+
+    #[test]
+    fn test_root_structure() {
+        let hashes = leaves(3);
+        let (a, b, c) = (
+            leaf_hash(&hashes[0]),
+            leaf_hash(&hashes[1]),
+            leaf_hash(&hashes[2]),
+        );
+        assert_eq!(merkle_root(&hashes), node_hash(&node_hash(&a, &b), &c));
+        assert_eq!(merkle_root(&hashes[..1]), a);
+        assert_eq!(merkle_root(&[]), hash_bytes(&[]));
+    }
+
+    #[test]
+    fn test_odd_levels_are_not_duplicated() {
+        let mut hashes = leaves(3);
+        let root = merkle_root(&hashes);
+        hashes.push(hashes[2].clone());
+        assert_ne!(merkle_root(&hashes), root);
+    }
+
+    #[test]
+    fn test_proofs_for_every_leaf() {
+        for n in 1..=9 {
+            let hashes = leaves(n);
+            let root = merkle_root(&hashes);
+            for (index, leaf) in hashes.iter().enumerate() {
+                let proof = merkle_proof(&hashes, index).unwrap();
+                assert_eq!(proof.index, index);
+                assert!(
+                    verify_merkle_proof(leaf, &proof, &root),
+                    "n={n} index={index}"
+                );
+
+                let other = &hashes[(index + 1) % n];
+                if n > 1 {
+                    assert!(!verify_merkle_proof(other, &proof, &root));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rejects_tampered_proofs() {
+        let hashes = leaves(4);
+        let root = merkle_root(&hashes);
+        let proof = merkle_proof(&hashes, 1).unwrap();
+
+        let mut flipped = proof.clone();
+        flipped.siblings[0].left = !flipped.siblings[0].left;
+        assert!(!verify_merkle_proof(&hashes[1], &flipped, &root));
+
+        let mut bad_hex = proof.clone();
+        bad_hex.siblings[1].hash = "zz".to_string();
+        assert!(!verify_merkle_proof(&hashes[1], &bad_hex, &root));
+
+        // An interior node is not accepted as a leaf
+        let inner = node_hash(&leaf_hash(&hashes[0]), &leaf_hash(&hashes[1]));
+        let short = MerkleProof {
+            index: 0,
+            leaf_count: 2,
+            siblings: proof.siblings[1..].to_vec(),
+        };
+        assert!(!verify_merkle_proof(&inner, &short, &root));
+    }
+
+    #[test]
+    fn test_rejects_proofs_at_another_position() {
+        for n in 1..=9 {
+            let hashes = leaves(n);
+            let root = merkle_root(&hashes);
+            for index in 0..n {
+                let proof = merkle_proof(&hashes, index).unwrap();
+                for other in (0..=n).filter(|&other| other != index) {
+                    let mut moved = proof.clone();
+                    moved.index = other;
+                    assert!(
+                        !verify_merkle_proof(&hashes[index], &moved, &root),
+                        "n={n} index={index} other={other}"
+                    );
+                }
+            }
+        }
+
+        // The root does not commit to the size, but a size that changes
+        // the path is rejected
+        let hashes = leaves(5);
+        let root = merkle_root(&hashes);
+        let proof = merkle_proof(&hashes, 2).unwrap();
+        for leaf_count in [0, 2, 3, 4] {
+            let mut resized = proof.clone();
+            resized.leaf_count = leaf_count;
+            assert!(!verify_merkle_proof(&hashes[2], &resized, &root));
+        }
+
+        let mut extra = proof.clone();
+        extra.siblings.push(proof.siblings[0].clone());
+        assert!(!verify_merkle_proof(&hashes[2], &extra, &root));
+    }
+}
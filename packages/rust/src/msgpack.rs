@@ -0,0 +1,195 @@
+//! Deterministic MessagePack
+//!
+//! In-crate MessagePack encoder for `MSGPACK_ALGORITHM`. Every value has
+//! exactly one encoding:
+//!
+//! - Map keys are sorted by their UTF-8 bytes
+//! - Integers use the smallest format that holds them; non-negative values
+//!   always use the unsigned formats (positive fixint, uint 8/16/32/64) and
+//!   negative values the signed ones (negative fixint, int 8/16/32/64)
+//! - Non-integer JSON numbers are always float 64
+//! - Strings, arrays and maps use the smallest length header (fix, 8, 16,
+//!   32 bits; strings only have the 8-bit form)
+
+use serde_json::{Number, Value};
+
+use crate::types::{Result, SdkError};
+
+/// Encode a JSON value as deterministic MessagePack
+pub(crate) fn encode(value: &Value) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    write_value(value, &mut out)?;
+    Ok(out)
+}
+
+fn write_value(value: &Value, out: &mut Vec<u8>) -> Result<()> {
+    match value {
+        Value::Null => out.push(0xc0),
+        Value::Bool(false) => out.push(0xc2),
+        Value::Bool(true) => out.push(0xc3),
+        Value::Number(n) => write_number(n, out),
+        Value::String(s) => write_str(s, out)?,
+        Value::Array(items) => {
+            write_header(items.len(), 0x90, 16, [None, Some(0xdc), Some(0xdd)], out)?;
+            for item in items {
+                write_value(item, out)?;
+            }
+        }
+        Value::Object(map) => {
+            write_header(map.len(), 0x80, 16, [None, Some(0xde), Some(0xdf)], out)?;
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+            for (key, item) in entries {
+                write_str(key, out)?;
+                write_value(item, out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_number(n: &Number, out: &mut Vec<u8>) {
+    if let Some(u) = n.as_u64() {
+        match u {
+            0..=0x7f => out.push(u as u8),
+            0x80..=0xff => out.extend_from_slice(&[0xcc, u as u8]),
+            0x100..=0xffff => {
+                out.push(0xcd);
+                out.extend_from_slice(&(u as u16).to_be_bytes());
+            }
+            0x1_0000..=0xffff_ffff => {
+                out.push(0xce);
+                out.extend_from_slice(&(u as u32).to_be_bytes());
+            }
+            _ => {
+                out.push(0xcf);
+                out.extend_from_slice(&u.to_be_bytes());
+            }
+        }
+    } else if let Some(i) = n.as_i64() {
+        // Only negative values reach here
+        if i >= -32 {
+            out.push(i as u8);
+        } else if i >= i64::from(i8::MIN) {
+            out.extend_from_slice(&[0xd0, i as u8]);
+        } else if i >= i64::from(i16::MIN) {
+            out.push(0xd1);
+            out.extend_from_slice(&(i as i16).to_be_bytes());
+        } else if i >= i64::from(i32::MIN) {
+            out.push(0xd2);
+            out.extend_from_slice(&(i as i32).to_be_bytes());
+        } else {
+            out.push(0xd3);
+            out.extend_from_slice(&i.to_be_bytes());
+        }
+    } else {
+        out.push(0xcb);
+        out.extend_from_slice(&n.as_f64().unwrap_or(f64::NAN).to_be_bytes());
+    }
+}
+
+fn write_str(s: &str, out: &mut Vec<u8>) -> Result<()> {
+    write_header(s.len(), 0xa0, 32, [Some(0xd9), Some(0xda), Some(0xdb)], out)?;
+    out.extend_from_slice(s.as_bytes());
+    Ok(())
+}
+
+/// Write the smallest length header: the fix form below `fix_limit`, then
+/// the 8-, 16- and 32-bit forms whose markers are given
+fn write_header(
+    len: usize,
+    fix_marker: u8,
+    fix_limit: usize,
+    markers: [Option<u8>; 3],
+    out: &mut Vec<u8>,
+) -> Result<()> {
+    match (len, markers) {
+        (len, _) if len < fix_limit => out.push(fix_marker | len as u8),
+        (0..=0xff, [Some(marker), _, _]) => out.extend_from_slice(&[marker, len as u8]),
+        (0..=0xffff, [_, Some(marker), _]) => {
+            out.push(marker);
+            out.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        (_, [_, _, Some(marker)]) => {
+            let len = u32::try_from(len).map_err(|_| {
+                SdkError::SerializationError(format!("MessagePack length {len} exceeds 2^32 - 1"))
+            })?;
+            out.push(marker);
+            out.extend_from_slice(&len.to_be_bytes());
+        }
+        _ => unreachable!("32-bit form is always available"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn hex_of(value: Value) -> String {
+        hex::encode(encode(&value).unwrap())
+    }
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(hex_of(json!(null)), "c0");
+        assert_eq!(hex_of(json!(false)), "c2");
+        assert_eq!(hex_of(json!(true)), "c3");
+        assert_eq!(hex_of(json!(1.5)), "cb3ff8000000000000");
+        // Integral floats stay floats
+        assert_eq!(hex_of(json!(1.0)), "cb3ff0000000000000");
+    }
+
+    #[test]
+    fn test_integers_use_smallest_format() {
+        for (n, expected) in [
+            (json!(0), "00"),
+            (json!(127), "7f"),
+            (json!(128), "cc80"),
+            (json!(255), "ccff"),
+            (json!(256), "cd0100"),
+            (json!(65535), "cdffff"),
+            (json!(65536), "ce00010000"),
+            (json!(4294967295u64), "ceffffffff"),
+            (json!(4294967296u64), "cf0000000100000000"),
+            (json!(u64::MAX), "cfffffffffffffffff"),
+            (json!(-1), "ff"),
+            (json!(-32), "e0"),
+            (json!(-33), "d0df"),
+            (json!(-128), "d080"),
+            (json!(-129), "d1ff7f"),
+            (json!(-32768), "d18000"),
+            (json!(-32769), "d2ffff7fff"),
+            (json!(-2147483648i64), "d280000000"),
+            (json!(-2147483649i64), "d3ffffffff7fffffff"),
+            (json!(i64::MIN), "d38000000000000000"),
+        ] {
+            assert_eq!(hex_of(n.clone()), expected, "{n}");
+        }
+    }
+
+    #[test]
+    fn test_length_headers() {
+        assert_eq!(hex_of(json!("")), "a0");
+        assert_eq!(hex_of(json!("a".repeat(31)))[..2], *"bf");
+        assert_eq!(hex_of(json!("a".repeat(32)))[..4], *"d920");
+        assert_eq!(hex_of(json!("a".repeat(256)))[..6], *"da0100");
+        assert_eq!(hex_of(json!([])), "90");
+        assert_eq!(hex_of(json!(vec![0; 15]))[..2], *"9f");
+        assert_eq!(hex_of(json!(vec![0; 16]))[..6], *"dc0010");
+        assert_eq!(hex_of(json!(vec![0; 65536]))[..10], *"dd00010000");
+        assert_eq!(hex_of(json!({})), "80");
+
+        let map: serde_json::Map<String, Value> =
+            (0..16).map(|i| (format!("{i:02}"), json!(i))).collect();
+        assert_eq!(hex_of(Value::Object(map))[..6], *"de0010");
+    }
+
+    #[test]
+    fn test_map_keys_sorted_by_utf8_bytes() {
+        // "Z" < "a" < "é" (0xc3 0xa9) < "😀" (0xf0 ...) by UTF-8 bytes
+        let value = json!({"😀": 4, "a": 2, "é": 3, "Z": 1});
+        assert_eq!(hex_of(value), "84a15a01a16102a2c3a903a4f09f988004");
+    }
+}
@@ -2,39 +2,84 @@
 
 use reqwest::Client;
 use serde::{de::DeserializeOwned, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
 
-use super::types::{NetworkError, NetworkResult};
+use super::types::{NetworkConfig, NetworkError, NetworkResult};
 
 const DEFAULT_TIMEOUT: u64 = 30;
+const DEFAULT_POOL_IDLE_TIMEOUT: u64 = 90;
+const DEFAULT_TCP_KEEPALIVE: u64 = 60;
 
 /// Simple HTTP client using reqwest
+///
+/// Cloning is cheap: the underlying connection pool and base URL are shared.
+///
+/// Every request method is cancellation-safe: dropping its future aborts the
+/// request and discards the connection instead of returning it to the pool.
+#[derive(Clone)]
 pub struct HttpClient {
     client: Client,
-    base_url: String,
+    base_url: Arc<str>,
 }
 
 impl HttpClient {
     /// Create a new HTTP client
     pub fn new(base_url: impl Into<String>, timeout: Option<u64>) -> NetworkResult<Self> {
-        let timeout_secs = timeout.unwrap_or(DEFAULT_TIMEOUT);
-        let client = Client::builder()
+        let config = NetworkConfig {
+            timeout,
+            ..Default::default()
+        };
+        Self::with_config(base_url, &config)
+    }
+
+    /// Create a new HTTP client with the timeout and pool settings of `config`
+    ///
+    /// The endpoint URLs in `config` are ignored in favor of `base_url`.
+    pub fn with_config(base_url: impl Into<String>, config: &NetworkConfig) -> NetworkResult<Self> {
+        let timeout_secs = config.timeout.unwrap_or(DEFAULT_TIMEOUT);
+        let mut builder = Client::builder()
             .timeout(Duration::from_secs(timeout_secs))
+            .pool_idle_timeout(Duration::from_secs(
+                config
+                    .pool_idle_timeout
+                    .unwrap_or(DEFAULT_POOL_IDLE_TIMEOUT),
+            ))
+            .tcp_keepalive(Duration::from_secs(
+                config.tcp_keepalive.unwrap_or(DEFAULT_TCP_KEEPALIVE),
+            ));
+        if let Some(max_idle) = config.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle);
+        }
+        let client = builder
             .build()
             .map_err(|e| NetworkError::http(e.to_string(), None, None))?;
 
         let url = base_url.into();
-        let base_url = url.trim_end_matches('/').to_string();
+        let base_url = Arc::from(url.trim_end_matches('/'));
 
         Ok(Self { client, base_url })
     }
 
     /// Make a GET request
     pub async fn get<T: DeserializeOwned>(&self, path: &str) -> NetworkResult<T> {
+        let response = self.send_get(path).await?;
+        self.handle_response(response).await
+    }
+
+    /// Make a GET request and return the raw response body
+    pub async fn get_text(&self, path: &str) -> NetworkResult<String> {
+        let response = self.check_status(self.send_get(path).await?).await?;
+        response
+            .text()
+            .await
+            .map_err(|e| NetworkError::http(e.to_string(), None, None))
+    }
+
+    async fn send_get(&self, path: &str) -> NetworkResult<reqwest::Response> {
         let url = format!("{}{}", self.base_url, path);
 
-        let response = self
-            .client
+        self.client
             .get(&url)
             .header("Accept", "application/json")
             .send()
@@ -45,9 +90,7 @@ impl HttpClient {
                 } else {
                     NetworkError::http(e.to_string(), None, None)
                 }
-            })?;
-
-        self.handle_response(response).await
+            })
     }
 
     /// Make a POST request
@@ -81,6 +124,14 @@ impl HttpClient {
         &self,
         response: reqwest::Response,
     ) -> NetworkResult<T> {
+        self.check_status(response)
+            .await?
+            .json()
+            .await
+            .map_err(|e| NetworkError::SerializationError(e.to_string()))
+    }
+
+    async fn check_status(&self, response: reqwest::Response) -> NetworkResult<reqwest::Response> {
         let status = response.status();
         let status_code = status.as_u16();
 
@@ -97,9 +148,6 @@ impl HttpClient {
             ));
         }
 
-        response
-            .json()
-            .await
-            .map_err(|e| NetworkError::SerializationError(e.to_string()))
+        Ok(response)
     }
 }
@@ -2,9 +2,14 @@
 
 use super::client::HttpClient;
 use super::types::{
-    NetworkConfig, NetworkError, NetworkResult, PendingTransaction, PostTransactionResponse,
+    NetworkConfig, NetworkError, NetworkResult, NodeInfo, PendingTransaction, SendTransferError,
+    SubmitResponse,
 };
-use crate::currency_types::{CurrencyTransaction, TransactionReference};
+use crate::currency_transaction::{create_currency_transaction, verify_currency_transaction};
+use crate::currency_types::{
+    CurrencyTransaction, TokenAmount, TransactionReference, TransferParams,
+};
+use crate::wallet::key_pair_from_private_key;
 
 /// Client for interacting with Currency L1 nodes
 ///
@@ -31,6 +36,10 @@ use crate::currency_types::{CurrencyTransaction, TransactionReference};
 ///     println!("Status: {:?}", pending.status);
 /// }
 /// ```
+///
+/// The client is `Send + Sync` and cheap to clone: clones share one connection
+/// pool, so create it once and hand clones to worker tasks.
+#[derive(Clone)]
 pub struct CurrencyL1Client {
     client: HttpClient,
 }
@@ -42,11 +51,11 @@ impl CurrencyL1Client {
     ///
     /// Returns an error if l1_url is not provided in the config
     pub fn new(config: NetworkConfig) -> NetworkResult<Self> {
-        let l1_url = config.l1_url.ok_or_else(|| {
+        let l1_url = config.l1_url.as_deref().ok_or_else(|| {
             NetworkError::ConfigError("l1_url is required for CurrencyL1Client".into())
         })?;
 
-        let client = HttpClient::new(l1_url, config.timeout)?;
+        let client = HttpClient::with_config(l1_url, &config)?;
         Ok(Self { client })
     }
 
@@ -54,20 +63,82 @@ impl CurrencyL1Client {
     ///
     /// This is needed to create a new transaction that chains from
     /// the address's most recent transaction.
+    ///
+    /// Enveloped or differently-cased responses are accepted, see
+    /// `TransactionReference::from_node_value`.
     pub async fn get_last_reference(&self, address: &str) -> NetworkResult<TransactionReference> {
-        self.client
+        let response: serde_json::Value = self
+            .client
             .get(&format!("/transactions/last-reference/{}", address))
-            .await
+            .await?;
+        TransactionReference::from_node_value(&response)
+            .map_err(|e| NetworkError::SerializationError(e.to_string()))
     }
 
     /// Submit a signed currency transaction to the L1 network
+    ///
+    /// The response carries the transaction hash and any metadata the
+    /// node reports, such as the assigned ordinal.
+    ///
+    /// Cancellation-safe: dropping the future aborts the request, though
+    /// the node may already have received it. See the module docs.
     pub async fn post_transaction(
         &self,
         transaction: &CurrencyTransaction,
-    ) -> NetworkResult<PostTransactionResponse> {
+    ) -> NetworkResult<SubmitResponse> {
         self.client.post("/transactions", transaction).await
     }
 
+    /// Send a transfer in one call
+    ///
+    /// Fetches the sender's last reference, builds and signs the
+    /// transaction, verifies it locally, and submits it. The error names
+    /// the step that failed. Use `get_last_reference`,
+    /// `create_currency_transaction` and `post_transaction` directly for
+    /// batches or custom flows.
+    ///
+    /// # Arguments
+    /// * `params` - Destination, amount and fee
+    /// * `private_key` - Sender's private key in hex format
+    ///
+    /// # Errors
+    /// * `SendTransferError::Reference` if the last reference can't be fetched
+    /// * `SendTransferError::Build` if the key or transfer parameters are invalid
+    /// * `SendTransferError::Verification` if the signed transaction doesn't verify
+    /// * `SendTransferError::Submit` if the node rejects or doesn't receive it
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let params = TransferParams { destination: "DAG...".to_string(), amount: 10.0, fee: 0.0 };
+    /// let response = client.send_transfer(params, &private_key).await?;
+    /// println!("Submitted: {}", response.hash);
+    /// ```
+    pub async fn send_transfer<A: Into<TokenAmount>>(
+        &self,
+        params: TransferParams<A>,
+        private_key: &str,
+    ) -> Result<SubmitResponse, SendTransferError> {
+        let source = key_pair_from_private_key(private_key)
+            .map_err(SendTransferError::Build)?
+            .address;
+        let last_ref = self
+            .get_last_reference(&source)
+            .await
+            .map_err(SendTransferError::Reference)?;
+
+        let transaction = create_currency_transaction(params, private_key, last_ref)
+            .map_err(SendTransferError::Build)?;
+        let verification = verify_currency_transaction(&transaction);
+        if !verification.is_valid {
+            return Err(SendTransferError::Verification(verification));
+        }
+
+        self.post_transaction(&transaction)
+            .await
+            .map_err(SendTransferError::Submit)
+    }
+
     /// Get a pending transaction by hash
     ///
     /// Use this to poll for transaction status after submission.
@@ -86,6 +157,14 @@ impl CurrencyL1Client {
         }
     }
 
+    /// Get the node's version, identity and state
+    ///
+    /// Use this for diagnostics and compatibility checks; `check_health`
+    /// remains the cheap liveness probe.
+    pub async fn node_info(&self) -> NetworkResult<NodeInfo> {
+        self.client.get("/node/info").await
+    }
+
     /// Check the health/availability of the L1 node
     pub async fn check_health(&self) -> bool {
         self.client
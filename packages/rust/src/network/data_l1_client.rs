@@ -1,11 +1,13 @@
 //! Data L1 client for submitting data transactions to metagraphs
 
 use serde::Serialize;
+use serde_json::Value;
 
 use super::client::HttpClient;
 use super::types::{
     EstimateFeeResponse, NetworkConfig, NetworkError, NetworkResult, PostDataResponse,
 };
+use crate::signed_object::parse_signed;
 use crate::types::Signed;
 
 /// Client for interacting with Data L1 nodes (metagraphs)
@@ -28,6 +30,10 @@ use crate::types::Signed;
 /// // Submit data
 /// let result = client.post_data(&signed_data).await?;
 /// ```
+///
+/// The client is `Send + Sync` and cheap to clone: clones share one connection
+/// pool.
+#[derive(Clone)]
 pub struct DataL1Client {
     client: HttpClient,
 }
@@ -39,11 +45,11 @@ impl DataL1Client {
     ///
     /// Returns an error if data_l1_url is not provided in the config
     pub fn new(config: NetworkConfig) -> NetworkResult<Self> {
-        let data_l1_url = config.data_l1_url.ok_or_else(|| {
+        let data_l1_url = config.data_l1_url.as_deref().ok_or_else(|| {
             NetworkError::ConfigError("data_l1_url is required for DataL1Client".into())
         })?;
 
-        let client = HttpClient::new(data_l1_url, config.timeout)?;
+        let client = HttpClient::with_config(data_l1_url, &config)?;
         Ok(Self { client })
     }
 
@@ -66,6 +72,17 @@ impl DataL1Client {
         self.client.post("/data", data).await
     }
 
+    /// Fetch a previously submitted data update by hash
+    ///
+    /// The stored envelope is parsed with `parse_signed`, so structurally
+    /// invalid envelopes are rejected. Verify the result, or compare it with
+    /// what was submitted, to catch serialization drift between this client
+    /// and the node.
+    pub async fn get_data_update(&self, hash: &str) -> NetworkResult<Signed<Value>> {
+        let body = self.client.get_text(&format!("/data/{}", hash)).await?;
+        parse_signed(&body).map_err(|e| NetworkError::SerializationError(e.to_string()))
+    }
+
     /// Check the health/availability of the Data L1 node
     pub async fn check_health(&self) -> bool {
         self.client
@@ -29,6 +29,26 @@
 //! // Submit a transaction
 //! let result = client.post_transaction(&signed_tx).await?;
 //! ```
+//!
+//! # Cancellation
+//!
+//! All client methods are cancellation-safe. Dropping a returned future, for
+//! example when `tokio::time::timeout` expires, aborts the HTTP request and
+//! closes its connection rather than returning it to the pool, so nothing
+//! leaks and no later request reads a stale response.
+//!
+//! A cancelled submission may still have reached the node. Before
+//! resubmitting, check `get_pending_transaction` with the transaction hash;
+//! resubmitting the same signed transaction is harmless either way.
+//!
+//! ```ignore
+//! use std::time::Duration;
+//!
+//! match tokio::time::timeout(Duration::from_secs(5), client.post_transaction(&tx)).await {
+//!     Ok(result) => println!("Submitted: {}", result?.hash),
+//!     Err(_) => println!("Timed out; request aborted"),
+//! }
+//! ```
 
 mod client;
 mod currency_l1_client;
@@ -5,6 +5,7 @@ use std::fmt;
 use thiserror::Error;
 
 use crate::currency_types::CurrencyTransaction;
+use crate::types::{SdkError, VerificationResult};
 
 /// Network configuration for connecting to L1 nodes
 #[derive(Debug, Clone, Default)]
@@ -15,6 +16,16 @@ pub struct NetworkConfig {
     pub data_l1_url: Option<String>,
     /// Request timeout in seconds (default: 30)
     pub timeout: Option<u64>,
+    /// Maximum idle connections kept open per host (default: unlimited)
+    ///
+    /// High-throughput submitters should leave this at least as large as
+    /// their request concurrency so connections are reused rather than
+    /// reopened.
+    pub pool_max_idle_per_host: Option<usize>,
+    /// Seconds an idle pooled connection is kept before closing (default: 90)
+    pub pool_idle_timeout: Option<u64>,
+    /// TCP keepalive interval in seconds (default: 60)
+    pub tcp_keepalive: Option<u64>,
 }
 
 /// Request options for individual requests
@@ -53,11 +64,55 @@ pub struct PendingTransaction {
     pub transaction: CurrencyTransaction,
 }
 
-/// Response from posting a transaction
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PostTransactionResponse {
+/// Response from submitting a transaction to an L1 node
+///
+/// Only `hash` is guaranteed. The other fields are filled when the node
+/// reports them and are `None` otherwise, including when the node sends
+/// them in an unexpected shape. Fields this type doesn't know about are
+/// kept in `extra`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitResponse {
     /// Transaction hash
     pub hash: String,
+    /// Ordinal assigned to the transaction by the node
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub ordinal: Option<i64>,
+    /// Status of the transaction at the time of submission
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub status: Option<TransactionStatus>,
+    /// Acceptance time in milliseconds since the Unix epoch
+    #[serde(
+        default,
+        alias = "timestamp",
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub accepted_at: Option<i64>,
+    /// Any other fields returned by the node
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response from posting a transaction
+pub type PostTransactionResponse = SubmitResponse;
+
+/// Deserialize an optional field, treating a value of the wrong shape as absent
+fn lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(serde_json::from_value(value).ok())
 }
 
 /// Response from estimating data transaction fee
@@ -76,6 +131,63 @@ pub struct PostDataResponse {
     pub hash: String,
 }
 
+/// Node information reported by an L1 node's `/node/info` endpoint
+///
+/// Useful for logging which tessellation version a client is talking to and
+/// gating behavior on it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeInfo {
+    /// Node state (e.g. "Ready")
+    pub state: String,
+    /// Node peer id (public key hex)
+    pub id: String,
+    /// Node host
+    pub host: String,
+    /// Public HTTP port
+    pub public_port: u16,
+    /// Peer-to-peer port
+    pub p2p_port: u16,
+    /// Node session token
+    #[serde(default)]
+    pub session: Option<String>,
+    /// Cluster session token, shared by all nodes in the cluster
+    #[serde(default)]
+    pub cluster_session: Option<String>,
+    /// Tessellation version (e.g. "2.8.1")
+    pub version: String,
+    /// Latest snapshot ordinal, if the node reports one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ordinal: Option<i64>,
+}
+
+impl NodeInfo {
+    /// Parse `version` into `(major, minor, patch)`
+    ///
+    /// Pre-release and build suffixes (e.g. `-rc.1`) are ignored.
+    /// Returns None if the version is not in `major.minor.patch` form.
+    pub fn version_parts(&self) -> Option<(u64, u64, u64)> {
+        let core = self
+            .version
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()?;
+        let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+        let major = parts.next()??;
+        let minor = parts.next()??;
+        let patch = parts.next()??;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some((major, minor, patch))
+    }
+
+    /// Whether the node reports itself as ready to serve requests
+    pub fn is_ready(&self) -> bool {
+        self.state == "Ready"
+    }
+}
+
 /// Network error with status code and response details
 #[derive(Error, Debug)]
 pub enum NetworkError {
@@ -119,3 +231,19 @@ impl NetworkError {
 
 /// Result type for network operations
 pub type NetworkResult<T> = std::result::Result<T, NetworkError>;
+
+/// Error from `CurrencyL1Client::send_transfer`, naming the step that failed
+#[derive(Error, Debug)]
+pub enum SendTransferError {
+    #[error("Failed to fetch last reference: {0}")]
+    Reference(NetworkError),
+
+    #[error("Failed to build transaction: {0}")]
+    Build(SdkError),
+
+    #[error("Transaction failed local verification")]
+    Verification(VerificationResult),
+
+    #[error("Failed to submit transaction: {0}")]
+    Submit(NetworkError),
+}
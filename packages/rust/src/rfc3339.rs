@@ -0,0 +1,187 @@
+//! RFC 3339 Timestamps
+//!
+//! Minimal in-crate formatting and parsing of RFC 3339 UTC timestamps, as
+//! milliseconds since the Unix epoch, for the timestamped signing helpers.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Milliseconds since the Unix epoch, now
+pub(crate) fn now_millis() -> i64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(elapsed) => elapsed.as_millis() as i64,
+        Err(before) => -(before.duration().as_millis() as i64),
+    }
+}
+
+/// Format milliseconds since the epoch as `YYYY-MM-DDTHH:MM:SS.sssZ`
+pub(crate) fn format(millis: i64) -> String {
+    let secs = millis.div_euclid(1000);
+    let days = secs.div_euclid(86_400);
+    let secs_of_day = secs.rem_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60,
+        millis.rem_euclid(1000)
+    )
+}
+
+/// Parse an RFC 3339 timestamp into milliseconds since the epoch
+///
+/// Accepts a `Z` or `±HH:MM` offset and any number of fractional second
+/// digits (truncated to milliseconds). Leap seconds are not supported.
+pub(crate) fn parse(timestamp: &str) -> Option<i64> {
+    let bytes = timestamp.as_bytes();
+    if !timestamp.is_ascii()
+        || bytes.len() < 20
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || !matches!(bytes[10], b'T' | b't' | b' ')
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return None;
+    }
+
+    let year = digits(&timestamp[0..4])?;
+    let month = digits(&timestamp[5..7])?;
+    let day = digits(&timestamp[8..10])?;
+    let hour = digits(&timestamp[11..13])?;
+    let minute = digits(&timestamp[14..16])?;
+    let second = digits(&timestamp[17..19])?;
+    if !(1..=12).contains(&month)
+        || day < 1
+        || day > days_in_month(year, month)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return None;
+    }
+
+    let mut rest = &timestamp[19..];
+    let mut millis = 0;
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let len = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if len == 0 {
+            return None;
+        }
+        millis = digits(&format!("{:0<3}", &fraction[..len.min(3)]))?;
+        rest = &fraction[len..];
+    }
+
+    let offset_minutes = match rest {
+        "Z" | "z" => 0,
+        _ if rest.len() == 6 && rest.as_bytes()[3] == b':' => {
+            let sign = match rest.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            let (hours, minutes) = (digits(&rest[1..3])?, digits(&rest[4..6])?);
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            sign * (hours * 60 + minutes)
+        }
+        _ => return None,
+    };
+
+    let days = days_from_civil(year, month, day);
+    let secs = days * 86_400 + hour * 3600 + minute * 60 + second - offset_minutes * 60;
+    Some(secs * 1000 + millis)
+}
+
+fn digits(s: &str) -> Option<i64> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Proleptic Gregorian date of a day count since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_known_instants() {
+        assert_eq!(format(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(format(951_782_400_123), "2000-02-29T00:00:00.123Z");
+        assert_eq!(format(1_700_000_000_000), "2023-11-14T22:13:20.000Z");
+        assert_eq!(format(-1), "1969-12-31T23:59:59.999Z");
+    }
+
+    #[test]
+    fn test_parse_round_trips_and_offsets() {
+        for millis in [0, 951_782_400_123, 1_700_000_000_000, 4_102_444_799_999] {
+            assert_eq!(parse(&format(millis)), Some(millis));
+        }
+        assert_eq!(parse("2023-11-14T22:13:20Z"), Some(1_700_000_000_000));
+        assert_eq!(parse("2023-11-15T00:13:20+02:00"), Some(1_700_000_000_000));
+        assert_eq!(
+            parse("2023-11-14t21:13:20.5-01:00"),
+            Some(1_700_000_000_500)
+        );
+        assert_eq!(
+            parse("2023-11-14T22:13:20.123456789Z"),
+            Some(1_700_000_000_123)
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        for bad in [
+            "",
+            "2023-11-14",
+            "2023-11-14T22:13:20",
+            "2023-13-14T22:13:20Z",
+            "2023-02-29T00:00:00Z",
+            "2023-11-14T24:00:00Z",
+            "2023-11-14T22:13:20.Z",
+            "2023-11-14T22:13:20+0200",
+            "+023-11-14T22:13:20Z",
+            "1700000000",
+            "2023-11-14T22:13:20Zé",
+            "2023-11-14T22:13:2é",
+        ] {
+            assert_eq!(parse(bad), None, "{bad}");
+        }
+    }
+}
@@ -3,13 +3,17 @@
 //! ECDSA signing using secp256k1 curve.
 //! Implements the Constellation signature protocol.
 
-use secp256k1::{Message, Secp256k1, SecretKey};
+use secp256k1::constants::CURVE_ORDER;
+use secp256k1::ecdsa::Signature;
+use secp256k1::{Message, SecretKey, SECP256K1};
 use serde::Serialize;
 
-use crate::binary::to_bytes;
-use crate::hash::{compute_digest_from_hash, hash_bytes};
-use crate::types::{Result, SdkError, SignatureProof};
-use crate::wallet::get_public_key_id;
+use crate::binary::{to_bytes, to_bytes_with_options};
+#[cfg(feature = "recovery")]
+use crate::hash::compute_signing_digest;
+use crate::hash::{compute_digest_from_hash, hash_bytes, hash_data_streaming};
+use crate::types::{Result, SdkError, SignatureError, SignatureProof, SigningOptions};
+use crate::wallet::{get_public_key_hex, get_public_key_id};
 
 /// Sign data using the regular Constellation protocol (non-DataUpdate)
 ///
@@ -76,6 +80,79 @@ pub fn sign_data_update<T: Serialize>(data: &T, private_key: &str) -> Result<Sig
     Ok(SignatureProof { id, signature })
 }
 
+/// Sign data with explicit signing options
+///
+/// # Arguments
+/// * `data` - Any serializable data
+/// * `private_key` - Private key in hex format
+/// * `options` - Signing options (DataUpdate mode, proof id encoding, size limit)
+///
+/// # Returns
+/// SignatureProof
+///
+/// # Example
+/// ```
+/// use constellation_sdk::sign::sign_with_options;
+/// use constellation_sdk::verify::verify_signature;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use constellation_sdk::SigningOptions;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let data = json!({"action": "test"});
+/// let options = SigningOptions { compressed_id: true, ..Default::default() };
+/// let proof = sign_with_options(&data, &key_pair.private_key, &options).unwrap();
+/// assert_eq!(proof.id.len(), 66);
+/// assert!(verify_signature(&data, &proof, false).unwrap());
+/// ```
+pub fn sign_with_options<T: Serialize>(
+    data: &T,
+    private_key: &str,
+    options: &SigningOptions,
+) -> Result<SignatureProof> {
+    // Serialize (enforcing any size limit) and hash
+    let bytes = to_bytes_with_options(data, options)?;
+    let hash = hash_bytes(&bytes);
+
+    // Sign the hash
+    let signature = sign_hash(&hash.value, private_key)?;
+
+    // Get public key ID in the requested encoding
+    let id = if options.compressed_id {
+        get_public_key_hex(private_key, true)?
+    } else {
+        get_public_key_id(private_key)?
+    };
+
+    Ok(SignatureProof { id, signature })
+}
+
+/// Sign data as deterministic MessagePack
+///
+/// Follows the regular protocol with `to_bytes_msgpack` in place of
+/// canonical JSON. Proofs made this way are for `MSGPACK_ALGORITHM` and
+/// only verify with `verify_msgpack`.
+///
+/// # Arguments
+/// * `data` - Any serializable data
+/// * `private_key` - Private key in hex format
+/// * `is_data_update` - Whether to sign as a DataUpdate
+///
+/// # Returns
+/// SignatureProof with public key ID and signature
+#[cfg(feature = "msgpack")]
+pub fn sign_msgpack<T: Serialize>(
+    data: &T,
+    private_key: &str,
+    is_data_update: bool,
+) -> Result<SignatureProof> {
+    let hash = hash_bytes(&crate::binary::to_bytes_msgpack(data, is_data_update)?);
+    let signature = sign_hash(&hash.value, private_key)?;
+    let id = get_public_key_id(private_key)?;
+
+    Ok(SignatureProof { id, signature })
+}
+
 /// Sign a pre-computed SHA-256 hash
 ///
 /// # Arguments
@@ -85,7 +162,7 @@ pub fn sign_data_update<T: Serialize>(data: &T, private_key: &str) -> Result<Sig
 /// # Returns
 /// DER-encoded signature in hex format
 pub fn sign_hash(hash_hex: &str, private_key: &str) -> Result<String> {
-    let secp = Secp256k1::new();
+    let secp = SECP256K1;
 
     // Parse private key
     let private_key_bytes = hex::decode(private_key)?;
@@ -105,12 +182,435 @@ pub fn sign_hash(hash_hex: &str, private_key: &str) -> Result<String> {
     Ok(hex::encode(signature.serialize_der()))
 }
 
+/// Encode a DER signature in both DER and compact form
+///
+/// Both encodings represent the same ECDSA signature, so both verify against
+/// the same hash and public key; pick whichever a given verifier accepts.
+/// The compact form is 64 bytes, `r || s`, big-endian.
+///
+/// # Arguments
+/// * `der_hex` - DER-encoded signature in hex format
+///
+/// # Returns
+/// `(der_hex, compact_hex)`, with the DER form re-serialized canonically
+///
+/// # Example
+/// ```
+/// use constellation_sdk::sign::{compact_to_der, dual_encode_signature, sign_hash};
+/// use constellation_sdk::verify::verify_hash;
+/// use constellation_sdk::wallet::generate_key_pair;
+///
+/// let key_pair = generate_key_pair();
+/// let hash_hex = "a".repeat(64);
+/// let signature = sign_hash(&hash_hex, &key_pair.private_key).unwrap();
+///
+/// let (der, compact) = dual_encode_signature(&signature).unwrap();
+/// assert_eq!(compact.len(), 128);
+/// assert_eq!(compact_to_der(&compact).unwrap(), der);
+/// assert!(verify_hash(&hash_hex, &der, &key_pair.public_key).unwrap());
+/// ```
+pub fn dual_encode_signature(der_hex: &str) -> Result<(String, String)> {
+    let signature = parse_der(der_hex)?;
+    Ok((
+        hex::encode(signature.serialize_der()),
+        hex::encode(signature.serialize_compact()),
+    ))
+}
+
+/// Convert a DER signature to compact (`r || s`) form
+///
+/// # Arguments
+/// * `der_hex` - DER-encoded signature in hex format
+///
+/// # Returns
+/// 64-byte compact signature in hex format (128 characters)
+pub fn der_to_compact(der_hex: &str) -> Result<String> {
+    Ok(hex::encode(parse_der(der_hex)?.serialize_compact()))
+}
+
+/// Convert a compact (`r || s`) signature to DER form
+///
+/// # Arguments
+/// * `compact_hex` - 64-byte compact signature in hex format
+///
+/// # Returns
+/// DER-encoded signature in hex format
+pub fn compact_to_der(compact_hex: &str) -> Result<String> {
+    let signature = Signature::from_compact(&hex::decode(compact_hex)?)
+        .map_err(|e| SdkError::InvalidSignature(e.to_string()))?;
+    Ok(hex::encode(signature.serialize_der()))
+}
+
+/// A DER signature split into its scalars
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParsedSignature {
+    /// `r` scalar, 32 bytes big-endian
+    pub r: [u8; 32],
+    /// `s` scalar, 32 bytes big-endian
+    pub s: [u8; 32],
+}
+
+impl ParsedSignature {
+    /// Whether `s` is in the upper half of the curve order (malleable form)
+    pub fn is_high_s(&self) -> bool {
+        let mut compact = [0u8; 64];
+        compact[..32].copy_from_slice(&self.r);
+        compact[32..].copy_from_slice(&self.s);
+        Signature::from_compact(&compact).is_ok_and(|sig| crate::verify::is_high_s(&sig))
+    }
+}
+
+/// Parse a hex DER signature, reporting exactly what is wrong with it
+///
+/// Distinguishes a malformed signature from one that parses but does not
+/// match: bad hex, a broken DER structure (tags, lengths, non-minimal or
+/// negative integers, trailing bytes), and `r`/`s` outside `1..n` each give
+/// a different `SignatureError`.
+///
+/// # Arguments
+/// * `der_hex` - DER-encoded signature in hex format
+///
+/// # Returns
+/// The signature scalars, or `SdkError::MalformedSignature`
+///
+/// # Example
+/// ```
+/// use constellation_sdk::sign::{parse_der_signature, sign_hash};
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use constellation_sdk::{SdkError, SignatureError};
+///
+/// let key_pair = generate_key_pair();
+/// let signature = sign_hash(&"a".repeat(64), &key_pair.private_key).unwrap();
+/// assert!(!parse_der_signature(&signature).unwrap().is_high_s());
+///
+/// assert!(matches!(
+///     parse_der_signature("zz"),
+///     Err(SdkError::MalformedSignature(SignatureError::BadHex(_)))
+/// ));
+/// ```
+pub fn parse_der_signature(der_hex: &str) -> Result<ParsedSignature> {
+    let bytes = hex::decode(der_hex).map_err(|e| SignatureError::BadHex(e.to_string()))?;
+    let bad_der = |msg: &str| SignatureError::BadDer(msg.to_string());
+
+    let body = match bytes.as_slice() {
+        [0x30, len, body @ ..] if usize::from(*len) == body.len() && *len < 0x80 => body,
+        [0x30, ..] => return Err(bad_der("sequence length does not match").into()),
+        _ => return Err(bad_der("expected a SEQUENCE").into()),
+    };
+    let (r, rest) = read_der_integer(body, "r")?;
+    let (s, rest) = read_der_integer(rest, "s")?;
+    if !rest.is_empty() {
+        return Err(bad_der("trailing bytes after s").into());
+    }
+
+    Ok(ParsedSignature {
+        r: to_scalar(r, "r")?,
+        s: to_scalar(s, "s")?,
+    })
+}
+
+/// Read one strict-DER INTEGER, returning its content and the remaining bytes
+fn read_der_integer<'a>(
+    bytes: &'a [u8],
+    name: &str,
+) -> std::result::Result<(&'a [u8], &'a [u8]), SignatureError> {
+    let bad_der = |msg: &str| SignatureError::BadDer(format!("{name}: {msg}"));
+    let (len, rest) = match bytes {
+        [0x02, len, rest @ ..] => (usize::from(*len), rest),
+        _ => return Err(bad_der("expected an INTEGER")),
+    };
+    if len == 0 || len > rest.len() {
+        return Err(bad_der("invalid length"));
+    }
+    let (value, rest) = rest.split_at(len);
+    if value[0] & 0x80 != 0 {
+        return Err(bad_der("negative integer"));
+    }
+    if len > 1 && value[0] == 0 && value[1] & 0x80 == 0 {
+        return Err(bad_der("non-minimal integer encoding"));
+    }
+    Ok((value, rest))
+}
+
+/// Check a DER integer is in `1..n` and widen it to 32 bytes
+fn to_scalar(value: &[u8], name: &'static str) -> std::result::Result<[u8; 32], SignatureError> {
+    let value = &value[value.iter().take_while(|&&b| b == 0).count()..];
+    if value.is_empty() || value.len() > 32 {
+        return Err(SignatureError::OutOfRange(name));
+    }
+    let mut scalar = [0u8; 32];
+    scalar[32 - value.len()..].copy_from_slice(value);
+    if scalar >= CURVE_ORDER {
+        return Err(SignatureError::OutOfRange(name));
+    }
+    Ok(scalar)
+}
+
+fn parse_der(der_hex: &str) -> Result<Signature> {
+    Signature::from_der(&hex::decode(der_hex)?)
+        .map_err(|e| SdkError::InvalidSignature(e.to_string()))
+}
+
+/// A signer that parses its private key and derives its proof id once
+///
+/// `sign` re-decodes the hex key and re-derives the public key id on every
+/// call; use this for repeated signing with the same key.
+///
+/// The secret key is erased when the signer is dropped. Each clone holds
+/// its own copy of the secret, erased when that clone is dropped.
+///
+/// # Example
+/// ```
+/// use constellation_sdk::sign::PreparedSigner;
+/// use constellation_sdk::verify::verify_signature;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let signer = PreparedSigner::new(&key_pair.private_key).unwrap();
+/// for i in 0..3 {
+///     let data = json!({"seq": i});
+///     let proof = signer.sign(&data, false).unwrap();
+///     assert!(verify_signature(&data, &proof, false).unwrap());
+/// }
+/// ```
+#[derive(Clone)]
+pub struct PreparedSigner {
+    secret_key: SecretKey,
+    id: String,
+}
+
+impl PreparedSigner {
+    /// Parse a private key and derive its proof id
+    ///
+    /// # Arguments
+    /// * `private_key` - Private key in hex format
+    pub fn new(private_key: &str) -> Result<Self> {
+        let secret_key = SecretKey::from_slice(&hex::decode(private_key)?)?;
+        let id = get_public_key_id(private_key)?;
+        Ok(Self { secret_key, id })
+    }
+
+    /// Public key id (128 characters, without 04 prefix) used in proofs
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Sign data, as a DataUpdate if `is_data_update` is set
+    ///
+    /// # Arguments
+    /// * `data` - Any serializable data
+    /// * `is_data_update` - Whether to sign as a DataUpdate
+    ///
+    /// # Returns
+    /// SignatureProof
+    pub fn sign<T: Serialize>(&self, data: &T, is_data_update: bool) -> Result<SignatureProof> {
+        let bytes = to_bytes(data, is_data_update)?;
+        let hash = hash_bytes(&bytes);
+        Ok(SignatureProof {
+            id: self.id.clone(),
+            signature: self.sign_hash(&hash.value),
+        })
+    }
+
+    /// Sign a pre-computed SHA-256 hash
+    ///
+    /// # Arguments
+    /// * `hash_hex` - SHA-256 hash as 64-character hex string
+    ///
+    /// # Returns
+    /// DER-encoded signature in hex format
+    pub fn sign_hash(&self, hash_hex: &str) -> String {
+        let message = Message::from_digest(compute_digest_from_hash(hash_hex));
+        let signature = SECP256K1.sign_ecdsa(&message, &self.secret_key);
+        hex::encode(signature.serialize_der())
+    }
+}
+
+impl std::fmt::Debug for PreparedSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the secret key
+        f.debug_struct("PreparedSigner")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for PreparedSigner {
+    fn drop(&mut self) {
+        self.secret_key.non_secure_erase();
+    }
+}
+
+/// A session for signing a stream of records with one key
+///
+/// Like `PreparedSigner`, the key is parsed and the proof id derived once.
+/// Each record is hashed with `hash_data_streaming`, so large records are
+/// signed without buffering their canonical encoding.
+///
+/// # Example
+/// ```
+/// use constellation_sdk::sign::SigningSession;
+/// use constellation_sdk::verify::verify_signature;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let session = SigningSession::new(&key_pair.private_key).unwrap();
+/// for line in ["started", "processed 10 items", "stopped"] {
+///     let record = json!({"log": line});
+///     let proof = session.sign_next(&record).unwrap();
+///     assert!(verify_signature(&record, &proof, false).unwrap());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SigningSession {
+    signer: PreparedSigner,
+    is_data_update: bool,
+}
+
+impl SigningSession {
+    /// Start a session that signs records in regular (non-DataUpdate) mode
+    ///
+    /// # Arguments
+    /// * `private_key` - Private key in hex format
+    pub fn new(private_key: &str) -> Result<Self> {
+        Ok(Self {
+            signer: PreparedSigner::new(private_key)?,
+            is_data_update: false,
+        })
+    }
+
+    /// Sign records as DataUpdates instead
+    pub fn with_data_update(mut self, is_data_update: bool) -> Self {
+        self.is_data_update = is_data_update;
+        self
+    }
+
+    /// Public key id (128 characters, without 04 prefix) used in proofs
+    pub fn id(&self) -> &str {
+        self.signer.id()
+    }
+
+    /// Sign the next record
+    ///
+    /// # Arguments
+    /// * `data` - Any serializable record
+    ///
+    /// # Returns
+    /// SignatureProof
+    pub fn sign_next<T: Serialize>(&self, data: &T) -> Result<SignatureProof> {
+        let hash = hash_data_streaming(data, self.is_data_update)?;
+        Ok(SignatureProof {
+            id: self.signer.id().to_string(),
+            signature: self.signer.sign_hash(&hash.value),
+        })
+    }
+}
+
+/// Sign a 32-byte digest as an EIP-2098 compact recoverable signature
+///
+/// Unlike `sign_hash`, the digest is signed directly (no Constellation
+/// SHA-512 transform), as EVM contracts verify with `ecrecover(digest, ...)`.
+/// The result is 64 bytes: `r || yParityAndS`, where the top bit of the
+/// second half holds the recovery parity.
+///
+/// # Arguments
+/// * `digest_hex` - 32-byte digest in hex format (e.g. a Keccak-256 hash)
+/// * `private_key` - Private key in hex format
+///
+/// # Returns
+/// 64-byte compact signature in hex format (128 characters)
+#[cfg(feature = "recovery")]
+pub fn sign_hash_eip2098(digest_hex: &str, private_key: &str) -> Result<String> {
+    let secp = SECP256K1;
+
+    let secret_key = SecretKey::from_slice(&hex::decode(private_key)?)?;
+    let message = Message::from_digest(compute_signing_digest(digest_hex, false)?);
+
+    let (recovery_id, compact) = secp
+        .sign_ecdsa_recoverable(&message, &secret_key)
+        .serialize_compact();
+
+    let y_parity = recovery_id.to_i32();
+    if y_parity > 1 {
+        return Err(SdkError::CryptoError(
+            "Recovery id cannot be encoded as EIP-2098".to_string(),
+        ));
+    }
+
+    // Signatures are low-S, so the top bit of s is free for the parity
+    let mut signature = compact;
+    signature[32] |= (y_parity as u8) << 7;
+    Ok(hex::encode(signature))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::wallet::generate_key_pair;
     use serde_json::json;
 
+    #[test]
+    fn test_parse_der_signature_classifies_errors() {
+        let key_pair = generate_key_pair();
+        let der = sign_hash(&"a".repeat(64), &key_pair.private_key).unwrap();
+        let parsed = parse_der_signature(&der).unwrap();
+        assert_eq!(
+            hex::encode([parsed.r, parsed.s].concat()),
+            der_to_compact(&der).unwrap()
+        );
+
+        let malformed = |hex: &str| match parse_der_signature(hex) {
+            Err(SdkError::MalformedSignature(e)) => e,
+            other => panic!("expected MalformedSignature, got {other:?}"),
+        };
+        assert!(matches!(malformed("3g"), SignatureError::BadHex(_)));
+        assert!(matches!(malformed("3100"), SignatureError::BadDer(_)));
+        assert!(matches!(
+            malformed(&format!("{der}00")),
+            SignatureError::BadDer(_)
+        ));
+        // Negative r, and r with a redundant leading zero
+        assert!(matches!(
+            malformed("3006020180020101"),
+            SignatureError::BadDer(_)
+        ));
+        assert!(matches!(
+            malformed("300702020001020101"),
+            SignatureError::BadDer(_)
+        ));
+        // r = 0, and s = n
+        assert_eq!(
+            malformed("3006020100020101"),
+            SignatureError::OutOfRange("r")
+        );
+        let n = hex::encode(CURVE_ORDER);
+        assert_eq!(
+            malformed(&format!("3026020101022100{n}")),
+            SignatureError::OutOfRange("s")
+        );
+    }
+
+    #[test]
+    fn test_parsed_signature_is_high_s() {
+        let key_pair = generate_key_pair();
+        let der = sign_hash(&"a".repeat(64), &key_pair.private_key).unwrap();
+        let mut sig = Signature::from_der(&hex::decode(&der).unwrap()).unwrap();
+        let mut compact = sig.serialize_compact();
+
+        // s' = n - s is the malleable twin of s
+        let mut borrow = 0i16;
+        for i in (32..64).rev() {
+            let diff = i16::from(CURVE_ORDER[i - 32]) - i16::from(compact[i]) - borrow;
+            compact[i] = diff.rem_euclid(256) as u8;
+            borrow = i16::from(diff < 0);
+        }
+        sig = Signature::from_compact(&compact).unwrap();
+        let high = parse_der_signature(&hex::encode(sig.serialize_der())).unwrap();
+        assert!(high.is_high_s());
+    }
+
     #[test]
     fn test_sign() {
         let key_pair = generate_key_pair();
@@ -131,6 +631,70 @@ mod tests {
         assert!(!proof.signature.is_empty());
     }
 
+    #[test]
+    fn test_dual_encoding_verifies() {
+        let key_pair = generate_key_pair();
+        let hash_hex = "b".repeat(64);
+        let der = sign_hash(&hash_hex, &key_pair.private_key).unwrap();
+
+        let (der_again, compact) = dual_encode_signature(&der).unwrap();
+        assert_eq!(der_again, der);
+        assert_eq!(der_to_compact(&der).unwrap(), compact);
+        assert_eq!(compact_to_der(&compact).unwrap(), der);
+
+        // The compact form verifies directly against the same digest and key
+        let signature = Signature::from_compact(&hex::decode(&compact).unwrap()).unwrap();
+        let message = Message::from_digest(compute_digest_from_hash(&hash_hex));
+        let public_key =
+            secp256k1::PublicKey::from_slice(&hex::decode(&key_pair.public_key).unwrap()).unwrap();
+        assert!(SECP256K1
+            .verify_ecdsa(&message, &signature, &public_key)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_dual_encoding_rejects_malformed() {
+        assert!(dual_encode_signature("3000").is_err());
+        assert!(der_to_compact("zz").is_err());
+        assert!(compact_to_der(&"ab".repeat(10)).is_err());
+    }
+
+    #[test]
+    fn test_prepared_signer_matches_sign() {
+        let key_pair = generate_key_pair();
+        let signer = PreparedSigner::new(&key_pair.private_key).unwrap();
+        let data = json!({"id": "test"});
+
+        // RFC 6979 signatures are deterministic
+        assert_eq!(
+            signer.sign(&data, false).unwrap(),
+            sign(&data, &key_pair.private_key).unwrap()
+        );
+        assert_eq!(
+            signer.sign(&data, true).unwrap(),
+            sign_data_update(&data, &key_pair.private_key).unwrap()
+        );
+        assert!(!format!("{signer:?}").contains(&key_pair.private_key));
+        assert!(PreparedSigner::new("not hex").is_err());
+    }
+
+    #[test]
+    fn test_signing_session_matches_sign() {
+        let key_pair = generate_key_pair();
+        let session = SigningSession::new(&key_pair.private_key).unwrap();
+        let record = json!({"seq": 1, "log": "x".repeat(10_000)});
+
+        assert_eq!(session.id(), key_pair.public_key[2..].to_string());
+        assert_eq!(
+            session.sign_next(&record).unwrap(),
+            sign(&record, &key_pair.private_key).unwrap()
+        );
+        assert_eq!(
+            session.with_data_update(true).sign_next(&record).unwrap(),
+            sign_data_update(&record, &key_pair.private_key).unwrap()
+        );
+    }
+
     #[test]
     fn test_sign_different_for_regular_vs_data_update() {
         let key_pair = generate_key_pair();
@@ -157,4 +721,38 @@ mod tests {
         // Note: ECDSA signatures may include random k value
         // so signatures might differ, but both should be valid
     }
+
+    #[test]
+    fn test_sign_with_options_compressed_id() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test"});
+        let options = SigningOptions {
+            compressed_id: true,
+            ..Default::default()
+        };
+
+        let proof = sign_with_options(&data, &key_pair.private_key, &options).unwrap();
+        assert_eq!(proof.id.len(), 66);
+        assert!(proof.id.starts_with("02") || proof.id.starts_with("03"));
+
+        let default_proof =
+            sign_with_options(&data, &key_pair.private_key, &SigningOptions::default()).unwrap();
+        assert_eq!(
+            default_proof.id,
+            get_public_key_id(&key_pair.private_key).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_sign_with_options_rejects_large_payload() {
+        let key_pair = generate_key_pair();
+        let data = json!({"blob": "x".repeat(1000)});
+        let options = SigningOptions {
+            max_bytes: Some(100),
+            ..Default::default()
+        };
+
+        let result = sign_with_options(&data, &key_pair.private_key, &options);
+        assert!(matches!(result, Err(SdkError::PayloadTooLarge(100))));
+    }
 }
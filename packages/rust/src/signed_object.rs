@@ -2,10 +2,184 @@
 //!
 //! Convenience functions for creating and managing signed objects.
 
+use secp256k1::ecdsa::Signature;
+use secp256k1::PublicKey;
+#[cfg(feature = "std")]
+use serde::de::DeserializeOwned;
 use serde::Serialize;
+use serde_json::Value;
 
+use crate::hash::hash_data;
+use crate::rfc3339;
 use crate::sign::{sign, sign_data_update};
-use crate::types::{Result, SdkError, Signed};
+use crate::types::{Hash, Result, SdkError, SignatureProof, Signed};
+use crate::wallet::{address_from_proof, normalize_public_key};
+
+/// Field `create_timestamped` adds to the signed value
+pub const SIGNED_AT_FIELD: &str = "_signedAt";
+
+impl<T: Serialize> Signed<T> {
+    /// Compute a deterministic content id for this signed object
+    ///
+    /// The id is the SHA-256 hash (hex) of the canonical JSON of the value only.
+    /// Proofs are not included, so two objects with the same value but different
+    /// proof sets (e.g. before and after adding a multi-sig signature) share an id.
+    ///
+    /// # Returns
+    /// 64-character hex string
+    ///
+    /// # Example
+    /// ```
+    /// use constellation_sdk::signed_object::{add_signature, create_signed_object};
+    /// use constellation_sdk::wallet::generate_key_pair;
+    /// use serde_json::json;
+    ///
+    /// let key1 = generate_key_pair();
+    /// let key2 = generate_key_pair();
+    /// let signed = create_signed_object(&json!({"id": "test"}), &key1.private_key, false).unwrap();
+    /// let id = signed.id().unwrap();
+    ///
+    /// let signed = add_signature(signed, &key2.private_key, false).unwrap();
+    /// assert_eq!(signed.id().unwrap(), id);
+    /// ```
+    pub fn id(&self) -> Result<String> {
+        Ok(hash_data(&self.value, false)?.value)
+    }
+}
+
+impl<T> Signed<T> {
+    /// Transform the value, keeping the existing proofs
+    ///
+    /// The proofs are carried over unchanged, so they stay valid only if the
+    /// new value canonicalizes to exactly the same JSON as the old one, e.g.
+    /// deserializing a verified `Signed<Value>` into a struct that keeps
+    /// every field with the same names and values. A transform that drops,
+    /// renames or reformats fields produces an object whose proofs no longer
+    /// verify; use `try_map_resign` for those.
+    ///
+    /// # Arguments
+    /// * `f` - Fallible transform applied to the value
+    ///
+    /// # Returns
+    /// The transformed signed object, or the error returned by `f`
+    ///
+    /// # Example
+    /// ```
+    /// use constellation_sdk::signed_object::create_signed_object;
+    /// use constellation_sdk::verify::verify;
+    /// use constellation_sdk::wallet::generate_key_pair;
+    /// use serde::{Deserialize, Serialize};
+    /// use serde_json::json;
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Reading {
+    ///     id: String,
+    ///     value: u64,
+    /// }
+    ///
+    /// let key_pair = generate_key_pair();
+    /// let signed = create_signed_object(&json!({"id": "a", "value": 7}), &key_pair.private_key, false).unwrap();
+    /// assert!(verify(&signed, false).is_valid);
+    ///
+    /// let typed = signed.try_map(|v| Ok(serde_json::from_value::<Reading>(v)?)).unwrap();
+    /// assert_eq!(typed.value.value, 7);
+    /// assert!(verify(&typed, false).is_valid);
+    /// ```
+    pub fn try_map<U, F: Fn(T) -> Result<U>>(self, f: F) -> Result<Signed<U>> {
+        Ok(Signed {
+            value: f(self.value)?,
+            proofs: self.proofs,
+        })
+    }
+
+    /// Transform the value and sign the result afresh
+    ///
+    /// For transforms that change the canonical JSON. The original proofs
+    /// are discarded and replaced by one proof per key over the new value,
+    /// so the result attests to the signers of this call, not to the
+    /// original signers; verify the original before transforming it.
+    ///
+    /// # Arguments
+    /// * `f` - Fallible transform applied to the value
+    /// * `private_keys` - Keys to sign the new value with
+    /// * `is_data_update` - Whether to sign as DataUpdate
+    ///
+    /// # Returns
+    /// The transformed and re-signed object
+    pub fn try_map_resign<U, F>(
+        self,
+        f: F,
+        private_keys: &[&str],
+        is_data_update: bool,
+    ) -> Result<Signed<U>>
+    where
+        U: Serialize + Clone,
+        F: Fn(T) -> Result<U>,
+    {
+        batch_sign(&f(self.value)?, private_keys, is_data_update)
+    }
+
+    /// Signer ids claimed by the proofs, without verifying them
+    ///
+    /// **UNVERIFIED.** Anyone can put any public key in a proof id, so these
+    /// are claims only and must never be used for authentication or
+    /// authorization. They are meant for cheap bucketing or indexing of
+    /// incoming objects before the expensive `verify` step.
+    ///
+    /// # Returns
+    /// Proof ids in proof order, with duplicates removed
+    ///
+    /// # Example
+    /// ```
+    /// use constellation_sdk::signed_object::create_signed_object;
+    /// use constellation_sdk::wallet::generate_key_pair;
+    /// use serde_json::json;
+    ///
+    /// let key_pair = generate_key_pair();
+    /// let signed = create_signed_object(&json!({"id": "test"}), &key_pair.private_key, false).unwrap();
+    /// assert_eq!(signed.claimed_signers(), vec![key_pair.public_key[2..].to_string()]);
+    /// ```
+    pub fn claimed_signers(&self) -> Vec<String> {
+        let mut ids: Vec<String> = Vec::with_capacity(self.proofs.len());
+        for proof in &self.proofs {
+            if !ids.contains(&proof.id) {
+                ids.push(proof.id.clone());
+            }
+        }
+        ids
+    }
+
+    /// DAG addresses derived from the claimed signer ids, without verifying
+    ///
+    /// **UNVERIFIED.** Like `claimed_signers`, these are claims only and must
+    /// never be trusted for authentication or authorization. No signature is
+    /// checked; only the ids are decoded.
+    ///
+    /// # Returns
+    /// Addresses in proof order with duplicates removed, or an error if any
+    /// proof id is not a valid public key
+    ///
+    /// # Example
+    /// ```
+    /// use constellation_sdk::signed_object::create_signed_object;
+    /// use constellation_sdk::wallet::generate_key_pair;
+    /// use serde_json::json;
+    ///
+    /// let key_pair = generate_key_pair();
+    /// let signed = create_signed_object(&json!({"id": "test"}), &key_pair.private_key, false).unwrap();
+    /// assert_eq!(signed.claimed_addresses().unwrap(), vec![key_pair.address]);
+    /// ```
+    pub fn claimed_addresses(&self) -> Result<Vec<String>> {
+        let mut addresses: Vec<String> = Vec::with_capacity(self.proofs.len());
+        for proof in &self.proofs {
+            let address = address_from_proof(proof)?;
+            if !addresses.contains(&address) {
+                addresses.push(address);
+            }
+        }
+        Ok(addresses)
+    }
+}
 
 /// Create a signed object with a single signature
 ///
@@ -156,6 +330,293 @@ pub fn batch_sign<T: Serialize + Clone>(
     })
 }
 
+/// Create a signed object whose value records when it was signed
+///
+/// Adds a `"_signedAt"` field holding the current time as an RFC 3339 UTC
+/// timestamp with millisecond precision (e.g. `2026-01-02T03:04:05.678Z`),
+/// replacing any existing one, and signs the result. The timestamp is part
+/// of the signed content: it cannot be changed without invalidating the
+/// signature, and verifiers must hash the value with it included. Use
+/// `verify_with_max_age` to enforce an age limit.
+///
+/// # Arguments
+/// * `data` - Data to sign; must serialize to a JSON object
+/// * `private_key` - Private key in hex format
+/// * `is_data_update` - Whether to sign as a DataUpdate
+///
+/// # Returns
+/// The signed value with its timestamp, or `SdkError::InvalidEnvelope` if
+/// the data is not an object
+///
+/// # Example
+/// ```
+/// use constellation_sdk::signed_object::{create_timestamped, SIGNED_AT_FIELD};
+/// use constellation_sdk::verify::verify;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let signed = create_timestamped(&json!({"id": "a"}), &key_pair.private_key, false).unwrap();
+/// assert!(signed.value[SIGNED_AT_FIELD].as_str().unwrap().ends_with('Z'));
+/// assert!(verify(&signed, false).is_valid);
+/// ```
+pub fn create_timestamped<T: Serialize>(
+    data: &T,
+    private_key: &str,
+    is_data_update: bool,
+) -> Result<Signed<Value>> {
+    let mut value = serde_json::to_value(data)?;
+    let object = value.as_object_mut().ok_or_else(|| {
+        SdkError::InvalidEnvelope("timestamped data must be a JSON object".to_string())
+    })?;
+    object.insert(
+        SIGNED_AT_FIELD.to_string(),
+        Value::String(rfc3339::format(rfc3339::now_millis())),
+    );
+
+    create_signed_object(&value, private_key, is_data_update)
+}
+
+/// Split a multi-signature object into one single-proof object per signer
+///
+/// Each result carries a clone of the value and one of the proofs, in the
+/// original proof order, and verifies on its own if that proof is valid.
+/// An object with no proofs splits into an empty vector.
+///
+/// # Arguments
+/// * `signed` - Signed object to split
+///
+/// # Returns
+/// One `Signed<T>` per proof
+///
+/// # Example
+/// ```
+/// use constellation_sdk::signed_object::{batch_sign, split_proofs};
+/// use constellation_sdk::verify::verify;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key1 = generate_key_pair();
+/// let key2 = generate_key_pair();
+/// let signed = batch_sign(&json!({"id": "test"}), &[&key1.private_key, &key2.private_key], false).unwrap();
+///
+/// let parts = split_proofs(&signed);
+/// assert_eq!(parts.len(), 2);
+/// assert!(parts.iter().all(|part| part.proofs.len() == 1 && verify(part, false).is_valid));
+/// ```
+pub fn split_proofs<T: Clone>(signed: &Signed<T>) -> Vec<Signed<T>> {
+    signed
+        .proofs
+        .iter()
+        .map(|proof| Signed {
+            value: signed.value.clone(),
+            proofs: vec![proof.clone()],
+        })
+        .collect()
+}
+
+/// Hash the entire signed envelope, including proofs
+///
+/// Canonicalizes `{"value": ..., "proofs": [...]}` with proofs sorted by
+/// `(id, signature)` and hashes it with SHA-256. Unlike [`Signed::id`], changing
+/// any proof (or the value) changes this hash, while proof order does not,
+/// making it suitable for tamper-evident audit logs.
+///
+/// # Arguments
+/// * `signed` - Signed object to hash
+///
+/// # Returns
+/// Hash of the canonical envelope
+///
+/// # Example
+/// ```
+/// use constellation_sdk::signed_object::{create_signed_object, envelope_hash};
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let signed = create_signed_object(&json!({"id": "test"}), &key_pair.private_key, false).unwrap();
+/// let hash = envelope_hash(&signed).unwrap();
+/// assert_ne!(hash.value, signed.id().unwrap());
+/// ```
+pub fn envelope_hash<T: Serialize>(signed: &Signed<T>) -> Result<Hash> {
+    #[derive(Serialize)]
+    struct Envelope<'a, T> {
+        value: &'a T,
+        proofs: Vec<&'a SignatureProof>,
+    }
+
+    let mut proofs: Vec<&SignatureProof> = signed.proofs.iter().collect();
+    proofs.sort();
+
+    hash_data(
+        &Envelope {
+            value: &signed.value,
+            proofs,
+        },
+        false,
+    )
+}
+
+/// Check that a signed object is structurally well-formed
+///
+/// Requires at least one proof, and that every proof id decodes to a
+/// secp256k1 public key and every signature is DER-encoded hex. This does
+/// not check that the signatures are valid; use `verify` for that.
+///
+/// # Arguments
+/// * `signed` - Signed object to check
+///
+/// # Returns
+/// `SdkError::InvalidEnvelope` if there are no proofs,
+/// `SdkError::InvalidPublicKey` or `SdkError::InvalidSignature` for a bad proof
+pub fn validate_structure<T>(signed: &Signed<T>) -> Result<()> {
+    if signed.proofs.is_empty() {
+        return Err(SdkError::InvalidEnvelope(
+            "at least one proof is required".to_string(),
+        ));
+    }
+
+    for (i, proof) in signed.proofs.iter().enumerate() {
+        hex::decode(normalize_public_key(&proof.id))
+            .ok()
+            .and_then(|bytes| PublicKey::from_slice(&bytes).ok())
+            .ok_or_else(|| SdkError::InvalidPublicKey(format!("proof {i} has an invalid id")))?;
+
+        hex::decode(&proof.signature)
+            .ok()
+            .and_then(|bytes| Signature::from_der(&bytes).ok())
+            .ok_or_else(|| {
+                SdkError::InvalidSignature(format!("proof {i} is not DER-encoded hex"))
+            })?;
+    }
+
+    Ok(())
+}
+
+/// Parse a signed object from untrusted JSON
+///
+/// Deserializes the envelope and runs `validate_structure` before returning,
+/// so callers get a single hardened entry point.
+///
+/// # Arguments
+/// * `json` - JSON text of a `{"value": ..., "proofs": [...]}` envelope
+///
+/// # Returns
+/// The parsed signed object, or:
+/// * `SdkError::SerializationError` if the input is not valid JSON
+/// * `SdkError::InvalidEnvelope` if `value` or `proofs` is missing or
+///   mistyped, or there are no proofs
+/// * `SdkError::InvalidPublicKey` / `SdkError::InvalidSignature` for a
+///   badly encoded proof
+///
+/// # Example
+/// ```
+/// use constellation_sdk::signed_object::{create_signed_object, parse_signed};
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use constellation_sdk::SdkError;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let signed = create_signed_object(&json!({"id": "test"}), &key_pair.private_key, false).unwrap();
+/// let text = serde_json::to_string(&signed).unwrap();
+/// assert_eq!(parse_signed(&text).unwrap(), signed);
+///
+/// assert!(matches!(parse_signed(r#"{"value": 1}"#), Err(SdkError::InvalidEnvelope(_))));
+/// ```
+pub fn parse_signed(json: &str) -> Result<Signed<Value>> {
+    let mut envelope: Value = serde_json::from_str(json)?;
+    let object = envelope
+        .as_object_mut()
+        .ok_or_else(|| SdkError::InvalidEnvelope("expected a JSON object".to_string()))?;
+
+    let value = object
+        .remove("value")
+        .ok_or_else(|| SdkError::InvalidEnvelope("missing field `value`".to_string()))?;
+    let proofs = object
+        .remove("proofs")
+        .ok_or_else(|| SdkError::InvalidEnvelope("missing field `proofs`".to_string()))?;
+    let proofs: Vec<SignatureProof> = serde_json::from_value(proofs)
+        .map_err(|e| SdkError::InvalidEnvelope(format!("invalid `proofs`: {e}")))?;
+
+    let signed = Signed { value, proofs };
+    validate_structure(&signed)?;
+    Ok(signed)
+}
+
+/// Write signed objects as newline-delimited JSON
+///
+/// One compact JSON envelope per line. The proofs sign the canonical form
+/// of the value, which any JSON encoding of it reproduces, so no manual
+/// canonicalization is needed; `read_ndjson` returns objects that verify
+/// exactly as the originals did.
+///
+/// # Arguments
+/// * `objects` - Signed objects to write
+/// * `w` - Destination; writes are buffered internally
+///
+/// # Returns
+/// `Ok(())` once every line is written and flushed
+///
+/// # Example
+/// ```
+/// use constellation_sdk::signed_object::{create_signed_object, read_ndjson, write_ndjson};
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::{json, Value};
+///
+/// let key_pair = generate_key_pair();
+/// let signed = create_signed_object(&json!({"id": "a"}), &key_pair.private_key, false).unwrap();
+///
+/// let mut buffer = Vec::new();
+/// write_ndjson(&[signed.clone(), signed.clone()], &mut buffer).unwrap();
+///
+/// let read: Vec<_> = read_ndjson::<_, Value>(buffer.as_slice())
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(read, vec![signed.clone(), signed]);
+/// ```
+#[cfg(feature = "std")]
+pub fn write_ndjson<W: std::io::Write, T: Serialize>(objects: &[Signed<T>], w: W) -> Result<()> {
+    use std::io::Write;
+
+    let mut w = std::io::BufWriter::new(w);
+    for signed in objects {
+        serde_json::to_writer(&mut w, signed)?;
+        w.write_all(b"\n")?;
+    }
+    w.flush()?;
+    Ok(())
+}
+
+/// Read signed objects from newline-delimited JSON
+///
+/// Lines are read and parsed lazily, one per item, so large files can be
+/// streamed. Blank lines are skipped. A malformed line yields
+/// `SdkError::SerializationError` naming its 1-based line number, and
+/// iteration can continue past it; a read failure yields
+/// `SdkError::IoError`. Objects are not verified.
+///
+/// # Arguments
+/// * `r` - Buffered source of NDJSON
+///
+/// # Returns
+/// Iterator over the parsed signed objects
+#[cfg(feature = "std")]
+pub fn read_ndjson<R: std::io::BufRead, T: DeserializeOwned>(
+    r: R,
+) -> impl Iterator<Item = Result<Signed<T>>> {
+    r.lines()
+        .enumerate()
+        .filter_map(|(index, line)| match line {
+            Err(e) => Some(Err(e.into())),
+            Ok(line) if line.trim().is_empty() => None,
+            Ok(line) => Some(
+                serde_json::from_str(&line)
+                    .map_err(|e| SdkError::SerializationError(format!("line {}: {e}", index + 1))),
+            ),
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,6 +624,64 @@ mod tests {
     use crate::wallet::generate_key_pair;
     use serde_json::json;
 
+    #[test]
+    fn test_try_map_keeps_proofs_for_lossless_transforms() {
+        #[derive(Debug, Clone, PartialEq, Serialize, serde::Deserialize)]
+        struct Reading {
+            id: String,
+            value: u64,
+        }
+
+        let key_pair = generate_key_pair();
+        let signed =
+            create_signed_object(&json!({"id": "a", "value": 7}), &key_pair.private_key, true)
+                .unwrap();
+        let proofs = signed.proofs.clone();
+
+        let typed = signed
+            .clone()
+            .try_map(|v| Ok(serde_json::from_value::<Reading>(v)?))
+            .unwrap();
+        assert_eq!(typed.proofs, proofs);
+        assert!(verify(&typed, true).is_valid);
+
+        // Dropping a field invalidates the carried-over proofs
+        let lossy = signed
+            .clone()
+            .try_map(|v| Ok(json!({"id": v["id"]})))
+            .unwrap();
+        assert!(!verify(&lossy, true).is_valid);
+
+        let err = signed
+            .try_map(|_| -> Result<Reading> { Err(SdkError::InvalidEnvelope("no".into())) })
+            .unwrap_err();
+        assert!(matches!(err, SdkError::InvalidEnvelope(_)));
+    }
+
+    #[test]
+    fn test_try_map_resign() {
+        let original = generate_key_pair();
+        let resigner = generate_key_pair();
+        let signed = create_signed_object(
+            &json!({"id": "a", "value": 7}),
+            &original.private_key,
+            false,
+        )
+        .unwrap();
+
+        let resigned = signed
+            .try_map_resign(
+                |v| Ok(json!({"id": v["id"], "v2": true})),
+                &[&resigner.private_key],
+                false,
+            )
+            .unwrap();
+        assert_eq!(resigned.value, json!({"id": "a", "v2": true}));
+        assert_eq!(resigned.proofs.len(), 1);
+        assert_eq!(resigned.proofs[0].id, resigner.public_key[2..]);
+        assert!(verify(&resigned, false).is_valid);
+    }
+
     #[test]
     fn test_create_signed_object() {
         let key_pair = generate_key_pair();
@@ -230,4 +749,105 @@ mod tests {
         let result = batch_sign::<serde_json::Value>(&data, &[], false);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_signed_id_ignores_proofs() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let data = json!({"b": 2, "a": 1});
+
+        let single = create_signed_object(&data, &key1.private_key, false).unwrap();
+        let multi = batch_sign(&data, &[&key1.private_key, &key2.private_key], false).unwrap();
+
+        let id = single.id().unwrap();
+        assert_eq!(id.len(), 64);
+        assert_eq!(id, multi.id().unwrap());
+        assert_eq!(
+            id,
+            hash_data(&json!({"a": 1, "b": 2}), false).unwrap().value
+        );
+    }
+
+    #[test]
+    fn test_signed_id_differs_for_different_values() {
+        let key_pair = generate_key_pair();
+        let a = create_signed_object(&json!({"v": 1}), &key_pair.private_key, false).unwrap();
+        let b = create_signed_object(&json!({"v": 2}), &key_pair.private_key, false).unwrap();
+        assert_ne!(a.id().unwrap(), b.id().unwrap());
+    }
+
+    #[test]
+    fn test_envelope_hash_covers_proofs() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let data = json!({"id": "test"});
+
+        let single = create_signed_object(&data, &key1.private_key, false).unwrap();
+        let multi = add_signature(single.clone(), &key2.private_key, false).unwrap();
+        assert_ne!(
+            envelope_hash(&single).unwrap(),
+            envelope_hash(&multi).unwrap()
+        );
+
+        // Proof order does not matter
+        let mut reordered = multi.clone();
+        reordered.proofs.reverse();
+        assert_eq!(
+            envelope_hash(&multi).unwrap(),
+            envelope_hash(&reordered).unwrap()
+        );
+
+        // Tampering with a proof changes the hash
+        let mut tampered = single.clone();
+        tampered.proofs[0].signature.push('0');
+        assert_ne!(
+            envelope_hash(&single).unwrap(),
+            envelope_hash(&tampered).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_signed_roundtrip() {
+        let key_pair = generate_key_pair();
+        let signed =
+            create_signed_object(&json!({"id": "test"}), &key_pair.private_key, false).unwrap();
+        let parsed = parse_signed(&serde_json::to_string(&signed).unwrap()).unwrap();
+        assert_eq!(parsed, signed);
+        assert!(verify(&parsed, false).is_valid);
+    }
+
+    #[test]
+    fn test_parse_signed_distinct_errors() {
+        let key_pair = generate_key_pair();
+        let proof = sign(&json!({"id": "test"}), &key_pair.private_key).unwrap();
+
+        assert!(matches!(
+            parse_signed("{not json"),
+            Err(SdkError::SerializationError(_))
+        ));
+        for envelope in [
+            json!([1, 2]),
+            json!({"value": 1}),
+            json!({"proofs": []}),
+            json!({"value": 1, "proofs": "abc"}),
+            json!({"value": 1, "proofs": []}),
+        ] {
+            assert!(matches!(
+                parse_signed(&envelope.to_string()),
+                Err(SdkError::InvalidEnvelope(_))
+            ));
+        }
+
+        let bad_id = json!({"value": 1, "proofs": [{"id": "zz", "signature": proof.signature}]});
+        assert!(matches!(
+            parse_signed(&bad_id.to_string()),
+            Err(SdkError::InvalidPublicKey(_))
+        ));
+
+        let bad_sig = json!({"value": 1, "proofs": [{"id": proof.id, "signature": "3000ff"}]});
+        assert!(matches!(
+            parse_signed(&bad_sig.to_string()),
+            Err(SdkError::InvalidSignature(_))
+        ));
+    }
 }
@@ -1,16 +1,23 @@
 //! Core type definitions for the Constellation Metagraph SDK
 
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
 use thiserror::Error;
 
 /// Supported signature algorithm
 pub const ALGORITHM: &str = "SECP256K1_RFC8785_V1";
 
+/// Signature algorithm for deterministic MessagePack payloads
+pub const MSGPACK_ALGORITHM: &str = "SECP256K1_MSGPACK_V1";
+
 /// Constellation prefix for DataUpdate signing
 pub const CONSTELLATION_PREFIX: &str = "\x19Constellation Signed Data:\n";
 
 /// A signature proof containing the signer's public key ID and signature
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Proofs are ordered by `(id, signature)`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct SignatureProof {
     /// Public key hex (uncompressed, without 04 prefix) - 128 characters
     pub id: String,
@@ -18,6 +25,74 @@ pub struct SignatureProof {
     pub signature: String,
 }
 
+impl SignatureProof {
+    /// Compare two proofs in constant time
+    ///
+    /// Unlike `==`, the running time does not depend on how many leading
+    /// characters of `id` and `signature` match, only on their lengths
+    /// (which are public for well-formed proofs).
+    ///
+    /// # Example
+    /// ```
+    /// use constellation_sdk::SignatureProof;
+    ///
+    /// let a = SignatureProof { id: "ab".repeat(64), signature: "3044".to_string() };
+    /// let mut b = a.clone();
+    /// assert!(a.ct_eq(&b));
+    ///
+    /// b.signature = "3045".to_string();
+    /// assert!(!a.ct_eq(&b));
+    /// ```
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        let id = self.id.as_bytes().ct_eq(other.id.as_bytes());
+        let signature = self.signature.as_bytes().ct_eq(other.signature.as_bytes());
+        (id & signature).into()
+    }
+}
+
+/// A signature proof tagged with the algorithm that produced it
+///
+/// Serializes as the `SignatureProof` fields plus `algorithm`. A missing
+/// `algorithm` deserializes as `ALGORITHM`, so untagged proofs parse as
+/// canonical-JSON proofs. Use `verify_tagged` to verify according to the
+/// tag.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TaggedProof {
+    /// The underlying proof
+    #[serde(flatten)]
+    pub proof: SignatureProof,
+    /// Algorithm identifier (e.g. `ALGORITHM`, `MSGPACK_ALGORITHM`)
+    #[serde(default = "default_algorithm")]
+    pub algorithm: String,
+}
+
+impl TaggedProof {
+    /// Tag a proof with an algorithm identifier
+    pub fn new(proof: SignatureProof, algorithm: impl Into<String>) -> Self {
+        Self {
+            proof,
+            algorithm: algorithm.into(),
+        }
+    }
+}
+
+impl From<SignatureProof> for TaggedProof {
+    /// Tag a proof as `ALGORITHM`
+    fn from(proof: SignatureProof) -> Self {
+        Self::new(proof, ALGORITHM)
+    }
+}
+
+impl From<TaggedProof> for SignatureProof {
+    fn from(tagged: TaggedProof) -> Self {
+        tagged.proof
+    }
+}
+
+fn default_algorithm() -> String {
+    ALGORITHM.to_string()
+}
+
 /// A signed object wrapping a value with one or more signature proofs
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Signed<T> {
@@ -34,12 +109,33 @@ pub struct KeyPair {
     pub private_key: String,
     /// Public key in hex format (uncompressed, with 04 prefix - 130 characters)
     pub public_key: String,
-    /// DAG address derived from the public key
+    /// Address derived from the public key, a DAG address unless another
+    /// `AddressScheme` was used
     pub address: String,
 }
 
+impl KeyPair {
+    /// Short identifier for displaying this key pair
+    ///
+    /// The first 8 bytes of the SHA-256 hash of the uncompressed public key,
+    /// as 16 hex characters. Meant for logs and UIs, not for identification.
+    ///
+    /// # Returns
+    /// The fingerprint, or `SdkError::InvalidPublicKey` if `public_key` is
+    /// not a valid secp256k1 public key
+    pub fn fingerprint(&self) -> Result<String> {
+        let public_key = crate::wallet::signer_key(&self.public_key).ok_or_else(|| {
+            SdkError::InvalidPublicKey(format!("not a public key: {}", self.public_key))
+        })?;
+        let bytes = hex::decode(public_key)?;
+        Ok(hex::encode(&Sha256::digest(&bytes)[..8]))
+    }
+}
+
 /// A hash result containing both hex string and raw bytes
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Hashes are ordered by their hex value.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Hash {
     /// SHA-256 hash as 64-character hex string
     pub value: String,
@@ -56,6 +152,227 @@ pub struct VerificationResult {
     pub valid_proofs: Vec<SignatureProof>,
     /// Proofs that failed verification
     pub invalid_proofs: Vec<SignatureProof>,
+    /// Why each invalid proof failed, in the same order as `invalid_proofs`
+    pub invalid_reasons: Vec<InvalidReason>,
+    /// Signing mode the valid proofs matched, if any proof was valid and all
+    /// valid proofs agree. None for currency transactions.
+    pub matched_mode: Option<SigningMode>,
+}
+
+impl VerificationResult {
+    /// Turn an invalid result into an error
+    ///
+    /// # Returns
+    /// The result itself if valid, otherwise
+    /// `SdkError::VerificationFailed` with the proof counts
+    ///
+    /// # Example
+    /// ```
+    /// use constellation_sdk::verify::verify;
+    /// use constellation_sdk::{SdkError, Signed};
+    /// use serde_json::json;
+    ///
+    /// let unsigned = Signed { value: json!({"id": "test"}), proofs: vec![] };
+    /// assert!(matches!(
+    ///     verify(&unsigned, false).into_result(),
+    ///     Err(SdkError::VerificationFailed { valid: 0, invalid: 0 })
+    /// ));
+    /// ```
+    pub fn into_result(self) -> Result<Self> {
+        if self.is_valid {
+            Ok(self)
+        } else {
+            Err(SdkError::VerificationFailed {
+                valid: self.valid_proofs.len(),
+                invalid: self.invalid_proofs.len(),
+            })
+        }
+    }
+
+    /// Whether verification failed because there were no proofs at all
+    ///
+    /// Distinguishes an unsigned object from one whose signatures are bad.
+    pub fn is_unsigned(&self) -> bool {
+        self.valid_proofs.is_empty() && self.invalid_proofs.is_empty()
+    }
+
+    /// Why verification failed, or None if the result is valid
+    ///
+    /// Returns `InvalidReason::NoProofs` for an unsigned object, otherwise
+    /// the reason the first invalid proof failed.
+    ///
+    /// # Example
+    /// ```
+    /// use constellation_sdk::verify::verify;
+    /// use constellation_sdk::{InvalidReason, Signed};
+    /// use serde_json::json;
+    ///
+    /// let unsigned = Signed { value: json!({"id": "test"}), proofs: vec![] };
+    /// let result = verify(&unsigned, false);
+    /// assert!(!result.is_valid);
+    /// assert_eq!(result.invalid_reason(), Some(InvalidReason::NoProofs));
+    /// ```
+    pub fn invalid_reason(&self) -> Option<InvalidReason> {
+        if self.is_valid {
+            None
+        } else if self.is_unsigned() {
+            Some(InvalidReason::NoProofs)
+        } else {
+            self.invalid_reasons.first().cloned()
+        }
+    }
+
+    /// Number of distinct signers whose proofs verified
+    ///
+    /// Proofs from the same key count once, whatever format their ids use.
+    pub fn valid_count(&self) -> usize {
+        distinct_signers(&self.valid_proofs)
+    }
+
+    /// Number of distinct signers across all proofs, valid or not
+    pub fn total_count(&self) -> usize {
+        distinct_signers(self.valid_proofs.iter().chain(&self.invalid_proofs))
+    }
+
+    /// Fraction of distinct signers whose proofs verified, 0.0 if unsigned
+    ///
+    /// # Example
+    /// ```
+    /// use constellation_sdk::signed_object::batch_sign;
+    /// use constellation_sdk::verify::verify;
+    /// use constellation_sdk::wallet::generate_key_pair;
+    /// use serde_json::json;
+    ///
+    /// let keys: Vec<_> = (0..4).map(|_| generate_key_pair()).collect();
+    /// let private_keys: Vec<&str> = keys.iter().map(|k| k.private_key.as_str()).collect();
+    /// let mut signed = batch_sign(&json!({"proposal": 7}), &private_keys, false).unwrap();
+    /// signed.proofs[3].signature = signed.proofs[2].signature.clone();
+    ///
+    /// let result = verify(&signed, false);
+    /// assert_eq!((result.valid_count(), result.total_count()), (3, 4));
+    /// assert_eq!(result.quorum_fraction(), 0.75);
+    /// assert!(result.meets_quorum(3));
+    /// ```
+    pub fn quorum_fraction(&self) -> f64 {
+        match self.total_count() {
+            0 => 0.0,
+            total => self.valid_count() as f64 / total as f64,
+        }
+    }
+
+    /// Whether at least `required` distinct signers verified (at least one,
+    /// even if `required` is 0)
+    pub fn meets_quorum(&self, required: usize) -> bool {
+        self.valid_count() >= required.max(1)
+    }
+
+    /// DAG addresses of the signers whose proofs verified, in proof order
+    ///
+    /// # Example
+    /// ```
+    /// use constellation_sdk::signed_object::batch_sign;
+    /// use constellation_sdk::verify::verify;
+    /// use constellation_sdk::wallet::generate_key_pair;
+    /// use serde_json::json;
+    ///
+    /// let key1 = generate_key_pair();
+    /// let key2 = generate_key_pair();
+    /// let keys = [key1.private_key.as_str(), key2.private_key.as_str()];
+    /// let signed = batch_sign(&json!({"id": "test"}), &keys, false).unwrap();
+    ///
+    /// let result = verify(&signed, false);
+    /// assert_eq!(result.signer_addresses(), vec![key1.address, key2.address]);
+    /// ```
+    pub fn signer_addresses(&self) -> Vec<String> {
+        self.valid_proofs
+            .iter()
+            .filter_map(|proof| crate::wallet::address_from_proof(proof).ok())
+            .collect()
+    }
+}
+
+/// Count distinct signers, treating ids in any format for one key as equal
+fn distinct_signers<'a>(proofs: impl IntoIterator<Item = &'a SignatureProof>) -> usize {
+    proofs
+        .into_iter()
+        .map(|proof| {
+            crate::wallet::signer_key(&proof.id).unwrap_or_else(|| proof.id.to_lowercase())
+        })
+        .collect::<std::collections::HashSet<_>>()
+        .len()
+}
+
+/// How a signed object's value was encoded before hashing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SigningMode {
+    /// Canonical JSON signed directly
+    Regular,
+    /// Canonical JSON wrapped with the Constellation DataUpdate prefix
+    DataUpdate,
+}
+
+impl SigningMode {
+    /// The mode for an `is_data_update` flag
+    pub fn from_data_update(is_data_update: bool) -> Self {
+        if is_data_update {
+            SigningMode::DataUpdate
+        } else {
+            SigningMode::Regular
+        }
+    }
+
+    /// Whether this is the DataUpdate mode
+    pub fn is_data_update(self) -> bool {
+        self == SigningMode::DataUpdate
+    }
+}
+
+/// Encoding of a public key used as a proof id
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdFormat {
+    /// Uncompressed key without the 04 prefix (128 characters), the default
+    Uncompressed,
+    /// Uncompressed key with the 04 prefix (130 characters)
+    Prefixed,
+    /// Compressed key (66 characters)
+    Compressed,
+}
+
+/// Reason a signature proof failed verification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidReason {
+    /// The signature does not match the signed content and public key
+    SignatureMismatch,
+    /// The signature is in malleable high-S form and high-S was rejected
+    HighS,
+    /// The proof id is not a valid secp256k1 public key (bad hex, wrong
+    /// length, or not a point on the curve)
+    MalformedPublicKey(String),
+    /// The proof signature could not be parsed (bad hex or DER encoding)
+    Malformed(String),
+    /// The signed value could not be serialized for hashing
+    Serialization(String),
+    /// The signature is valid but the signer is not in the authorized set
+    Unauthorized,
+    /// The signature is valid but an earlier proof already came from the
+    /// same key, so it doesn't count as another signer
+    DuplicateSigner,
+    /// The object carries no proofs at all (it was never signed)
+    NoProofs,
+}
+
+/// A single difference between two JSON documents
+///
+/// Produced by [`crate::verify::diff_against`] to report which fields of a
+/// signed value differ from a presumed original.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonDiff {
+    /// JSON pointer (RFC 6901) to the differing location ("" for the root)
+    pub pointer: String,
+    /// Value in the original document, or None if absent
+    pub old: Option<serde_json::Value>,
+    /// Value in the signed document, or None if absent
+    pub new: Option<serde_json::Value>,
 }
 
 /// Options for signing operations
@@ -63,6 +380,18 @@ pub struct VerificationResult {
 pub struct SigningOptions {
     /// Whether to sign as a DataUpdate (with Constellation prefix)
     pub is_data_update: bool,
+    /// Whether the proof id should be the compressed public key (66 characters)
+    /// instead of the uncompressed id without 04 prefix (128 characters)
+    pub compressed_id: bool,
+    /// Maximum canonical JSON size in bytes; larger payloads are rejected
+    /// before hashing with `SdkError::PayloadTooLarge`
+    pub max_bytes: Option<usize>,
+    /// Domain-separation tag binding the signature to one application.
+    /// See `binary::apply_domain` for the byte layout.
+    pub domain: Option<String>,
+    /// DataUpdate prefix to use instead of `CONSTELLATION_PREFIX`, for
+    /// networks forked with a different one. Only used with `is_data_update`.
+    pub data_update_prefix: Option<String>,
 }
 
 /// SDK error types
@@ -94,6 +423,64 @@ pub enum SdkError {
 
     #[error("Invalid amount: {0}")]
     InvalidAmount(String),
+
+    #[error("Encoding error: {0}")]
+    EncodingError(String),
+
+    #[error("Payload exceeds maximum size of {0} bytes")]
+    PayloadTooLarge(usize),
+
+    #[error("Invalid domain: {0}")]
+    InvalidDomain(String),
+
+    #[error("Invalid signed envelope: {0}")]
+    InvalidEnvelope(String),
+
+    #[error("Unsupported schema version: {0}")]
+    UnsupportedVersion(u64),
+
+    #[error("Unsupported algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+
+    #[error("Replay detected: {0}")]
+    ReplayDetected(String),
+
+    #[error("No matching key found in {0} attempts")]
+    SearchExhausted(u64),
+
+    #[error("Timestamp out of range: {0}")]
+    StaleTimestamp(String),
+
+    #[error("Verification failed: {valid} valid and {invalid} invalid proofs")]
+    VerificationFailed { valid: usize, invalid: usize },
+
+    #[error("Self-check failed: {0}")]
+    SelfCheckFailed(String),
+
+    #[error("Transaction preflight failed: {}", .0.join("; "))]
+    PreflightFailed(Vec<String>),
+
+    #[error("Too many proofs: {count} exceeds the maximum of {max}")]
+    TooManyProofs { count: usize, max: usize },
+
+    #[error("Duplicate signer: {0}")]
+    DuplicateSigner(String),
+
+    #[error("Unauthorized signer: {0}")]
+    UnauthorizedSigner(String),
+
+    #[error("Secret sharing error: {0}")]
+    SecretSharing(String),
+
+    #[error("Transaction chain broken at index {index}: {reason}")]
+    ChainBroken { index: usize, reason: String },
+
+    #[error("Malformed signature: {0}")]
+    MalformedSignature(#[from] SignatureError),
+
+    #[cfg(feature = "std")]
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
 }
 
 impl From<hex::FromHexError> for SdkError {
@@ -114,5 +501,18 @@ impl From<serde_json::Error> for SdkError {
     }
 }
 
+/// Why a DER signature could not be parsed
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SignatureError {
+    #[error("not valid hex: {0}")]
+    BadHex(String),
+
+    #[error("invalid DER structure: {0}")]
+    BadDer(String),
+
+    #[error("{0} is zero or not below the curve order")]
+    OutOfRange(&'static str),
+}
+
 /// Result type for SDK operations
 pub type Result<T> = std::result::Result<T, SdkError>;
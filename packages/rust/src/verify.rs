@@ -3,13 +3,20 @@
 //! Verify ECDSA signatures using secp256k1 curve.
 
 use secp256k1::ecdsa::Signature;
-use secp256k1::{Message, PublicKey, Secp256k1};
+use secp256k1::{Message, PublicKey, SECP256K1};
 use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 
-use crate::binary::to_bytes;
-use crate::hash::{compute_digest_from_hash, hash_bytes};
-use crate::types::{Result, SignatureProof, Signed, VerificationResult};
-use crate::wallet::normalize_public_key;
+use crate::binary::{apply_domain, to_bytes, to_bytes_with_prefix, wrap_canonical};
+use crate::canonicalize::escape_pointer_token;
+use crate::hash::{compute_digest_from_hash, hash_bytes, hash_data};
+use crate::rfc3339;
+use crate::types::{
+    InvalidReason, JsonDiff, Result, SdkError, SignatureProof, Signed, SigningMode, TaggedProof,
+    VerificationResult, ALGORITHM,
+};
+use crate::wallet::{normalize_public_key, signer_key};
 
 /// Verify a signed object
 ///
@@ -33,103 +40,1617 @@ use crate::wallet::normalize_public_key;
 /// assert!(result.is_valid);
 /// ```
 pub fn verify<T: Serialize>(signed: &Signed<T>, is_data_update: bool) -> VerificationResult {
+    verify_with_options(signed, is_data_update, &VerifyOptions::default())
+}
+
+/// Options controlling how strictly signatures are verified
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyOptions {
+    /// Reject high-S (malleable) signatures instead of normalizing them to low-S.
+    ///
+    /// Defaults to false, which accepts both forms for compatibility with
+    /// signers that do not normalize.
+    pub reject_high_s: bool,
+    /// Require signatures to be bound to this domain, matching
+    /// `SigningOptions::domain`. Only applies to signed objects; currency
+    /// transactions are hashed by their kryo encoding and ignore it.
+    pub domain: Option<String>,
+    /// DataUpdate prefix to expect instead of `CONSTELLATION_PREFIX`,
+    /// matching `SigningOptions::data_update_prefix`. Only used when
+    /// verifying DataUpdates.
+    pub data_update_prefix: Option<String>,
+}
+
+/// Verify a signed object with explicit verification options
+///
+/// # Arguments
+/// * `signed` - Signed object with value and proofs
+/// * `is_data_update` - Whether the value was signed as a DataUpdate
+/// * `options` - Verification options
+///
+/// # Returns
+/// VerificationResult with valid/invalid proof lists and failure reasons
+///
+/// # Example
+/// ```
+/// use constellation_sdk::verify::{verify_with_options, VerifyOptions};
+/// use constellation_sdk::signed_object::create_signed_object;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let signed = create_signed_object(&json!({"id": "test"}), &key_pair.private_key, false).unwrap();
+/// let options = VerifyOptions { reject_high_s: true, ..Default::default() };
+/// // Signatures produced by this SDK are always low-S
+/// assert!(verify_with_options(&signed, false, &options).is_valid);
+/// ```
+pub fn verify_with_options<T: Serialize>(
+    signed: &Signed<T>,
+    is_data_update: bool,
+    options: &VerifyOptions,
+) -> VerificationResult {
     // Compute the hash that should have been signed
-    let bytes = match to_bytes(&signed.value, is_data_update) {
-        Ok(b) => b,
-        Err(_) => {
-            return VerificationResult {
-                is_valid: false,
-                valid_proofs: vec![],
-                invalid_proofs: signed.proofs.clone(),
-            };
+    let hash = match signing_hash(&signed.value, is_data_update, options) {
+        Ok(hash) => hash,
+        Err(e) => return serialization_failure(&signed.proofs, e),
+    };
+
+    check_proofs(&hash, &signed.proofs, is_data_update, options)
+}
+
+/// Verify a signed object, checking its proofs on all available cores
+///
+/// Like `verify`, but the proofs are split across one worker thread per
+/// available core. The value is canonicalized and hashed once, and every
+/// proof is checked against that digest independently, so the result is
+/// identical to `verify`'s, including the order of `valid_proofs` and
+/// `invalid_proofs`. Worth it for objects with many proofs; with only a
+/// few, thread startup costs more than it saves.
+///
+/// # Arguments
+/// * `signed` - Signed object with value and proofs
+/// * `is_data_update` - Whether the value was signed as a DataUpdate
+///
+/// # Returns
+/// VerificationResult with valid/invalid proof lists in input order
+#[cfg(feature = "parallel")]
+pub fn verify_parallel<T: Serialize>(
+    signed: &Signed<T>,
+    is_data_update: bool,
+) -> VerificationResult {
+    let options = VerifyOptions::default();
+    let hash = match signing_hash(&signed.value, is_data_update, &options) {
+        Ok(hash) => hash,
+        Err(e) => return serialization_failure(&signed.proofs, e),
+    };
+
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = signed.proofs.len().div_ceil(threads).max(1);
+    let outcomes: Vec<ProofOutcome> = std::thread::scope(|scope| {
+        let workers: Vec<_> = signed
+            .proofs
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let (hash, options) = (&hash, &options);
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|proof| check_proof(hash, proof, options))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        // Joining in spawn order keeps outcomes in proof order
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    });
+
+    let mut outcomes = outcomes.into_iter();
+    collect_outcomes(&signed.proofs, is_data_update, |_| {
+        outcomes.next().expect("one outcome per proof")
+    })
+}
+
+/// Verify proofs over a JSON value held outside a `Signed` wrapper
+///
+/// Equivalent to `verify` on a `Signed` built from `value` and `proofs`,
+/// without cloning either into one. Useful for signed sub-objects embedded
+/// in a larger document.
+///
+/// # Arguments
+/// * `value` - The signed value
+/// * `proofs` - Proofs over the value
+/// * `is_data_update` - Whether the value was signed as a DataUpdate
+///
+/// # Returns
+/// VerificationResult with valid/invalid proof lists
+///
+/// # Example
+/// ```
+/// use constellation_sdk::signed_object::create_signed_object;
+/// use constellation_sdk::verify::verify_value;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let signed = create_signed_object(&json!({"id": "test"}), &key_pair.private_key, false).unwrap();
+/// let document = json!({"items": [serde_json::to_value(&signed).unwrap()]});
+///
+/// let embedded = &document["items"][0];
+/// let proofs: Vec<_> = serde_json::from_value(embedded["proofs"].clone()).unwrap();
+/// assert!(verify_value(&embedded["value"], &proofs, false).is_valid);
+/// ```
+pub fn verify_value(
+    value: &Value,
+    proofs: &[SignatureProof],
+    is_data_update: bool,
+) -> VerificationResult {
+    let options = VerifyOptions::default();
+    let hash = match signing_hash(value, is_data_update, &options) {
+        Ok(hash) => hash,
+        Err(e) => return serialization_failure(proofs, e),
+    };
+
+    check_proofs(&hash, proofs, is_data_update, &options)
+}
+
+/// Verify proofs against already-canonicalized bytes
+///
+/// Skips canonicalization: the bytes are wrapped as a DataUpdate if
+/// requested, hashed and checked against each proof, exactly as `verify`
+/// does after canonicalizing a value.
+///
+/// **The caller's canonical form is trusted.** If `canonical_bytes` are not
+/// the RFC 8785 form of the value the caller acts on (stale, hand-written,
+/// or produced by a non-conforming canonicalizer), a valid result only says
+/// these bytes were signed, not that the value was. Use this only for bytes
+/// that came from `canonicalize_bytes` on the value being trusted, or that
+/// the caller will parse and use as-is.
+///
+/// # Arguments
+/// * `canonical_bytes` - RFC 8785 canonical JSON of the signed value
+/// * `proofs` - Proofs to verify
+/// * `is_data_update` - Whether the value was signed as a DataUpdate
+///
+/// # Returns
+/// VerificationResult with valid/invalid proof lists
+///
+/// # Example
+/// ```
+/// use constellation_sdk::canonicalize::canonicalize_bytes;
+/// use constellation_sdk::signed_object::create_signed_object;
+/// use constellation_sdk::verify::verify_canonical;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let signed = create_signed_object(&json!({"id": "test"}), &key_pair.private_key, true).unwrap();
+///
+/// let canonical = canonicalize_bytes(&signed.value).unwrap();
+/// assert!(verify_canonical(&canonical, &signed.proofs, true).is_valid);
+/// ```
+pub fn verify_canonical(
+    canonical_bytes: &[u8],
+    proofs: &[SignatureProof],
+    is_data_update: bool,
+) -> VerificationResult {
+    let hash = hash_bytes(&wrap_canonical(canonical_bytes.to_vec(), is_data_update)).value;
+    check_proofs(&hash, proofs, is_data_update, &VerifyOptions::default())
+}
+
+/// Outcome of checking one proof
+type ProofOutcome = std::result::Result<(), InvalidReason>;
+
+/// Cache key: the signed hash, the signature and the proof id
+type VerifyCacheKey = (String, String, String);
+
+/// Size-bounded LRU cache of proof verification results
+///
+/// Memoizes the outcome of checking a `(hash_hex, signature, id)` triple so
+/// that re-verifying a proof already seen is a map lookup instead of an
+/// ECDSA verification. Only public data is stored.
+///
+/// Cache poisoning is not a risk: the key is everything the outcome
+/// depends on, so whoever populates an entry can only ever store the true
+/// result for that triple. Entries assume the default `VerifyOptions`,
+/// which is what `verify_cached` uses. Canonicalization and hashing still
+/// run on every call; only the signature check is skipped.
+#[derive(Debug, Clone)]
+pub struct VerifyCache {
+    capacity: usize,
+    entries: HashMap<VerifyCacheKey, (ProofOutcome, u64)>,
+    recency: BTreeMap<u64, VerifyCacheKey>,
+    tick: u64,
+}
+
+impl VerifyCache {
+    /// Create a cache holding at most `capacity` results
+    ///
+    /// A capacity of 0 disables caching.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Maximum number of cached results
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of cached results
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove all cached results
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    /// Look up a cached outcome, marking it most recently used
+    fn get(&mut self, key: &VerifyCacheKey) -> Option<ProofOutcome> {
+        self.tick += 1;
+        let (outcome, used) = self.entries.get_mut(key)?;
+        let key = self.recency.remove(used).expect("recency out of sync");
+        *used = self.tick;
+        self.recency.insert(self.tick, key);
+        Some(outcome.clone())
+    }
+
+    /// Cache an outcome, evicting the least recently used if full
+    fn insert(&mut self, key: VerifyCacheKey, outcome: ProofOutcome) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = self.recency.pop_first() {
+                self.entries.remove(&oldest);
+            }
         }
+        self.tick += 1;
+        self.recency.insert(self.tick, key.clone());
+        self.entries.insert(key, (outcome, self.tick));
+    }
+}
+
+/// Verify a signed object, memoizing per-proof results in `cache`
+///
+/// Returns the same result as `verify`. Proofs whose outcome is already
+/// cached skip the ECDSA check; see `VerifyCache` for why this is safe.
+///
+/// # Arguments
+/// * `signed` - Signed object with value and proofs
+/// * `is_data_update` - Whether the value was signed as a DataUpdate
+/// * `cache` - Cache to consult and update
+///
+/// # Returns
+/// VerificationResult with valid/invalid proof lists
+///
+/// # Example
+/// ```
+/// use constellation_sdk::signed_object::create_signed_object;
+/// use constellation_sdk::verify::{verify_cached, VerifyCache};
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let signed = create_signed_object(&json!({"id": "test"}), &key_pair.private_key, false).unwrap();
+///
+/// let mut cache = VerifyCache::new(1024);
+/// assert!(verify_cached(&signed, false, &mut cache).is_valid);
+/// assert_eq!(cache.len(), 1);
+/// // Served from the cache
+/// assert!(verify_cached(&signed, false, &mut cache).is_valid);
+/// ```
+pub fn verify_cached<T: Serialize>(
+    signed: &Signed<T>,
+    is_data_update: bool,
+    cache: &mut VerifyCache,
+) -> VerificationResult {
+    let options = VerifyOptions::default();
+    let hash = match signing_hash(&signed.value, is_data_update, &options) {
+        Ok(hash) => hash,
+        Err(e) => return serialization_failure(&signed.proofs, e),
+    };
+
+    collect_outcomes(&signed.proofs, is_data_update, |proof| {
+        let key = (hash.clone(), proof.signature.clone(), proof.id.clone());
+        if let Some(outcome) = cache.get(&key) {
+            return outcome;
+        }
+        let outcome = check_proof(&hash, proof, &options);
+        cache.insert(key, outcome.clone());
+        outcome
+    })
+}
+
+/// Check each proof against the signed hash and collect the results
+fn check_proofs(
+    hash: &str,
+    proofs: &[SignatureProof],
+    is_data_update: bool,
+    options: &VerifyOptions,
+) -> VerificationResult {
+    collect_outcomes(proofs, is_data_update, |proof| {
+        check_proof(hash, proof, options)
+    })
+}
+
+/// Sort proofs into valid and invalid using `check`
+fn collect_outcomes(
+    proofs: &[SignatureProof],
+    is_data_update: bool,
+    mut check: impl FnMut(&SignatureProof) -> ProofOutcome,
+) -> VerificationResult {
+    let mut valid_proofs = Vec::new();
+    let mut invalid_proofs = Vec::new();
+    let mut invalid_reasons = Vec::new();
+
+    for proof in proofs {
+        match check(proof) {
+            Ok(()) => valid_proofs.push(proof.clone()),
+            Err(reason) => {
+                invalid_proofs.push(proof.clone());
+                invalid_reasons.push(reason);
+            }
+        }
+    }
+
+    let matched_mode =
+        (!valid_proofs.is_empty()).then(|| SigningMode::from_data_update(is_data_update));
+
+    VerificationResult {
+        is_valid: invalid_proofs.is_empty() && !valid_proofs.is_empty(),
+        valid_proofs,
+        invalid_proofs,
+        invalid_reasons,
+        matched_mode,
+    }
+}
+
+/// Verify a signed object without knowing whether it was signed as a DataUpdate
+///
+/// Each proof is checked in regular mode first, then in DataUpdate mode. A
+/// proof that verifies under neither mode is invalid. `matched_mode` reports
+/// the mode the valid proofs matched, or None if no proof was valid or the
+/// valid proofs were signed in different modes.
+///
+/// # Arguments
+/// * `signed` - Signed object with value and proofs
+///
+/// # Returns
+/// VerificationResult with valid/invalid proof lists and the matched mode
+///
+/// # Example
+/// ```
+/// use constellation_sdk::signed_object::create_signed_object;
+/// use constellation_sdk::verify::verify_auto;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use constellation_sdk::SigningMode;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let signed = create_signed_object(&json!({"id": "test"}), &key_pair.private_key, true).unwrap();
+///
+/// let result = verify_auto(&signed);
+/// assert!(result.is_valid);
+/// assert_eq!(result.matched_mode, Some(SigningMode::DataUpdate));
+/// ```
+pub fn verify_auto<T: Serialize>(signed: &Signed<T>) -> VerificationResult {
+    let options = VerifyOptions::default();
+    let hashes = signing_hash(&signed.value, false, &options)
+        .and_then(|regular| Ok((regular, signing_hash(&signed.value, true, &options)?)));
+    let (regular_hash, data_update_hash) = match hashes {
+        Ok(hashes) => hashes,
+        Err(e) => return serialization_failure(&signed.proofs, e),
     };
-    let hash = hash_bytes(&bytes);
 
     let mut valid_proofs = Vec::new();
     let mut invalid_proofs = Vec::new();
+    let mut invalid_reasons = Vec::new();
+    let mut modes = Vec::new();
 
     for proof in &signed.proofs {
-        match verify_hash(&hash.value, &proof.signature, &proof.id) {
-            Ok(true) => valid_proofs.push(proof.clone()),
-            Ok(false) | Err(_) => invalid_proofs.push(proof.clone()),
+        let result = check_proof(&regular_hash, proof, &options)
+            .map(|()| SigningMode::Regular)
+            .or_else(|reason| {
+                check_proof(&data_update_hash, proof, &options)
+                    .map(|()| SigningMode::DataUpdate)
+                    .map_err(|_| reason)
+            });
+        match result {
+            Ok(mode) => {
+                valid_proofs.push(proof.clone());
+                modes.push(mode);
+            }
+            Err(reason) => {
+                invalid_proofs.push(proof.clone());
+                invalid_reasons.push(reason);
+            }
         }
     }
 
+    let matched_mode = match modes.split_first() {
+        Some((first, rest)) if rest.iter().all(|mode| mode == first) => Some(*first),
+        _ => None,
+    };
+
     VerificationResult {
         is_valid: invalid_proofs.is_empty() && !valid_proofs.is_empty(),
         valid_proofs,
         invalid_proofs,
+        invalid_reasons,
+        matched_mode,
     }
 }
 
-/// Verify a signature against a SHA-256 hash
+/// Find which signing mode an object's proofs were made in
 ///
-/// Protocol:
-/// 1. Treat hash hex as UTF-8 bytes (NOT hex decode)
-/// 2. SHA-512 hash
-/// 3. Truncate to 32 bytes
-/// 4. Verify ECDSA signature
+/// The DataUpdate prefix is applied to the bytes before hashing and is not
+/// recorded anywhere in a `Signed` object, so the mode is not
+/// self-describing. The only way to recover it is to try both: this checks
+/// every proof against the regular hash, then against the DataUpdate hash.
 ///
 /// # Arguments
-/// * `hash_hex` - SHA-256 hash as 64-character hex string
-/// * `signature` - DER-encoded signature in hex format
-/// * `public_key_id` - Public key in hex (with or without 04 prefix)
+/// * `signed` - Signed object with value and proofs
 ///
 /// # Returns
-/// true if signature is valid
-pub fn verify_hash(hash_hex: &str, signature: &str, public_key_id: &str) -> Result<bool> {
-    let secp = Secp256k1::new();
+/// `Some(false)` if every proof verifies in regular mode, `Some(true)` if
+/// every proof verifies as a DataUpdate, or None if there are no proofs,
+/// some proof verifies in neither mode, or the proofs mix modes. Fails only
+/// if the value can't be serialized.
+///
+/// # Example
+/// ```
+/// use constellation_sdk::signed_object::create_signed_object;
+/// use constellation_sdk::verify::{probe_mode, verify};
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let signed = create_signed_object(&json!({"id": "test"}), &key_pair.private_key, true).unwrap();
+///
+/// let is_data_update = probe_mode(&signed).unwrap().expect("no mode verifies");
+/// assert!(is_data_update);
+/// assert!(verify(&signed, is_data_update).is_valid);
+/// ```
+pub fn probe_mode<T: Serialize>(signed: &Signed<T>) -> Result<Option<bool>> {
+    let options = VerifyOptions::default();
+    let regular_hash = signing_hash(&signed.value, false, &options)?;
+    let data_update_hash = signing_hash(&signed.value, true, &options)?;
 
-    // Normalize and parse public key
-    let full_public_key = normalize_public_key(public_key_id);
-    let public_key_bytes = hex::decode(&full_public_key)?;
-    let public_key = PublicKey::from_slice(&public_key_bytes)?;
+    let all_verify = |hash: &str| {
+        !signed.proofs.is_empty()
+            && signed
+                .proofs
+                .iter()
+                .all(|proof| check_proof(hash, proof, &options).is_ok())
+    };
 
-    // Parse signature
-    let signature_bytes = hex::decode(signature)?;
-    let mut sig = Signature::from_der(&signature_bytes)?;
+    Ok(if all_verify(&regular_hash) {
+        Some(false)
+    } else if all_verify(&data_update_hash) {
+        Some(true)
+    } else {
+        None
+    })
+}
 
-    // Normalize to low-S form for verification compatibility
-    // Some signing implementations produce high-S signatures which are mathematically
-    // valid but rejected by strict BIP 62/146 implementations
-    sig.normalize_s();
+/// Compute the SHA-256 hash hex that proofs over `value` should have signed
+fn signing_hash<T: Serialize>(
+    value: &T,
+    is_data_update: bool,
+    options: &VerifyOptions,
+) -> Result<String> {
+    let bytes = to_bytes_with_prefix(value, is_data_update, options.data_update_prefix.as_deref())?;
+    let bytes = apply_domain(bytes, options.domain.as_deref())?;
+    Ok(hash_bytes(&bytes).value)
+}
+
+/// Result marking every proof invalid because the value could not be encoded
+fn serialization_failure(proofs: &[SignatureProof], error: SdkError) -> VerificationResult {
+    VerificationResult {
+        is_valid: false,
+        valid_proofs: vec![],
+        invalid_proofs: proofs.to_vec(),
+        invalid_reasons: vec![InvalidReason::Serialization(error.to_string()); proofs.len()],
+        matched_mode: None,
+    }
+}
+
+/// Parse a proof id (`04 || id`, or a compressed key) as a curve point
+pub(crate) fn parse_proof_key(id: &str) -> std::result::Result<PublicKey, InvalidReason> {
+    let malformed = |e: SdkError| InvalidReason::MalformedPublicKey(e.to_string());
+    let public_key_bytes =
+        hex::decode(normalize_public_key(id)).map_err(|e| malformed(e.into()))?;
+    PublicKey::from_slice(&public_key_bytes).map_err(|e| malformed(e.into()))
+}
+
+/// Check a single proof against a SHA-256 hash, classifying any failure
+fn check_proof(hash_hex: &str, proof: &SignatureProof, options: &VerifyOptions) -> ProofOutcome {
+    let malformed = |e: SdkError| InvalidReason::Malformed(e.to_string());
+
+    let public_key = parse_proof_key(&proof.id)?;
+
+    let signature_bytes = hex::decode(&proof.signature).map_err(|e| malformed(e.into()))?;
+    let mut sig = Signature::from_der(&signature_bytes).map_err(|e| malformed(e.into()))?;
+
+    if is_high_s(&sig) {
+        if options.reject_high_s {
+            return Err(InvalidReason::HighS);
+        }
+        sig.normalize_s();
+    }
 
-    // Compute signing digest
     let digest = compute_digest_from_hash(hash_hex);
+    let message = Message::from_digest_slice(&digest).map_err(|e| malformed(e.into()))?;
 
-    // Create message from digest
-    let message = Message::from_digest_slice(&digest)?;
+    let secp = SECP256K1;
+    secp.verify_ecdsa(&message, &sig, &public_key)
+        .map_err(|_| InvalidReason::SignatureMismatch)
+}
+
+/// Whether a signature is in high-S (malleable) form
+pub(crate) fn is_high_s(sig: &Signature) -> bool {
+    let mut normalized = *sig;
+    normalized.normalize_s();
+    normalized != *sig
+}
+
+/// Verify a MessagePack proof over data
+///
+/// Counterpart of `sign_msgpack`: the data is re-encoded with
+/// `to_bytes_msgpack`, which is deterministic, so any value equal to the
+/// signed one verifies.
+///
+/// # Arguments
+/// * `data` - The signed data
+/// * `proof` - Signature proof to check
+/// * `is_data_update` - Whether the data was signed as a DataUpdate
+///
+/// # Returns
+/// true if the signature is valid
+///
+/// # Example
+/// ```
+/// use constellation_sdk::sign::sign_msgpack;
+/// use constellation_sdk::verify::verify_msgpack;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let proof = sign_msgpack(&json!({"id": 1}), &key_pair.private_key, false).unwrap();
+/// assert!(verify_msgpack(&json!({"id": 1}), &proof, false).unwrap());
+/// assert!(!verify_msgpack(&json!({"id": 2}), &proof, false).unwrap());
+/// ```
+#[cfg(feature = "msgpack")]
+pub fn verify_msgpack<T: Serialize>(
+    data: &T,
+    proof: &SignatureProof,
+    is_data_update: bool,
+) -> Result<bool> {
+    let hash = hash_bytes(&crate::binary::to_bytes_msgpack(data, is_data_update)?);
+    verify_hash(&hash.value, &proof.signature, &proof.id)
+}
+
+/// Verify a tagged proof over serializable data
+///
+/// Dispatches on `proof.algorithm`: `ALGORITHM`, and `MSGPACK_ALGORITHM`
+/// with the `msgpack` feature.
+///
+/// # Arguments
+/// * `data` - The signed data
+/// * `proof` - Tagged signature proof
+/// * `is_data_update` - Whether the data was signed as a DataUpdate
+///
+/// # Returns
+/// Whether the signature is valid, or `SdkError::UnsupportedAlgorithm` for
+/// any other tag
+///
+/// # Example
+/// ```
+/// use constellation_sdk::{generate_key_pair, sign, verify_tagged, TaggedProof};
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let data = json!({"id": "test"});
+/// let proof = TaggedProof::from(sign(&data, &key_pair.private_key).unwrap());
+/// assert!(verify_tagged(&data, &proof, false).unwrap());
+/// ```
+pub fn verify_tagged<T: Serialize>(
+    data: &T,
+    proof: &TaggedProof,
+    is_data_update: bool,
+) -> Result<bool> {
+    match proof.algorithm.as_str() {
+        ALGORITHM => verify_signature(data, &proof.proof, is_data_update),
+        #[cfg(feature = "msgpack")]
+        crate::types::MSGPACK_ALGORITHM => verify_msgpack(data, &proof.proof, is_data_update),
+        other => Err(SdkError::UnsupportedAlgorithm(other.to_string())),
+    }
+}
+
+/// Check the structure of a proof set before verifying it
+///
+/// A cheap gate for threshold schemes that runs before any ECDSA work:
+/// there are at most `max` proofs, no signer appears twice, and every
+/// signer is authorized. Ids are compared as public keys, so compressed,
+/// uncompressed and unprefixed forms of one key match. No signature is
+/// checked; call `verify` afterwards.
+///
+/// # Arguments
+/// * `proofs` - Proofs to check
+/// * `authorized` - Public keys allowed to sign, in any common form
+/// * `max` - Maximum number of proofs
+///
+/// # Returns
+/// `Ok(())`, or the first violation found:
+/// * `SdkError::TooManyProofs` if there are more than `max` proofs
+/// * `SdkError::UnauthorizedSigner` with the id of a signer not in `authorized`
+/// * `SdkError::DuplicateSigner` with the id of a signer seen earlier
+///
+/// # Example
+/// ```
+/// use constellation_sdk::sign::sign;
+/// use constellation_sdk::verify::validate_proof_set;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use constellation_sdk::SdkError;
+/// use serde_json::json;
+///
+/// let keys: Vec<_> = (0..3).map(|_| generate_key_pair()).collect();
+/// let authorized: Vec<&str> = keys.iter().map(|k| k.public_key.as_str()).collect();
+/// let data = json!({"proposal": 1});
+/// let proof = |i: usize| sign(&data, &keys[i].private_key).unwrap();
+///
+/// assert!(validate_proof_set(&[proof(0), proof(2)], &authorized, 3).is_ok());
+/// assert!(matches!(
+///     validate_proof_set(&[proof(0), proof(0)], &authorized, 3),
+///     Err(SdkError::DuplicateSigner(_))
+/// ));
+/// ```
+pub fn validate_proof_set(
+    proofs: &[SignatureProof],
+    authorized: &[&str],
+    max: usize,
+) -> Result<()> {
+    if proofs.len() > max {
+        return Err(SdkError::TooManyProofs {
+            count: proofs.len(),
+            max,
+        });
+    }
+
+    let key = |id: &str| signer_key(id).unwrap_or_else(|| id.to_ascii_lowercase());
+    let authorized: Vec<String> = authorized.iter().map(|id| key(id)).collect();
+
+    let mut seen = Vec::with_capacity(proofs.len());
+    for proof in proofs {
+        let signer = key(&proof.id);
+        if !authorized.contains(&signer) {
+            return Err(SdkError::UnauthorizedSigner(proof.id.clone()));
+        }
+        if seen.contains(&signer) {
+            return Err(SdkError::DuplicateSigner(proof.id.clone()));
+        }
+        seen.push(signer);
+    }
+    Ok(())
+}
+
+/// Verify a signature against a SHA-256 hash
+///
+/// Protocol:
+/// 1. Treat hash hex as UTF-8 bytes (NOT hex decode)
+/// 2. SHA-512 hash
+/// 3. Truncate to 32 bytes
+/// 4. Verify ECDSA signature
+///
+/// # Arguments
+/// * `hash_hex` - SHA-256 hash as 64-character hex string
+/// * `signature` - DER-encoded signature in hex format
+/// * `public_key_id` - Public key in hex (with or without 04 prefix)
+///
+/// # Returns
+/// true if signature is valid
+pub fn verify_hash(hash_hex: &str, signature: &str, public_key_id: &str) -> Result<bool> {
+    let secp = SECP256K1;
+
+    // Normalize and parse public key
+    let full_public_key = normalize_public_key(public_key_id);
+    let public_key_bytes = hex::decode(&full_public_key)?;
+    let public_key = PublicKey::from_slice(&public_key_bytes)?;
+
+    // Parse signature
+    let signature_bytes = hex::decode(signature)?;
+    let mut sig = Signature::from_der(&signature_bytes)?;
+
+    // Normalize to low-S form for verification compatibility
+    // Some signing implementations produce high-S signatures which are mathematically
+    // valid but rejected by strict BIP 62/146 implementations
+    sig.normalize_s();
+
+    // Compute signing digest
+    let digest = compute_digest_from_hash(hash_hex);
+
+    // Create message from digest
+    let message = Message::from_digest_slice(&digest)?;
+
+    // Verify signature
+    Ok(secp.verify_ecdsa(&message, &sig, &public_key).is_ok())
+}
+
+/// Verify a signature over raw message bytes
+///
+/// Applies the Constellation signing-digest transform directly to the bytes,
+/// without canonicalization or any other encoding:
+/// 1. SHA-256 hash the message
+/// 2. Hex encode the hash, treat as UTF-8
+/// 3. SHA-512 hash, truncate to 32 bytes
+/// 4. Verify ECDSA signature
+///
+/// # Arguments
+/// * `message` - Raw message bytes
+/// * `signature_hex` - DER-encoded signature in hex format
+/// * `public_key_hex` - Public key in hex (with or without 04 prefix)
+///
+/// # Returns
+/// true if signature is valid
+///
+/// # Example
+/// ```
+/// use constellation_sdk::hash::hash_bytes;
+/// use constellation_sdk::sign::sign_hash;
+/// use constellation_sdk::verify::verify_message;
+/// use constellation_sdk::wallet::generate_key_pair;
+///
+/// let key_pair = generate_key_pair();
+/// let message = b"custom message scheme";
+/// let signature = sign_hash(&hash_bytes(message).value, &key_pair.private_key).unwrap();
+/// assert!(verify_message(message, &signature, &key_pair.public_key).unwrap());
+/// ```
+pub fn verify_message(message: &[u8], signature_hex: &str, public_key_hex: &str) -> Result<bool> {
+    let hash = hash_bytes(message);
+    verify_hash(&hash.value, signature_hex, public_key_hex)
+}
+
+/// Check whether two proofs were produced by the same key
+///
+/// Each proof is first verified against its own signed value, which
+/// establishes that the key in its `id` really produced the signature; the
+/// two keys are then compared as curve points, so compressed and uncompressed
+/// ids of the same key match. A proof that does not verify cannot establish
+/// its signer, so the result is false.
+///
+/// # Arguments
+/// * `signed_a` / `signed_b` - Signed objects holding the proofs
+/// * `proof_idx_a` / `proof_idx_b` - Index of the proof within each object
+/// * `is_data_update` - Whether both values were signed as DataUpdates
+///
+/// # Returns
+/// true if both proofs verify and were produced by the same key
+///
+/// # Errors
+/// Returns an error if a proof index is out of range
+pub fn same_signer<A: Serialize, B: Serialize>(
+    signed_a: &Signed<A>,
+    proof_idx_a: usize,
+    signed_b: &Signed<B>,
+    proof_idx_b: usize,
+    is_data_update: bool,
+) -> Result<bool> {
+    let key_a = verified_signer(signed_a, proof_idx_a, is_data_update)?;
+    let key_b = verified_signer(signed_b, proof_idx_b, is_data_update)?;
+
+    Ok(match (key_a, key_b) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    })
+}
+
+/// Verify a signed object and check that its proofs are in a given signer order
+///
+/// Every proof must be valid, as with `verify`, and the proof ids must match
+/// `expected_id_order` one-to-one and in order. Since `add_signature`
+/// appends, this enforces a signing sequence such as manager then director.
+/// Ids are compared as keys, so compressed, prefixed and unprefixed forms of
+/// the same key match.
+///
+/// # Arguments
+/// * `signed` - Signed object with value and proofs
+/// * `expected_id_order` - Public key ids of the signers, in signing order
+/// * `is_data_update` - Whether the value was signed as a DataUpdate
+///
+/// # Returns
+/// true if all proofs are valid and were made by the expected signers in order
+///
+/// # Errors
+/// Returns `SdkError::InvalidPublicKey` if an expected id is not a valid key
+///
+/// # Example
+/// ```
+/// use constellation_sdk::signed_object::{add_signature, create_signed_object};
+/// use constellation_sdk::verify::verify_ordered;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let manager = generate_key_pair();
+/// let director = generate_key_pair();
+/// let signed = create_signed_object(&json!({"expense": 42}), &manager.private_key, false).unwrap();
+/// let signed = add_signature(signed, &director.private_key, false).unwrap();
+///
+/// assert!(verify_ordered(&signed, &[&manager.public_key, &director.public_key], false).unwrap());
+/// assert!(!verify_ordered(&signed, &[&director.public_key, &manager.public_key], false).unwrap());
+/// ```
+pub fn verify_ordered<T: Serialize>(
+    signed: &Signed<T>,
+    expected_id_order: &[&str],
+    is_data_update: bool,
+) -> Result<bool> {
+    let expected = expected_id_order
+        .iter()
+        .map(|id| {
+            signer_key(id)
+                .ok_or_else(|| SdkError::InvalidPublicKey(format!("Invalid expected id: {id}")))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if signed.proofs.len() != expected.len() || !verify(signed, is_data_update).is_valid {
+        return Ok(false);
+    }
+    Ok(signed
+        .proofs
+        .iter()
+        .zip(&expected)
+        .all(|(proof, key)| signer_key(&proof.id).as_ref() == Some(key)))
+}
+
+/// Public key behind a proof, if the proof verifies against the signed value
+fn verified_signer<T: Serialize>(
+    signed: &Signed<T>,
+    proof_idx: usize,
+    is_data_update: bool,
+) -> Result<Option<PublicKey>> {
+    let proof = signed.proofs.get(proof_idx).ok_or_else(|| {
+        SdkError::InvalidSignature(format!(
+            "Proof index {proof_idx} out of range ({} proofs)",
+            signed.proofs.len()
+        ))
+    })?;
+
+    if !verify_signature(&signed.value, proof, is_data_update).unwrap_or(false) {
+        return Ok(None);
+    }
+
+    let public_key_bytes = hex::decode(normalize_public_key(&proof.id))?;
+    Ok(Some(PublicKey::from_slice(&public_key_bytes)?))
+}
+
+/// Recover the signer's public key from an EIP-2098 compact signature
+///
+/// # Arguments
+/// * `digest_hex` - 32-byte digest in hex format that was signed
+/// * `signature_hex` - 64-byte compact signature (`r || yParityAndS`) in hex
+///
+/// # Returns
+/// Recovered uncompressed public key in hex (with 04 prefix - 130 characters)
+///
+/// # Example
+/// ```
+/// use constellation_sdk::hash::keccak256;
+/// use constellation_sdk::sign::sign_hash_eip2098;
+/// use constellation_sdk::verify::recover_from_eip2098;
+/// use constellation_sdk::wallet::generate_key_pair;
+///
+/// let key_pair = generate_key_pair();
+/// let digest = hex::encode(keccak256(b"bridge message"));
+/// let signature = sign_hash_eip2098(&digest, &key_pair.private_key).unwrap();
+/// assert_eq!(recover_from_eip2098(&digest, &signature).unwrap(), key_pair.public_key);
+/// ```
+#[cfg(feature = "recovery")]
+pub fn recover_from_eip2098(digest_hex: &str, signature_hex: &str) -> Result<String> {
+    use crate::hash::compute_signing_digest;
+    use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+
+    let signature_bytes = hex::decode(signature_hex)?;
+    if signature_bytes.len() != 64 {
+        return Err(SdkError::InvalidSignature(format!(
+            "EIP-2098 signature must be 64 bytes, got {}",
+            signature_bytes.len()
+        )));
+    }
+
+    let mut compact = [0u8; 64];
+    compact.copy_from_slice(&signature_bytes);
+    let y_parity = (compact[32] >> 7) as i32;
+    compact[32] &= 0x7f;
+
+    let recovery_id = RecoveryId::from_i32(y_parity)?;
+    let signature = RecoverableSignature::from_compact(&compact, recovery_id)?;
+    let message = Message::from_digest(compute_signing_digest(digest_hex, false)?);
+
+    let secp = SECP256K1;
+    let public_key = secp.recover_ecdsa(&message, &signature)?;
+    Ok(hex::encode(public_key.serialize_uncompressed()))
+}
+
+/// Verify a signature knowing only the signer's DAG address
+///
+/// DER signatures carry no recovery id, so each candidate public key is
+/// recovered from the signature and digest and checked against `address`.
+/// A match proves the holder of that address's key signed `data`.
+///
+/// # Arguments
+/// * `data` - The original data that was signed
+/// * `signature_hex` - DER-encoded signature in hex format
+/// * `address` - DAG address of the expected signer
+/// * `is_data_update` - Whether data was signed as DataUpdate
+///
+/// # Returns
+/// true if a key recovered from the signature derives to `address`
+///
+/// # Example
+/// ```
+/// use constellation_sdk::sign::sign;
+/// use constellation_sdk::verify::verify_by_address;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let data = json!({"id": "test"});
+/// let proof = sign(&data, &key_pair.private_key).unwrap();
+///
+/// assert!(verify_by_address(&data, &proof.signature, &key_pair.address, false).unwrap());
+/// assert!(!verify_by_address(&data, &proof.signature, &generate_key_pair().address, false).unwrap());
+/// ```
+#[cfg(feature = "recovery")]
+pub fn verify_by_address<T: Serialize>(
+    data: &T,
+    signature_hex: &str,
+    address: &str,
+    is_data_update: bool,
+) -> Result<bool> {
+    use crate::hash::compute_digest;
+    use crate::wallet::get_address;
+    use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+
+    let signature = Signature::from_der(&hex::decode(signature_hex)?)?;
+    let compact = signature.serialize_compact();
+    let message = Message::from_digest(compute_digest(data, is_data_update)?);
+
+    let secp = SECP256K1;
+    let matches = (0..4)
+        .filter_map(|id| {
+            let recovery_id = RecoveryId::from_i32(id).ok()?;
+            let recoverable = RecoverableSignature::from_compact(&compact, recovery_id).ok()?;
+            secp.recover_ecdsa(&message, &recoverable).ok()
+        })
+        .any(|public_key| {
+            get_address(&hex::encode(public_key.serialize_uncompressed())) == address.trim()
+        });
+    Ok(matches)
+}
+
+/// Verify a single signature proof against data
+///
+/// # Arguments
+/// * `data` - The original data that was signed
+/// * `proof` - The signature proof to verify
+/// * `is_data_update` - Whether data was signed as DataUpdate
+///
+/// # Returns
+/// true if signature is valid
+pub fn verify_signature<T: Serialize>(
+    data: &T,
+    proof: &SignatureProof,
+    is_data_update: bool,
+) -> Result<bool> {
+    let bytes = to_bytes(data, is_data_update)?;
+    let hash = hash_bytes(&bytes);
+    verify_hash(&hash.value, &proof.signature, &proof.id)
+}
+
+/// Verify a signature against data and a trusted public key
+///
+/// Unlike `verify_signature`, the key comes from the caller rather than the
+/// proof, in any common form.
+///
+/// # Arguments
+/// * `data` - The original data that was signed
+/// * `signature_hex` - DER-encoded signature in hex format
+/// * `public_key` - Public key in hex: 128 characters (no prefix), 130
+///   characters (04-prefixed) or 66 characters (compressed)
+/// * `is_data_update` - Whether data was signed as DataUpdate
+///
+/// # Returns
+/// true if the signature is valid, or `SdkError::InvalidPublicKey` if the
+/// key is not a valid secp256k1 public key in one of those forms
+///
+/// # Example
+/// ```
+/// use constellation_sdk::sign::sign;
+/// use constellation_sdk::verify::verify_signature_against;
+/// use constellation_sdk::wallet::{generate_key_pair, get_public_key_hex};
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let data = json!({"id": "test"});
+/// let proof = sign(&data, &key_pair.private_key).unwrap();
+///
+/// let compressed = get_public_key_hex(&key_pair.private_key, true).unwrap();
+/// assert!(verify_signature_against(&data, &proof.signature, &compressed, false).unwrap());
+/// ```
+pub fn verify_signature_against<T: Serialize>(
+    data: &T,
+    signature_hex: &str,
+    public_key: &str,
+    is_data_update: bool,
+) -> Result<bool> {
+    let public_key = public_key.trim();
+    let key = match public_key.len() {
+        66 | 128 | 130 => signer_key(public_key),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        SdkError::InvalidPublicKey(format!(
+            "expected 128, 130 (04-prefixed) or 66 (compressed) hex characters \
+             encoding a secp256k1 point, got {} characters",
+            public_key.len()
+        ))
+    })?;
+
+    let bytes = to_bytes(data, is_data_update)?;
+    let hash = hash_bytes(&bytes);
+    verify_hash(&hash.value, signature_hex, &key)
+}
+
+/// Verify a signed object and extract the fields the caller knows about
+///
+/// The signature is checked over the full value, exactly as `verify` does,
+/// so unknown fields added by a newer producer are still authenticated. The
+/// returned projection holds only the top-level `known_fields` that are
+/// present (an empty object if the value is not an object).
+///
+/// # Arguments
+/// * `signed` - Signed object with value and proofs
+/// * `known_fields` - Top-level field names to project
+/// * `is_data_update` - Whether the value was signed as a DataUpdate
+///
+/// # Returns
+/// The verification result and the projected value
+///
+/// # Example
+/// ```
+/// use constellation_sdk::signed_object::create_signed_object;
+/// use constellation_sdk::verify::verify_projection;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let data = json!({"id": "a", "amount": 5, "newField": true});
+/// let signed = create_signed_object(&data, &key_pair.private_key, false).unwrap();
+///
+/// let (result, known) = verify_projection(&signed, &["id", "amount"], false);
+/// assert!(result.is_valid);
+/// assert_eq!(known, json!({"id": "a", "amount": 5}));
+/// ```
+pub fn verify_projection(
+    signed: &Signed<Value>,
+    known_fields: &[&str],
+    is_data_update: bool,
+) -> (VerificationResult, Value) {
+    (
+        verify(signed, is_data_update),
+        project(&signed.value, known_fields),
+    )
+}
+
+/// Verify signatures over only the known fields of a value
+///
+/// **Security: this only succeeds if the producer signed the projection.**
+/// The signature is checked against the projection of `known_fields`, not
+/// the full value, so every other field is *unauthenticated*: anyone can add,
+/// change or remove it without invalidating the proofs. Never read fields
+/// outside `known_fields` from an object verified this way; use
+/// `verify_projection` when the producer signed the full value.
+///
+/// # Arguments
+/// * `signed` - Signed object with value and proofs
+/// * `known_fields` - Top-level field names the producer signed
+/// * `is_data_update` - Whether the projection was signed as a DataUpdate
+///
+/// # Returns
+/// VerificationResult for the projected value
+///
+/// # Example
+/// ```
+/// use constellation_sdk::signed_object::create_signed_object;
+/// use constellation_sdk::verify::verify_ignoring_unknown;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let projection = json!({"id": "a", "amount": 5});
+/// let mut signed = create_signed_object(&projection, &key_pair.private_key, false).unwrap();
+///
+/// // A relay appended an extra field after signing
+/// signed.value["relayedBy"] = json!("node-7");
+/// assert!(verify_ignoring_unknown(&signed, &["id", "amount"], false).is_valid);
+/// ```
+pub fn verify_ignoring_unknown(
+    signed: &Signed<Value>,
+    known_fields: &[&str],
+    is_data_update: bool,
+) -> VerificationResult {
+    let projected = Signed {
+        value: project(&signed.value, known_fields),
+        proofs: signed.proofs.clone(),
+    };
+    verify(&projected, is_data_update)
+}
+
+/// Keep only the top-level `fields` of an object
+fn project(value: &Value, fields: &[&str]) -> Value {
+    let projected = value
+        .as_object()
+        .map(|object| {
+            object
+                .iter()
+                .filter(|(key, _)| fields.contains(&key.as_str()))
+                .map(|(key, v)| (key.clone(), v.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+    Value::Object(projected)
+}
+
+/// Check that a migrated value still hashes to the originally signed content
+///
+/// `migrate` maps the migrated value back to the schema that was signed; the
+/// result is canonicalized and hashed, and compared with `original_hash`.
+/// A match proves the migration did not alter signed content, so the
+/// original proofs still vouch for it.
+///
+/// # Arguments
+/// * `original_hash` - SHA-256 hex hash of the originally signed value
+/// * `migrated_value` - Value in the new schema
+/// * `migrate` - Maps the migrated value back to the signed schema
+/// * `is_data_update` - Whether the original was signed as a DataUpdate
+///
+/// # Returns
+/// true if the back-migrated value hashes to `original_hash`
+///
+/// # Example
+/// ```
+/// use constellation_sdk::hash::hash_data;
+/// use constellation_sdk::verify::verify_migrated;
+/// use serde_json::{json, Value};
+///
+/// let original = json!({"name": "sensor", "temp": 21});
+/// let original_hash = hash_data(&original, false).unwrap().value;
+///
+/// // v2 renamed `temp` to `temperature`
+/// let migrated = json!({"name": "sensor", "temperature": 21});
+/// let to_v1 = |v: &Value| json!({"name": v["name"], "temp": v["temperature"]});
+/// assert!(verify_migrated(&original_hash, &migrated, to_v1, false).unwrap());
+/// ```
+pub fn verify_migrated<T>(
+    original_hash: &str,
+    migrated_value: &T,
+    migrate: impl Fn(&T) -> Value,
+    is_data_update: bool,
+) -> Result<bool> {
+    let hash = hash_data(&migrate(migrated_value), is_data_update)?;
+    Ok(hash.value.eq_ignore_ascii_case(original_hash.trim()))
+}
+
+/// Check that an ordinal carried in a signed value is recent
+///
+/// Complements signature verification with a freshness policy: the value's
+/// ordinal must be at most `current_ordinal` and no more than `max_lag`
+/// behind it. An ordinal ahead of `current_ordinal` is not fresh. This does
+/// not verify signatures.
+///
+/// # Arguments
+/// * `signed` - Signed object whose value carries the ordinal
+/// * `field` - Top-level field name, or a JSON pointer (e.g. "/meta/ordinal")
+/// * `current_ordinal` - The latest known ordinal
+/// * `max_lag` - Maximum allowed distance behind `current_ordinal`
+///
+/// # Returns
+/// Whether the ordinal is fresh, or `SdkError::InvalidEnvelope` if the field
+/// is missing or not a non-negative integer (number or numeric string)
+///
+/// # Example
+/// ```
+/// use constellation_sdk::verify::check_freshness;
+/// use constellation_sdk::Signed;
+/// use serde_json::json;
+///
+/// let signed = Signed { value: json!({"ordinal": 95}), proofs: vec![] };
+/// assert!(check_freshness(&signed, "ordinal", 100, 10).unwrap());
+/// assert!(!check_freshness(&signed, "ordinal", 200, 10).unwrap());
+/// assert!(!check_freshness(&signed, "ordinal", 90, 10).unwrap());
+/// assert!(check_freshness(&signed, "epoch", 100, 10).is_err());
+/// ```
+pub fn check_freshness(
+    signed: &Signed<Value>,
+    field: &str,
+    current_ordinal: u64,
+    max_lag: u64,
+) -> Result<bool> {
+    let ordinal = u64_field(&signed.value, field, "ordinal")?;
+    Ok(ordinal <= current_ordinal && current_ordinal - ordinal <= max_lag)
+}
+
+/// Verify a signed object and enforce its schema version
+///
+/// Reads the version from `field` (a top-level key, or a JSON pointer if it
+/// starts with `/`; a number or numeric string) and checks it is one of
+/// `accepted` before verifying the signatures. The version policy is
+/// checked independently of the signatures, so callers must still check
+/// `is_valid` on the returned result.
+///
+/// # Arguments
+/// * `signed` - Signed object with value and proofs
+/// * `field` - Field name or JSON pointer of the version
+/// * `accepted` - Supported schema versions
+/// * `is_data_update` - Whether the value was signed as a DataUpdate
+///
+/// # Returns
+/// The verification result and the schema version, or:
+/// * `SdkError::InvalidEnvelope` if the field is missing or not a
+///   non-negative integer
+/// * `SdkError::UnsupportedVersion` if the version is not in `accepted`
+///
+/// # Example
+/// ```
+/// use constellation_sdk::signed_object::create_signed_object;
+/// use constellation_sdk::verify::verify_with_version;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use constellation_sdk::SdkError;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let data = json!({"schemaVersion": 2, "id": "a"});
+/// let signed = create_signed_object(&data, &key_pair.private_key, false).unwrap();
+///
+/// let (result, version) = verify_with_version(&signed, "schemaVersion", &[1, 2], false).unwrap();
+/// assert!(result.is_valid);
+/// assert_eq!(version, 2);
+///
+/// assert!(matches!(
+///     verify_with_version(&signed, "schemaVersion", &[3], false),
+///     Err(SdkError::UnsupportedVersion(2))
+/// ));
+/// ```
+pub fn verify_with_version(
+    signed: &Signed<Value>,
+    field: &str,
+    accepted: &[u64],
+    is_data_update: bool,
+) -> Result<(VerificationResult, u64)> {
+    let version = u64_field(&signed.value, field, "version")?;
+    if !accepted.contains(&version) {
+        return Err(SdkError::UnsupportedVersion(version));
+    }
+    Ok((verify(signed, is_data_update), version))
+}
+
+/// Verify a signed object and check that its timestamp is recent
+///
+/// Reads an RFC 3339 timestamp from `field` (a top-level key such as
+/// `"_signedAt"` from `create_timestamped`, or a JSON pointer) and rejects
+/// it if it is older than `max_age`, or more than `max_age` in the future
+/// (allowing for clock skew). The timestamp is only meaningful because it
+/// is part of the signed value; callers must still check `is_valid` on the
+/// returned result.
+///
+/// # Arguments
+/// * `signed` - Signed object with value and proofs
+/// * `field` - Field name or JSON pointer of the timestamp
+/// * `max_age` - Maximum allowed age
+/// * `is_data_update` - Whether the value was signed as a DataUpdate
+///
+/// # Returns
+/// The verification result, or:
+/// * `SdkError::InvalidEnvelope` if the field is missing or not an RFC 3339
+///   timestamp
+/// * `SdkError::StaleTimestamp` if the timestamp is out of range
+///
+/// # Example
+/// ```
+/// use constellation_sdk::signed_object::{create_timestamped, SIGNED_AT_FIELD};
+/// use constellation_sdk::verify::verify_with_max_age;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+/// use std::time::Duration;
+///
+/// let key_pair = generate_key_pair();
+/// let signed = create_timestamped(&json!({"id": "a"}), &key_pair.private_key, false).unwrap();
+///
+/// let result = verify_with_max_age(&signed, SIGNED_AT_FIELD, Duration::from_secs(60), false);
+/// assert!(result.unwrap().is_valid);
+/// ```
+pub fn verify_with_max_age(
+    signed: &Signed<Value>,
+    field: &str,
+    max_age: std::time::Duration,
+    is_data_update: bool,
+) -> Result<VerificationResult> {
+    let timestamp = field_value(&signed.value, field, "timestamp")?;
+    let signed_at = timestamp.as_str().and_then(rfc3339::parse).ok_or_else(|| {
+        SdkError::InvalidEnvelope(format!(
+            "timestamp field `{field}` is not an RFC 3339 timestamp"
+        ))
+    })?;
+
+    let age = rfc3339::now_millis() - signed_at;
+    let max_age = i64::try_from(max_age.as_millis()).unwrap_or(i64::MAX);
+    if age.abs() > max_age {
+        return Err(SdkError::StaleTimestamp(format!(
+            "{} is {} ms {} (limit {} ms)",
+            rfc3339::format(signed_at),
+            age.abs(),
+            if age < 0 { "in the future" } else { "old" },
+            max_age
+        )));
+    }
+    Ok(verify(signed, is_data_update))
+}
+
+/// Look up a top-level field, or a JSON pointer if `field` starts with `/`
+fn field_value<'a>(value: &'a Value, field: &str, kind: &str) -> Result<&'a Value> {
+    if field.starts_with('/') {
+        value.pointer(field)
+    } else {
+        value.get(field)
+    }
+    .ok_or_else(|| SdkError::InvalidEnvelope(format!("missing {kind} field `{field}`")))
+}
+
+/// Read a non-negative integer (number or numeric string) from a field or
+/// JSON pointer
+fn u64_field(value: &Value, field: &str, kind: &str) -> Result<u64> {
+    let value = field_value(value, field, kind)?;
+
+    match value {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        SdkError::InvalidEnvelope(format!(
+            "{kind} field `{field}` is not a non-negative integer"
+        ))
+    })
+}
+
+/// List the differences between a signed value and a presumed original
+///
+/// This is a diagnostic aid for verification failures: when a signed object
+/// does not verify, comparing it with the value you expected to be signed
+/// shows which fields were altered.
+///
+/// # Arguments
+/// * `signed` - Signed object whose value is compared
+/// * `original` - The presumed original value
+///
+/// # Returns
+/// One JsonDiff per differing JSON pointer (empty if the values are equal)
+///
+/// # Example
+/// ```
+/// use constellation_sdk::verify::diff_against;
+/// use constellation_sdk::Signed;
+/// use serde_json::json;
+///
+/// let signed = Signed { value: json!({"amount": 999}), proofs: vec![] };
+/// let diffs = diff_against(&signed, &json!({"amount": 100}));
+/// assert_eq!(diffs[0].pointer, "/amount");
+/// ```
+pub fn diff_against(signed: &Signed<Value>, original: &Value) -> Vec<JsonDiff> {
+    let mut diffs = Vec::new();
+    diff_values("", Some(original), Some(&signed.value), &mut diffs);
+    diffs
+}
+
+fn diff_values(pointer: &str, old: Option<&Value>, new: Option<&Value>, diffs: &mut Vec<JsonDiff>) {
+    match (old, new) {
+        (Some(Value::Object(old_map)), Some(Value::Object(new_map))) => {
+            let mut keys: Vec<&String> = old_map.keys().chain(new_map.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let child = format!("{pointer}/{}", escape_pointer_token(key));
+                diff_values(&child, old_map.get(key), new_map.get(key), diffs);
+            }
+        }
+        (Some(Value::Array(old_items)), Some(Value::Array(new_items))) => {
+            for i in 0..old_items.len().max(new_items.len()) {
+                let child = format!("{pointer}/{i}");
+                diff_values(&child, old_items.get(i), new_items.get(i), diffs);
+            }
+        }
+        (old, new) if old != new => diffs.push(JsonDiff {
+            pointer: pointer.to_string(),
+            old: old.cloned(),
+            new: new.cloned(),
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sign::{sign, sign_data_update, sign_hash, sign_with_options};
+    use crate::types::{SigningOptions, CONSTELLATION_PREFIX};
+    use crate::wallet::generate_key_pair;
+    use serde_json::json;
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_verify_parallel_matches_verify() {
+        let value = json!({"proposal": 7});
+        let mut signed = Signed {
+            value: value.clone(),
+            proofs: vec![],
+        };
+        for i in 0..50 {
+            let key_pair = generate_key_pair();
+            let mut proof = sign(&value, &key_pair.private_key).unwrap();
+            if i % 7 == 3 {
+                // Signature over other data, or not a signature at all
+                proof.signature = if i % 2 == 0 {
+                    sign(&json!({"proposal": 8}), &key_pair.private_key)
+                        .unwrap()
+                        .signature
+                } else {
+                    "zz".to_string()
+                };
+            }
+            signed.proofs.push(proof);
+        }
+
+        let serial = verify(&signed, false);
+        let parallel = verify_parallel(&signed, false);
+        assert_eq!(parallel, serial);
+        assert_eq!(parallel.invalid_proofs.len(), 7);
+
+        signed.proofs.truncate(0);
+        assert_eq!(verify_parallel(&signed, false), verify(&signed, false));
+    }
+
+    #[test]
+    fn test_verify_cached_matches_verify() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let value = json!({"id": "cached"});
+        let mut signed = Signed {
+            value: value.clone(),
+            proofs: vec![
+                sign(&value, &key1.private_key).unwrap(),
+                sign(&json!({"id": "other"}), &key2.private_key).unwrap(),
+            ],
+        };
+
+        let mut cache = VerifyCache::new(8);
+        for _ in 0..2 {
+            assert_eq!(
+                verify_cached(&signed, false, &mut cache),
+                verify(&signed, false)
+            );
+        }
+        // Invalid outcomes are cached too, with their reasons
+        assert_eq!(cache.len(), 2);
+
+        // A tampered value hashes differently, so it never hits the cache
+        signed.value = json!({"id": "tampered"});
+        let result = verify_cached(&signed, false, &mut cache);
+        assert!(!result.is_valid);
+        assert_eq!(result, verify(&signed, false));
+        assert_eq!(cache.len(), 4);
+    }
+
+    #[test]
+    fn test_verify_cache_evicts_least_recently_used() {
+        let key_pair = generate_key_pair();
+        let signed: Vec<_> = (0..3)
+            .map(|i| {
+                let value = json!({"n": i});
+                let proof = sign(&value, &key_pair.private_key).unwrap();
+                Signed {
+                    value,
+                    proofs: vec![proof],
+                }
+            })
+            .collect();
+        let key = |s: &Signed<Value>| {
+            let hash = signing_hash(&s.value, false, &VerifyOptions::default()).unwrap();
+            (hash, s.proofs[0].signature.clone(), s.proofs[0].id.clone())
+        };
+
+        let mut cache = VerifyCache::new(2);
+        verify_cached(&signed[0], false, &mut cache);
+        verify_cached(&signed[1], false, &mut cache);
+        // Touch 0 so that 1 is the least recently used
+        verify_cached(&signed[0], false, &mut cache);
+        verify_cached(&signed[2], false, &mut cache);
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&key(&signed[0])).is_some());
+        assert!(cache.get(&key(&signed[1])).is_none());
+        assert!(cache.get(&key(&signed[2])).is_some());
+
+        cache.clear();
+        assert!(cache.is_empty());
+
+        let mut disabled = VerifyCache::new(0);
+        assert!(verify_cached(&signed[0], false, &mut disabled).is_valid);
+        assert!(disabled.is_empty());
+    }
+
+    #[test]
+    fn test_verify_value_matches_verify() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let value = json!({"id": "embedded", "n": 1});
+        let signed = Signed {
+            value: value.clone(),
+            proofs: vec![
+                sign_data_update(&value, &key1.private_key).unwrap(),
+                sign(&value, &key2.private_key).unwrap(),
+            ],
+        };
+
+        let result = verify_value(&value, &signed.proofs, true);
+        assert_eq!(result, verify(&signed, true));
+        assert!(!result.is_valid);
+        assert_eq!(result.valid_proofs, vec![signed.proofs[0].clone()]);
+        assert!(verify_value(&value, &signed.proofs[..1], true).is_valid);
+        assert!(!verify_value(&value, &[], true).is_valid);
+    }
+
+    #[test]
+    fn test_verify_canonical_matches_verify() {
+        let key_pair = generate_key_pair();
+        let value = json!({"b": [1, 2], "a": "x"});
 
-    // Verify signature
-    Ok(secp.verify_ecdsa(&message, &sig, &public_key).is_ok())
-}
+        for is_data_update in [false, true] {
+            let proof = if is_data_update {
+                sign_data_update(&value, &key_pair.private_key).unwrap()
+            } else {
+                sign(&value, &key_pair.private_key).unwrap()
+            };
+            let signed = Signed {
+                value: value.clone(),
+                proofs: vec![proof],
+            };
+            let canonical = crate::canonicalize::canonicalize_bytes(&value).unwrap();
 
-/// Verify a single signature proof against data
-///
-/// # Arguments
-/// * `data` - The original data that was signed
-/// * `proof` - The signature proof to verify
-/// * `is_data_update` - Whether data was signed as DataUpdate
-///
-/// # Returns
-/// true if signature is valid
-pub fn verify_signature<T: Serialize>(
-    data: &T,
-    proof: &SignatureProof,
-    is_data_update: bool,
-) -> Result<bool> {
-    let bytes = to_bytes(data, is_data_update)?;
-    let hash = hash_bytes(&bytes);
-    verify_hash(&hash.value, &proof.signature, &proof.id)
-}
+            let result = verify_canonical(&canonical, &signed.proofs, is_data_update);
+            assert_eq!(result, verify(&signed, is_data_update));
+            assert!(result.is_valid);
+            assert!(!verify_canonical(&canonical, &signed.proofs, !is_data_update).is_valid);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::sign::{sign, sign_data_update};
-    use crate::wallet::generate_key_pair;
-    use serde_json::json;
+            // Non-canonical bytes of the same value don't verify
+            let pretty = serde_json::to_vec_pretty(&value).unwrap();
+            assert!(!verify_canonical(&pretty, &signed.proofs, is_data_update).is_valid);
+        }
+    }
 
     #[test]
     fn test_verify_signed_object() {
@@ -204,4 +1725,619 @@ mod tests {
         let is_valid = verify_signature(&data, &proof, false).unwrap();
         assert!(is_valid);
     }
+
+    #[test]
+    fn test_verify_accepts_compressed_id() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test"});
+        let options = SigningOptions {
+            compressed_id: true,
+            ..Default::default()
+        };
+        let proof = sign_with_options(&data, &key_pair.private_key, &options).unwrap();
+
+        let signed = Signed {
+            value: data,
+            proofs: vec![proof],
+        };
+        assert!(verify(&signed, false).is_valid);
+    }
+
+    #[test]
+    fn test_domain_separation_prevents_replay() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test"});
+        let options = SigningOptions {
+            domain: Some("app-a".to_string()),
+            ..Default::default()
+        };
+        let signed = Signed {
+            value: data.clone(),
+            proofs: vec![sign_with_options(&data, &key_pair.private_key, &options).unwrap()],
+        };
+
+        let verify_in = |domain: Option<&str>| {
+            let options = VerifyOptions {
+                domain: domain.map(str::to_string),
+                ..Default::default()
+            };
+            verify_with_options(&signed, false, &options).is_valid
+        };
+        assert!(verify_in(Some("app-a")));
+        assert!(!verify_in(Some("app-b")));
+        assert!(!verify_in(None));
+
+        // Undomained signatures don't verify under a domain requirement
+        let plain = Signed {
+            value: data.clone(),
+            proofs: vec![sign(&data, &key_pair.private_key).unwrap()],
+        };
+        let options = VerifyOptions {
+            domain: Some("app-a".to_string()),
+            ..Default::default()
+        };
+        assert!(!verify_with_options(&plain, false, &options).is_valid);
+    }
+
+    #[test]
+    fn test_verify_reports_matched_mode() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test"});
+        let signed = Signed {
+            value: data.clone(),
+            proofs: vec![sign(&data, &key_pair.private_key).unwrap()],
+        };
+        assert_eq!(
+            verify(&signed, false).matched_mode,
+            Some(SigningMode::Regular)
+        );
+        assert_eq!(verify(&signed, true).matched_mode, None);
+    }
+
+    #[test]
+    fn test_verify_auto_detects_mode() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test"});
+
+        let regular = Signed {
+            value: data.clone(),
+            proofs: vec![sign(&data, &key_pair.private_key).unwrap()],
+        };
+        let result = verify_auto(&regular);
+        assert!(result.is_valid);
+        assert_eq!(result.matched_mode, Some(SigningMode::Regular));
+
+        let update = Signed {
+            value: data.clone(),
+            proofs: vec![sign_data_update(&data, &key_pair.private_key).unwrap()],
+        };
+        let result = verify_auto(&update);
+        assert!(result.is_valid);
+        assert_eq!(result.matched_mode, Some(SigningMode::DataUpdate));
+    }
+
+    #[test]
+    fn test_verify_auto_mixed_and_invalid() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let data = json!({"id": "test"});
+
+        let mixed = Signed {
+            value: data.clone(),
+            proofs: vec![
+                sign(&data, &key1.private_key).unwrap(),
+                sign_data_update(&data, &key2.private_key).unwrap(),
+            ],
+        };
+        let result = verify_auto(&mixed);
+        assert!(result.is_valid);
+        assert_eq!(result.matched_mode, None);
+
+        let tampered = Signed {
+            value: json!({"id": "other"}),
+            proofs: vec![sign(&data, &key1.private_key).unwrap()],
+        };
+        let result = verify_auto(&tampered);
+        assert!(!result.is_valid);
+        assert_eq!(result.matched_mode, None);
+        assert_eq!(
+            result.invalid_reasons,
+            vec![InvalidReason::SignatureMismatch]
+        );
+    }
+
+    #[test]
+    fn test_off_curve_id_is_malformed_public_key() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let data = json!({"id": "test"});
+        let proof = sign(&data, &key1.private_key).unwrap();
+
+        let off_curve = SignatureProof {
+            id: "00".repeat(64),
+            signature: proof.signature.clone(),
+        };
+        let wrong_key = SignatureProof {
+            id: key2.public_key[2..].to_string(),
+            signature: proof.signature,
+        };
+        let signed = Signed {
+            value: data,
+            proofs: vec![off_curve, wrong_key],
+        };
+
+        let result = verify(&signed, false);
+        assert!(matches!(
+            result.invalid_reasons[0],
+            InvalidReason::MalformedPublicKey(_)
+        ));
+        assert_eq!(result.invalid_reasons[1], InvalidReason::SignatureMismatch);
+    }
+
+    #[test]
+    fn test_signer_addresses_only_lists_valid_proofs() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let data = json!({"id": "test"});
+        let signed = Signed {
+            value: data.clone(),
+            proofs: vec![
+                sign(&data, &key1.private_key).unwrap(),
+                sign(&json!({"id": "other"}), &key2.private_key).unwrap(),
+            ],
+        };
+        let result = verify(&signed, false);
+        assert_eq!(result.signer_addresses(), vec![key1.address]);
+    }
+
+    #[test]
+    fn test_check_freshness() {
+        let signed = Signed {
+            value: json!({"ordinal": 100, "meta": {"epoch": "42"}, "bad": -1}),
+            proofs: vec![],
+        };
+        // Boundary: exactly max_lag behind is fresh
+        assert!(check_freshness(&signed, "ordinal", 110, 10).unwrap());
+        assert!(check_freshness(&signed, "ordinal", 100, 10).unwrap());
+        assert!(!check_freshness(&signed, "ordinal", 111, 10).unwrap());
+
+        // An ordinal from the future is not fresh, however close
+        assert!(!check_freshness(&signed, "ordinal", 99, 10).unwrap());
+        assert!(!check_freshness(&signed, "ordinal", 90, 10).unwrap());
+        assert!(!check_freshness(&signed, "ordinal", 0, u64::MAX).unwrap());
+
+        assert!(check_freshness(&signed, "/meta/epoch", 42, 0).unwrap());
+        assert!(check_freshness(&signed, "bad", 0, 10).is_err());
+        assert!(check_freshness(&signed, "meta", 0, 10).is_err());
+        assert!(check_freshness(&signed, "missing", 0, 10).is_err());
+    }
+
+    #[test]
+    fn test_unsigned_object_is_distinguished() {
+        let key_pair = generate_key_pair();
+        let unsigned = Signed {
+            value: json!({"id": "test"}),
+            proofs: vec![],
+        };
+        let result = verify(&unsigned, false);
+        assert!(!result.is_valid);
+        assert!(result.is_unsigned());
+        assert_eq!(result.invalid_reason(), Some(InvalidReason::NoProofs));
+        assert_eq!(
+            verify_auto(&unsigned).invalid_reason(),
+            Some(InvalidReason::NoProofs)
+        );
+
+        let bad = Signed {
+            value: json!({"id": "test"}),
+            proofs: vec![sign(&json!({"id": "other"}), &key_pair.private_key).unwrap()],
+        };
+        let result = verify(&bad, false);
+        assert!(!result.is_unsigned());
+        assert_eq!(
+            result.invalid_reason(),
+            Some(InvalidReason::SignatureMismatch)
+        );
+
+        let good = Signed {
+            value: json!({"id": "test"}),
+            proofs: vec![sign(&json!({"id": "test"}), &key_pair.private_key).unwrap()],
+        };
+        assert_eq!(verify(&good, false).invalid_reason(), None);
+    }
+
+    #[test]
+    fn test_verify_projection_authenticates_full_value() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "a", "amount": 5, "extra": [1]});
+        let mut signed = Signed {
+            value: data.clone(),
+            proofs: vec![sign(&data, &key_pair.private_key).unwrap()],
+        };
+
+        let (result, known) = verify_projection(&signed, &["id", "missing"], false);
+        assert!(result.is_valid);
+        assert_eq!(known, json!({"id": "a"}));
+
+        // Tampering with an unknown field is still detected
+        signed.value["extra"] = json!([2]);
+        let (result, _) = verify_projection(&signed, &["id"], false);
+        assert!(!result.is_valid);
+    }
+
+    #[test]
+    fn test_verify_ignoring_unknown_requires_signed_projection() {
+        let key_pair = generate_key_pair();
+        let full = json!({"id": "a", "amount": 5, "extra": true});
+
+        // Producer signed the full value: the projection does not verify
+        let signed_full = Signed {
+            value: full.clone(),
+            proofs: vec![sign(&full, &key_pair.private_key).unwrap()],
+        };
+        assert!(!verify_ignoring_unknown(&signed_full, &["id", "amount"], false).is_valid);
+
+        // Producer signed the projection: unknown fields are ignored
+        let projection = json!({"id": "a", "amount": 5});
+        let signed_projection = Signed {
+            value: full,
+            proofs: vec![sign(&projection, &key_pair.private_key).unwrap()],
+        };
+        assert!(verify_ignoring_unknown(&signed_projection, &["id", "amount"], false).is_valid);
+        assert!(!verify_ignoring_unknown(&signed_projection, &["id"], false).is_valid);
+    }
+
+    #[test]
+    fn test_verify_migrated_detects_altered_content() {
+        let original = json!({"id": "a", "amount": 5});
+        let original_hash = crate::hash::hash_data(&original, true).unwrap().value;
+        let to_v1 = |v: &Value| json!({"id": v["id"], "amount": v["value"]["amount"]});
+
+        let migrated = json!({"id": "a", "value": {"amount": 5}});
+        assert!(verify_migrated(&original_hash, &migrated, to_v1, true).unwrap());
+        assert!(verify_migrated(&original_hash.to_uppercase(), &migrated, to_v1, true).unwrap());
+        assert!(!verify_migrated(&original_hash, &migrated, to_v1, false).unwrap());
+
+        let altered = json!({"id": "a", "value": {"amount": 6}});
+        assert!(!verify_migrated(&original_hash, &altered, to_v1, true).unwrap());
+    }
+
+    #[test]
+    fn test_verify_with_version() {
+        let key_pair = generate_key_pair();
+        let data = json!({"meta": {"schemaVersion": "3"}, "id": "a"});
+        let mut signed = Signed {
+            value: data.clone(),
+            proofs: vec![sign(&data, &key_pair.private_key).unwrap()],
+        };
+
+        let (result, version) =
+            verify_with_version(&signed, "/meta/schemaVersion", &[3], false).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(version, 3);
+
+        assert!(matches!(
+            verify_with_version(&signed, "/meta/schemaVersion", &[1, 2], false),
+            Err(SdkError::UnsupportedVersion(3))
+        ));
+        assert!(matches!(
+            verify_with_version(&signed, "schemaVersion", &[3], false),
+            Err(SdkError::InvalidEnvelope(_))
+        ));
+
+        // A supported version does not vouch for the signature
+        signed.value["id"] = json!("b");
+        let (result, _) = verify_with_version(&signed, "/meta/schemaVersion", &[3], false).unwrap();
+        assert!(!result.is_valid);
+    }
+
+    #[test]
+    fn test_same_signer() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let a = Signed {
+            value: json!({"id": "a"}),
+            proofs: vec![sign(&json!({"id": "a"}), &key1.private_key).unwrap()],
+        };
+        let compressed = SigningOptions {
+            compressed_id: true,
+            ..Default::default()
+        };
+        let b = Signed {
+            value: json!({"id": "b"}),
+            proofs: vec![
+                sign_with_options(&json!({"id": "b"}), &key1.private_key, &compressed).unwrap(),
+                sign(&json!({"id": "b"}), &key2.private_key).unwrap(),
+            ],
+        };
+
+        assert!(same_signer(&a, 0, &b, 0, false).unwrap());
+        assert!(!same_signer(&a, 0, &b, 1, false).unwrap());
+        assert!(same_signer(&a, 0, &b, 5, false).is_err());
+    }
+
+    #[test]
+    fn test_same_signer_rejects_lying_id() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let a = Signed {
+            value: json!({"id": "a"}),
+            proofs: vec![sign(&json!({"id": "a"}), &key1.private_key).unwrap()],
+        };
+        // Claims key1's id but was signed by key2
+        let mut forged = sign(&json!({"id": "b"}), &key2.private_key).unwrap();
+        forged.id = a.proofs[0].id.clone();
+        let b = Signed {
+            value: json!({"id": "b"}),
+            proofs: vec![forged],
+        };
+
+        assert!(!same_signer(&a, 0, &b, 0, false).unwrap());
+    }
+
+    #[cfg(feature = "recovery")]
+    #[test]
+    fn test_eip2098_known_vector() {
+        use crate::hash::keccak256;
+        use crate::sign::sign_hash_eip2098;
+        use crate::wallet::{get_evm_address, key_pair_from_private_key};
+
+        // Test vector from EIP-2098 (personal_sign of "Hello World")
+        let private_key = "1234567890123456789012345678901234567890123456789012345678901234";
+        let digest = hex::encode(keccak256(b"\x19Ethereum Signed Message:\n11Hello World"));
+        assert_eq!(
+            digest,
+            "a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2"
+        );
+
+        let signature = sign_hash_eip2098(&digest, private_key).unwrap();
+        assert_eq!(
+            signature,
+            "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90\
+             7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064"
+        );
+
+        let recovered = recover_from_eip2098(&digest, &signature).unwrap();
+        let key_pair = key_pair_from_private_key(private_key).unwrap();
+        assert_eq!(recovered, key_pair.public_key);
+        assert_eq!(
+            get_evm_address(&recovered).unwrap(),
+            "0x2e988A386a799F506693793c6A5AF6B54dfAaBfB"
+        );
+    }
+
+    #[cfg(feature = "recovery")]
+    #[test]
+    fn test_eip2098_roundtrip_both_parities() {
+        use crate::sign::sign_hash_eip2098;
+
+        let key_pair = generate_key_pair();
+        let mut seen_parities = [false; 2];
+        for i in 0u32..64 {
+            let digest = hash_bytes(&i.to_be_bytes()).value;
+            let signature = sign_hash_eip2098(&digest, &key_pair.private_key).unwrap();
+            seen_parities[(hex::decode(&signature).unwrap()[32] >> 7) as usize] = true;
+            assert_eq!(
+                recover_from_eip2098(&digest, &signature).unwrap(),
+                key_pair.public_key
+            );
+        }
+        assert_eq!(seen_parities, [true, true]);
+    }
+
+    #[cfg(feature = "recovery")]
+    #[test]
+    fn test_verify_by_address() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test"});
+
+        for is_data_update in [false, true] {
+            let proof = if is_data_update {
+                sign_data_update(&data, &key_pair.private_key).unwrap()
+            } else {
+                sign(&data, &key_pair.private_key).unwrap()
+            };
+            assert!(
+                verify_by_address(&data, &proof.signature, &key_pair.address, is_data_update)
+                    .unwrap()
+            );
+            assert!(!verify_by_address(
+                &data,
+                &proof.signature,
+                &key_pair.address,
+                !is_data_update
+            )
+            .unwrap());
+            assert!(!verify_by_address(
+                &json!({"id": "other"}),
+                &proof.signature,
+                &key_pair.address,
+                is_data_update
+            )
+            .unwrap());
+        }
+
+        assert!(verify_by_address(&data, "zz", &key_pair.address, false).is_err());
+    }
+
+    #[test]
+    fn test_verify_message_raw_bytes() {
+        let key_pair = generate_key_pair();
+        let message = b"\x00\x01 not json";
+        let signature = sign_hash(&hash_bytes(message).value, &key_pair.private_key).unwrap();
+
+        assert!(verify_message(message, &signature, &key_pair.public_key).unwrap());
+        assert!(!verify_message(b"other", &signature, &key_pair.public_key).unwrap());
+    }
+
+    #[test]
+    fn test_diff_against_reports_altered_fields() {
+        let original = json!({"id": "test", "value": 42, "tags": ["a"]});
+        let signed = Signed {
+            value: json!({"id": "test", "value": 999, "tags": ["a", "b"], "x/y": 1}),
+            proofs: vec![],
+        };
+
+        let diffs = diff_against(&signed, &original);
+        let pointers: Vec<&str> = diffs.iter().map(|d| d.pointer.as_str()).collect();
+        assert_eq!(pointers, vec!["/tags/1", "/value", "/x~1y"]);
+        assert_eq!(diffs[1].old, Some(json!(42)));
+        assert_eq!(diffs[1].new, Some(json!(999)));
+        assert_eq!(diffs[0].old, None);
+    }
+
+    #[test]
+    fn test_diff_against_identical_values() {
+        let data = json!({"id": "test", "nested": {"a": 1}});
+        let signed = Signed {
+            value: data.clone(),
+            proofs: vec![],
+        };
+        assert!(diff_against(&signed, &data).is_empty());
+    }
+
+    /// Flip a DER signature to its high-S twin (s' = n - s)
+    fn to_high_s(signature_hex: &str) -> String {
+        let sig = Signature::from_der(&hex::decode(signature_hex).unwrap()).unwrap();
+        let compact = sig.serialize_compact();
+        let order = secp256k1::constants::CURVE_ORDER;
+        let mut s = [0u8; 32];
+        let mut borrow = 0i16;
+        for i in (0..32).rev() {
+            let diff = order[i] as i16 - compact[32 + i] as i16 - borrow;
+            borrow = i16::from(diff < 0);
+            s[i] = (diff + 256 * borrow) as u8;
+        }
+        let mut high = [0u8; 64];
+        high[..32].copy_from_slice(&compact[..32]);
+        high[32..].copy_from_slice(&s);
+        // from_compact does not normalize, so the high-S value is preserved
+        hex::encode(Signature::from_compact(&high).unwrap().serialize_der())
+    }
+
+    #[test]
+    fn test_verify_high_s_accepted_by_default() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test"});
+        let mut proof = sign(&data, &key_pair.private_key).unwrap();
+        proof.signature = to_high_s(&proof.signature);
+
+        let signed = Signed {
+            value: data,
+            proofs: vec![proof],
+        };
+        assert!(verify(&signed, false).is_valid);
+    }
+
+    #[test]
+    fn test_verify_high_s_rejected_when_requested() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test"});
+        let mut proof = sign(&data, &key_pair.private_key).unwrap();
+        proof.signature = to_high_s(&proof.signature);
+
+        let signed = Signed {
+            value: data,
+            proofs: vec![proof],
+        };
+        let options = VerifyOptions {
+            reject_high_s: true,
+            ..Default::default()
+        };
+        let result = verify_with_options(&signed, false, &options);
+        assert!(!result.is_valid);
+        assert_eq!(result.invalid_reasons, vec![InvalidReason::HighS]);
+    }
+
+    #[test]
+    fn test_custom_data_update_prefix() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "forked"});
+        let fork_prefix = "\x19Forked Signed Data:\n".to_string();
+        let signing = SigningOptions {
+            is_data_update: true,
+            data_update_prefix: Some(fork_prefix.clone()),
+            ..Default::default()
+        };
+        let signed = Signed {
+            value: data.clone(),
+            proofs: vec![sign_with_options(&data, &key_pair.private_key, &signing).unwrap()],
+        };
+
+        let fork = VerifyOptions {
+            data_update_prefix: Some(fork_prefix),
+            ..Default::default()
+        };
+        assert!(verify_with_options(&signed, true, &fork).is_valid);
+        assert!(!verify(&signed, true).is_valid);
+
+        // The prefix only applies to DataUpdates, and the default is unchanged
+        let regular = Signed {
+            value: data.clone(),
+            proofs: vec![sign(&data, &key_pair.private_key).unwrap()],
+        };
+        assert!(verify_with_options(&regular, false, &fork).is_valid);
+        let standard = Signed {
+            value: data.clone(),
+            proofs: vec![sign_data_update(&data, &key_pair.private_key).unwrap()],
+        };
+        assert!(verify_with_options(&standard, true, &VerifyOptions::default()).is_valid);
+        assert!(!verify_with_options(&standard, true, &fork).is_valid);
+
+        let explicit_default = VerifyOptions {
+            data_update_prefix: Some(CONSTELLATION_PREFIX.to_string()),
+            ..Default::default()
+        };
+        assert!(verify_with_options(&standard, true, &explicit_default).is_valid);
+    }
+
+    #[test]
+    fn test_probe_mode() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let data = json!({"id": "probe"});
+        let signed = |proofs| Signed {
+            value: data.clone(),
+            proofs,
+        };
+        let regular = sign(&data, &key1.private_key).unwrap();
+        let data_update = sign_data_update(&data, &key2.private_key).unwrap();
+
+        assert_eq!(
+            probe_mode(&signed(vec![regular.clone()])).unwrap(),
+            Some(false)
+        );
+        assert_eq!(
+            probe_mode(&signed(vec![
+                data_update.clone(),
+                sign_data_update(&data, &key1.private_key).unwrap()
+            ]))
+            .unwrap(),
+            Some(true)
+        );
+
+        // Mixed modes, no proofs, or a proof over other data
+        assert_eq!(
+            probe_mode(&signed(vec![regular, data_update])).unwrap(),
+            None
+        );
+        assert_eq!(probe_mode(&signed(vec![])).unwrap(), None);
+        let other = sign(&json!({"id": "other"}), &key1.private_key).unwrap();
+        assert_eq!(probe_mode(&signed(vec![other])).unwrap(), None);
+    }
+
+    #[test]
+    fn test_verify_reports_mismatch_reason() {
+        let key_pair = generate_key_pair();
+        let proof = sign(&json!({"id": "a"}), &key_pair.private_key).unwrap();
+        let signed = Signed {
+            value: json!({"id": "b"}),
+            proofs: vec![proof],
+        };
+        let result = verify(&signed, false);
+        assert_eq!(
+            result.invalid_reasons,
+            vec![InvalidReason::SignatureMismatch]
+        );
+    }
 }
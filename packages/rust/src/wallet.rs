@@ -3,12 +3,83 @@
 //! Functions for generating and managing cryptographic keys.
 
 use rand::rngs::OsRng;
-use secp256k1::{PublicKey, Secp256k1, SecretKey};
+use secp256k1::{PublicKey, SecretKey, SECP256K1};
 use sha2::{Digest, Sha256};
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
-use crate::types::{KeyPair, Result, SdkError};
+use crate::currency_transaction::is_valid_dag_address;
+use crate::encoding::base58_encode;
+use crate::types::{IdFormat, KeyPair, Result, SdkError, SignatureProof};
 
-const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+mod bip32;
+pub mod shamir;
+pub mod stealth;
+
+use bip32::{ExtendedKey, HARDENED};
+
+/// SLIP-44 coin type registered for Constellation (DAG)
+pub const DAG_COIN_TYPE: u32 = 1137;
+
+/// X.509 SubjectPublicKeyInfo DER prefix for an uncompressed secp256k1 key
+const PKCS_PREFIX: &str = "3056301006072a8648ce3d020106052b8104000a034200";
+
+/// X.509 SubjectPublicKeyInfo DER prefix for a compressed secp256k1 key
+const PKCS_PREFIX_COMPRESSED: &str = "3036301006072a8648ce3d020106052b8104000a032200";
+
+/// Address format derived from a public key
+///
+/// The SDK uses `DagAddressScheme` by default. Implement this trait to
+/// target a metagraph with a different address format, then create key
+/// pairs with `generate_key_pair_with_scheme` or
+/// `key_pair_from_private_key_with_scheme`.
+///
+/// # Example
+/// ```
+/// use constellation_sdk::wallet::{generate_key_pair_with_scheme, AddressScheme};
+/// use sha2::{Digest, Sha256};
+///
+/// struct PrefixedScheme;
+///
+/// impl AddressScheme for PrefixedScheme {
+///     fn from_public_key(public_key: &str) -> String {
+///         let hash = Sha256::digest(public_key.as_bytes());
+///         format!("meta1{}", hex::encode(&hash[..20]))
+///     }
+///
+///     fn validate(address: &str) -> bool {
+///         address.strip_prefix("meta1").is_some_and(|body| {
+///             body.len() == 40 && body.bytes().all(|b| b.is_ascii_hexdigit())
+///         })
+///     }
+/// }
+///
+/// let key_pair = generate_key_pair_with_scheme::<PrefixedScheme>();
+/// assert!(PrefixedScheme::validate(&key_pair.address));
+/// ```
+pub trait AddressScheme {
+    /// Derive the address of a public key in hex format
+    fn from_public_key(public_key: &str) -> String;
+
+    /// Check whether an address is well-formed in this scheme
+    fn validate(address: &str) -> bool;
+}
+
+/// Constellation DAG addresses, the default `AddressScheme`
+///
+/// Derives addresses with `get_address` and validates them with
+/// `is_valid_dag_address`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DagAddressScheme;
+
+impl AddressScheme for DagAddressScheme {
+    fn from_public_key(public_key: &str) -> String {
+        get_address(public_key)
+    }
+
+    fn validate(address: &str) -> bool {
+        is_valid_dag_address(address)
+    }
+}
 
 /// Generate a new random key pair
 ///
@@ -22,12 +93,19 @@ const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmno
 /// println!("Public key: {}", key_pair.public_key);
 /// ```
 pub fn generate_key_pair() -> KeyPair {
-    let secp = Secp256k1::new();
+    generate_key_pair_with_scheme::<DagAddressScheme>()
+}
+
+/// Generate a new random key pair with an address in the given scheme
+///
+/// The scheme `S` derives `KeyPair::address`.
+pub fn generate_key_pair_with_scheme<S: AddressScheme>() -> KeyPair {
+    let secp = SECP256K1;
     let (secret_key, public_key) = secp.generate_keypair(&mut OsRng);
 
     let private_key_hex = hex::encode(secret_key.secret_bytes());
     let public_key_hex = hex::encode(public_key.serialize_uncompressed());
-    let address = get_address(&public_key_hex);
+    let address = S::from_public_key(&public_key_hex);
 
     KeyPair {
         private_key: private_key_hex,
@@ -36,6 +114,74 @@ pub fn generate_key_pair() -> KeyPair {
     }
 }
 
+/// Generate random key pairs until one's address satisfies `predicate`
+///
+/// Each attempt is an independent random key, so the expected number of
+/// attempts is the inverse of the fraction of addresses the predicate
+/// accepts. With the `parallel` feature, `generate_matching_parallel`
+/// spreads the search across threads.
+///
+/// # Arguments
+/// * `predicate` - Test applied to each candidate DAG address
+/// * `max_attempts` - Number of keys to try before giving up
+///
+/// # Returns
+/// The first matching key pair, or `SdkError::SearchExhausted`
+///
+/// # Example
+/// ```
+/// use constellation_sdk::wallet::generate_matching;
+///
+/// // About 1 in 9 addresses has parity digit 4
+/// let key_pair = generate_matching(|address| address.starts_with("DAG4"), 10_000).unwrap();
+/// assert!(key_pair.address.starts_with("DAG4"));
+/// ```
+pub fn generate_matching(predicate: impl Fn(&str) -> bool, max_attempts: u64) -> Result<KeyPair> {
+    (0..max_attempts)
+        .map(|_| generate_key_pair())
+        .find(|key_pair| predicate(&key_pair.address))
+        .ok_or(SdkError::SearchExhausted(max_attempts))
+}
+
+/// Search for a key pair matching `predicate` on all available cores
+///
+/// Like `generate_matching`, but `max_attempts` is shared across one worker
+/// thread per available core, and the search stops as soon as any worker
+/// finds a match.
+#[cfg(feature = "parallel")]
+pub fn generate_matching_parallel(
+    predicate: impl Fn(&str) -> bool + Sync,
+    max_attempts: u64,
+) -> Result<KeyPair> {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Mutex;
+
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let attempts = AtomicU64::new(0);
+    let found = Mutex::new(None);
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                while attempts.fetch_add(1, Ordering::Relaxed) < max_attempts {
+                    let key_pair = generate_key_pair();
+                    if predicate(&key_pair.address) {
+                        // Claim the remaining attempts so other workers stop
+                        attempts.store(max_attempts, Ordering::Relaxed);
+                        found.lock().unwrap().get_or_insert(key_pair);
+                        return;
+                    }
+                }
+            });
+        }
+    });
+
+    found
+        .into_inner()
+        .unwrap()
+        .ok_or(SdkError::SearchExhausted(max_attempts))
+}
+
 /// Derive a key pair from an existing private key
 ///
 /// # Arguments
@@ -50,22 +196,35 @@ pub fn generate_key_pair() -> KeyPair {
 /// assert_eq!(original.public_key, derived.public_key);
 /// ```
 pub fn key_pair_from_private_key(private_key: &str) -> Result<KeyPair> {
+    key_pair_from_private_key_with_scheme::<DagAddressScheme>(private_key)
+}
+
+/// Derive a key pair from an existing private key with an address in the
+/// given scheme
+///
+/// # Arguments
+/// * `private_key` - Private key in hex format (64 characters)
+///
+/// The scheme `S` derives `KeyPair::address`.
+pub fn key_pair_from_private_key_with_scheme<S: AddressScheme>(
+    private_key: &str,
+) -> Result<KeyPair> {
     if !is_valid_private_key(private_key) {
         return Err(SdkError::InvalidPrivateKey(
             "Invalid private key format".to_string(),
         ));
     }
 
-    let secp = Secp256k1::new();
+    let secp = SECP256K1;
     let private_key_bytes = hex::decode(private_key)?;
     let secret_key = SecretKey::from_slice(&private_key_bytes)?;
-    let public_key = PublicKey::from_secret_key(&secp, &secret_key);
+    let public_key = PublicKey::from_secret_key(secp, &secret_key);
 
     let public_key_hex = hex::encode(public_key.serialize_uncompressed());
-    let address = get_address(&public_key_hex);
+    let address = S::from_public_key(&public_key_hex);
 
     Ok(KeyPair {
-        private_key: private_key.to_string(),
+        private_key: private_key.to_ascii_lowercase(),
         public_key: public_key_hex,
         address,
     })
@@ -78,9 +237,9 @@ pub fn key_pair_from_private_key(private_key: &str) -> Result<KeyPair> {
 /// * `compressed` - If true, returns compressed public key (33 bytes)
 pub fn get_public_key_hex(private_key: &str, compressed: bool) -> Result<String> {
     let private_key_bytes = hex::decode(private_key)?;
-    let secp = Secp256k1::new();
+    let secp = SECP256K1;
     let secret_key = SecretKey::from_slice(&private_key_bytes)?;
-    let public_key = PublicKey::from_secret_key(&secp, &secret_key);
+    let public_key = PublicKey::from_secret_key(secp, &secret_key);
 
     if compressed {
         Ok(hex::encode(public_key.serialize()))
@@ -103,6 +262,66 @@ pub fn get_public_key_id(private_key: &str) -> Result<String> {
     Ok(normalize_public_key_to_id(&public_key))
 }
 
+/// Sanity-check key handling for a private key
+///
+/// Intended as a startup gate in security-sensitive deployments. Checks that:
+/// 1. The address derived from the uncompressed public key equals the one
+///    derived from the compressed key, and the compressed key decompresses
+///    to the uncompressed one
+/// 2. A signature made with the key verifies, both as regular data and as
+///    a DataUpdate, and its proof id is the key's public key id
+/// 3. The signature does not verify against different data
+///
+/// # Arguments
+/// * `private_key` - Private key in hex format
+///
+/// # Returns
+/// `Ok(())` if every invariant holds, otherwise
+/// `SdkError::SelfCheckFailed` naming the broken one
+///
+/// # Example
+/// ```
+/// use constellation_sdk::wallet::{generate_key_pair, self_check};
+///
+/// let key_pair = generate_key_pair();
+/// self_check(&key_pair.private_key).unwrap();
+/// assert!(self_check("not a key").is_err());
+/// ```
+pub fn self_check(private_key: &str) -> Result<()> {
+    use crate::sign::{sign, sign_data_update};
+    use crate::verify::verify_signature;
+
+    let fail = |msg: &str| Err(SdkError::SelfCheckFailed(msg.to_string()));
+    let key_pair = key_pair_from_private_key(private_key)?;
+
+    let compressed = hex::decode(get_public_key_hex(private_key, true)?)?;
+    let decompressed = PublicKey::from_slice(&compressed)
+        .map_err(|e| SdkError::SelfCheckFailed(format!("compressed key rejected: {e}")))?;
+    if hex::encode(decompressed.serialize_uncompressed()) != key_pair.public_key {
+        return fail("compressed public key decompresses to a different key");
+    }
+    if address_from_bytes(&compressed)? != key_pair.address {
+        return fail("compressed and uncompressed paths derive different addresses");
+    }
+
+    let probe = serde_json::json!({"selfCheck": key_pair.address});
+    let proof = sign(&probe, private_key)?;
+    if proof.id != get_public_key_id(private_key)? {
+        return fail("proof id does not match the public key");
+    }
+    if !verify_signature(&probe, &proof, false)? {
+        return fail("signature does not verify");
+    }
+    let update_proof = sign_data_update(&probe, private_key)?;
+    if !verify_signature(&probe, &update_proof, true)? {
+        return fail("DataUpdate signature does not verify");
+    }
+    if verify_signature(&serde_json::json!({"selfCheck": ""}), &proof, false)? {
+        return fail("signature verifies against different data");
+    }
+    Ok(())
+}
+
 /// Get DAG address from a public key
 ///
 /// Uses Constellation's address derivation:
@@ -117,9 +336,6 @@ pub fn get_public_key_id(private_key: &str) -> Result<String> {
 /// # Arguments
 /// * `public_key` - Public key in hex format (with or without 04 prefix)
 pub fn get_address(public_key: &str) -> String {
-    // PKCS prefix for X.509 DER encoding (secp256k1)
-    const PKCS_PREFIX: &str = "3056301006072a8648ce3d020106052b8104000a034200";
-
     // Normalize public key to include 04 prefix
     let normalized_key = normalize_public_key(public_key);
 
@@ -154,6 +370,158 @@ pub fn get_address(public_key: &str) -> String {
     format!("DAG{parity}{last36}")
 }
 
+/// Get the DAG address of a proof's signer
+///
+/// Unlike `get_address`, the proof id is parsed as a public key first, so
+/// compressed ids (66 characters) are decompressed before deriving the address.
+///
+/// # Arguments
+/// * `proof` - Signature proof whose id is a public key in hex format
+///
+/// # Returns
+/// DAG address, or an error if the id is not a valid public key
+///
+/// # Example
+/// ```
+/// use constellation_sdk::sign::sign;
+/// use constellation_sdk::wallet::{address_from_proof, generate_key_pair};
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let proof = sign(&json!({"id": "test"}), &key_pair.private_key).unwrap();
+/// assert_eq!(address_from_proof(&proof).unwrap(), key_pair.address);
+/// ```
+pub fn address_from_proof(proof: &SignatureProof) -> Result<String> {
+    address_from_bytes(&hex::decode(&proof.id)?)
+}
+
+/// Get the DAG address for a public key given as raw bytes
+///
+/// # Arguments
+/// * `public_key` - 64-byte uncompressed key without prefix, 65-byte
+///   uncompressed key with 04 prefix, or 33-byte compressed key
+///
+/// # Returns
+/// DAG address, or `SdkError::InvalidPublicKey` for any other length or a
+/// point that is not on the curve
+///
+/// # Example
+/// ```
+/// use constellation_sdk::wallet::{address_from_bytes, generate_key_pair};
+///
+/// let key_pair = generate_key_pair();
+/// let bytes = hex::decode(&key_pair.public_key).unwrap();
+/// assert_eq!(address_from_bytes(&bytes).unwrap(), key_pair.address);
+/// assert_eq!(address_from_bytes(&bytes[1..]).unwrap(), key_pair.address);
+/// assert!(address_from_bytes(&bytes[..10]).is_err());
+/// ```
+pub fn address_from_bytes(public_key: &[u8]) -> Result<String> {
+    let public_key = match public_key.len() {
+        64 => {
+            let mut prefixed = [0u8; 65];
+            prefixed[0] = 0x04;
+            prefixed[1..].copy_from_slice(public_key);
+            PublicKey::from_slice(&prefixed)
+        }
+        33 | 65 => PublicKey::from_slice(public_key),
+        len => {
+            return Err(SdkError::InvalidPublicKey(format!(
+                "expected 33, 64 or 65 bytes, got {len}"
+            )))
+        }
+    }
+    .map_err(|e| SdkError::InvalidPublicKey(e.to_string()))?;
+
+    Ok(get_address(&hex::encode(
+        public_key.serialize_uncompressed(),
+    )))
+}
+
+/// Get the EVM (Ethereum) address for a public key
+///
+/// The address is the last 20 bytes of the Keccak-256 hash of the
+/// uncompressed public key (without 04 prefix), with EIP-55 checksum casing.
+///
+/// # Arguments
+/// * `public_key` - Public key in hex format (compressed, or uncompressed with or without 04 prefix)
+///
+/// # Returns
+/// Checksummed address, e.g. "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+#[cfg(feature = "recovery")]
+pub fn get_evm_address(public_key: &str) -> Result<String> {
+    use crate::hash::keccak256;
+
+    let public_key_bytes = hex::decode(normalize_public_key(public_key))?;
+    let public_key = PublicKey::from_slice(&public_key_bytes)?;
+    let hash = keccak256(&public_key.serialize_uncompressed()[1..]);
+    let address = hex::encode(&hash[12..]);
+
+    // EIP-55: uppercase each letter whose nibble in keccak(address) is >= 8
+    let checksum = hex::encode(keccak256(address.as_bytes()));
+    let checksummed: String = address
+        .chars()
+        .zip(checksum.chars())
+        .map(|(c, h)| {
+            if c.is_ascii_alphabetic() && h.to_digit(16).unwrap_or(0) >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    Ok(format!("0x{checksummed}"))
+}
+
+/// Rewrite proof ids to one public key format
+///
+/// Only ids change: signatures cover the signing digest, not the id, so the
+/// proofs stay valid for `verify`. Every id is validated first; if any is not
+/// a valid public key, an error is returned and no proof is modified.
+/// Currency transactions expect `IdFormat::Uncompressed` ids.
+///
+/// # Arguments
+/// * `proofs` - Proofs to rewrite in place
+/// * `target` - Format for every id
+///
+/// # Returns
+/// `SdkError::InvalidPublicKey` naming the first invalid proof
+///
+/// # Example
+/// ```
+/// use constellation_sdk::sign::sign;
+/// use constellation_sdk::wallet::{generate_key_pair, normalize_proof_ids};
+/// use constellation_sdk::IdFormat;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let mut proofs = vec![sign(&json!({"id": "test"}), &key_pair.private_key).unwrap()];
+///
+/// normalize_proof_ids(&mut proofs, IdFormat::Prefixed).unwrap();
+/// assert_eq!(proofs[0].id, key_pair.public_key);
+/// ```
+pub fn normalize_proof_ids(proofs: &mut [SignatureProof], target: IdFormat) -> Result<()> {
+    let keys = proofs
+        .iter()
+        .enumerate()
+        .map(|(i, proof)| {
+            hex::decode(normalize_public_key(&proof.id))
+                .ok()
+                .and_then(|bytes| PublicKey::from_slice(&bytes).ok())
+                .ok_or_else(|| SdkError::InvalidPublicKey(format!("proof {i} has an invalid id")))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    for (proof, key) in proofs.iter_mut().zip(keys) {
+        proof.id = match target {
+            IdFormat::Uncompressed => hex::encode(&key.serialize_uncompressed()[1..]),
+            IdFormat::Prefixed => hex::encode(key.serialize_uncompressed()),
+            IdFormat::Compressed => hex::encode(key.serialize()),
+        };
+    }
+    Ok(())
+}
+
 /// Validate that a private key is correctly formatted
 ///
 /// # Arguments
@@ -184,14 +552,84 @@ pub fn is_valid_public_key(public_key: &str) -> bool {
 }
 
 /// Normalize public key to include 04 prefix
+///
+/// 128-character uncompressed ids are prefixed with `04`, and compressed keys
+/// (66 characters, `02`/`03` prefix) are decompressed to the 130-character
+/// uncompressed form. Prefixed uncompressed keys, and anything that is not a
+/// recognizable key (including compressed keys not on the curve), are
+/// returned unchanged so that parsing them downstream fails.
+///
+/// # Example
+/// ```
+/// use constellation_sdk::wallet::{generate_key_pair, get_public_key_hex, normalize_public_key};
+///
+/// let key_pair = generate_key_pair();
+/// let compressed = get_public_key_hex(&key_pair.private_key, true).unwrap();
+/// assert_eq!(normalize_public_key(&compressed), key_pair.public_key);
+/// assert_eq!(normalize_public_key(&key_pair.public_key[2..]), key_pair.public_key);
+/// ```
 pub fn normalize_public_key(public_key: &str) -> String {
-    if public_key.len() == 128 {
-        format!("04{public_key}")
-    } else {
-        public_key.to_string()
+    match public_key.len() {
+        128 => format!("04{public_key}"),
+        66 if public_key.starts_with("02") || public_key.starts_with("03") => {
+            hex::decode(public_key)
+                .ok()
+                .and_then(|bytes| PublicKey::from_slice(&bytes).ok())
+                .map(|key| hex::encode(key.serialize_uncompressed()))
+                .unwrap_or_else(|| public_key.to_string())
+        }
+        _ => public_key.to_string(),
     }
 }
 
+/// Canonical (uncompressed, 04-prefixed) hex form of a public key id
+///
+/// Lets ids in different formats for the same key compare equal; None if
+/// the id is not a valid public key.
+pub(crate) fn signer_key(id: &str) -> Option<String> {
+    let bytes = hex::decode(normalize_public_key(id)).ok()?;
+    let public_key = PublicKey::from_slice(&bytes).ok()?;
+    Some(hex::encode(public_key.serialize_uncompressed()))
+}
+
+/// Extract the public key from an X.509 SubjectPublicKeyInfo (SPKI)
+///
+/// The inverse of the DER encoding `get_address` hashes. Both uncompressed
+/// (`3056...034200 04||X||Y`) and compressed (`3036...032200 02/03||X`)
+/// secp256k1 SPKIs are accepted.
+///
+/// # Arguments
+/// * `der_hex` - DER-encoded SPKI in hex
+///
+/// # Returns
+/// Uncompressed public key hex (130 characters, 04 prefix)
+///
+/// # Example
+/// ```
+/// use constellation_sdk::wallet::{generate_key_pair, public_key_from_spki};
+///
+/// let key_pair = generate_key_pair();
+/// let spki = format!("3056301006072a8648ce3d020106052b8104000a034200{}", key_pair.public_key);
+/// assert_eq!(public_key_from_spki(&spki).unwrap(), key_pair.public_key);
+/// ```
+pub fn public_key_from_spki(der_hex: &str) -> Result<String> {
+    let der_hex = der_hex.trim().to_ascii_lowercase();
+    let key_hex = der_hex
+        .strip_prefix(PKCS_PREFIX)
+        .filter(|key| key.len() == 130)
+        .or_else(|| {
+            der_hex
+                .strip_prefix(PKCS_PREFIX_COMPRESSED)
+                .filter(|key| key.len() == 66)
+        })
+        .ok_or_else(|| {
+            SdkError::InvalidPublicKey("Not a secp256k1 SubjectPublicKeyInfo".to_string())
+        })?;
+
+    let public_key = PublicKey::from_slice(&hex::decode(key_hex)?)?;
+    Ok(hex::encode(public_key.serialize_uncompressed()))
+}
+
 /// Normalize public key to ID format (without 04 prefix)
 pub fn normalize_public_key_to_id(public_key: &str) -> String {
     if public_key.len() == 130 && public_key.starts_with("04") {
@@ -201,50 +639,317 @@ pub fn normalize_public_key_to_id(public_key: &str) -> String {
     }
 }
 
-/// Base58 encode bytes using Bitcoin/Constellation alphabet
-fn base58_encode(data: &[u8]) -> String {
-    if data.is_empty() {
-        return String::new();
-    }
-
-    // Count leading zeros
-    let leading_zeros = data.iter().take_while(|&&b| b == 0).count();
+/// Lazily derive the receive addresses of a BIP44 account
+///
+/// Yields the key pairs at `m/44'/1137'/account'/0/i` for `i = 0, 1, 2, ...`,
+/// deriving each one only when it is requested. Indices that BIP32 defines
+/// as invalid (probability below 2^-127 each) are skipped, so use
+/// `next_index` rather than counting items if the index matters.
+///
+/// # Gap limit
+///
+/// Wallet recovery should follow the BIP44 gap-limit convention: keep
+/// scanning until 20 consecutive addresses have no activity, then stop.
+/// Wallets only hand out addresses in order, so a run of 20 unused
+/// addresses means no later address has been used.
+///
+/// # Example
+/// ```
+/// use constellation_sdk::wallet::DerivationScanner;
+///
+/// let seed = [7u8; 32];
+/// let has_activity = |address: &str| address.is_empty(); // e.g. a balance query
+///
+/// const GAP_LIMIT: usize = 20;
+/// let mut used = Vec::new();
+/// let mut gap = 0;
+/// for key_pair in DerivationScanner::new(&seed, 0).unwrap() {
+///     if has_activity(&key_pair.address) {
+///         used.push(key_pair);
+///         gap = 0;
+///     } else {
+///         gap += 1;
+///         if gap == GAP_LIMIT {
+///             break;
+///         }
+///     }
+/// }
+/// assert!(used.is_empty());
+/// ```
+///
+/// The scanner holds the account's extended private key and zeroizes it
+/// when dropped.
+pub struct DerivationScanner {
+    chain: ExtendedKey,
+    next_index: u32,
+}
 
-    // Convert to big integer representation
-    let mut num: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
-    for &byte in data {
-        let mut carry = byte as u32;
-        for digit in num.iter_mut() {
-            carry += (*digit as u32) << 8;
-            *digit = (carry % 58) as u8;
-            carry /= 58;
+impl DerivationScanner {
+    /// Start scanning an account of the wallet with the given BIP32 seed
+    ///
+    /// # Arguments
+    /// * `seed` - BIP32 seed (16 to 64 bytes, e.g. from a BIP39 mnemonic)
+    /// * `account` - Account number (hardened in the path)
+    pub fn new(seed: &[u8], account: u32) -> Result<Self> {
+        if account >= HARDENED {
+            return Err(SdkError::InvalidPrivateKey(format!(
+                "Account must be below 2^31, got {}",
+                account
+            )));
         }
-        while carry > 0 {
-            num.push((carry % 58) as u8);
-            carry /= 58;
+
+        let path = [
+            44 | HARDENED,
+            DAG_COIN_TYPE | HARDENED,
+            account | HARDENED,
+            0,
+        ];
+        let mut chain = ExtendedKey::master(seed)?;
+        for index in path {
+            chain = chain.child(index).ok_or_else(|| {
+                SdkError::InvalidPrivateKey("Seed derives an invalid account key".to_string())
+            })?;
         }
+
+        Ok(Self {
+            chain,
+            next_index: 0,
+        })
     }
 
-    // Build result string
-    let mut result = String::with_capacity(leading_zeros + num.len());
+    /// Index of the address the next call to `next` will derive from
+    pub fn next_index(&self) -> u32 {
+        self.next_index
+    }
+}
 
-    // Add '1' for each leading zero byte
-    for _ in 0..leading_zeros {
-        result.push('1');
+impl Iterator for DerivationScanner {
+    type Item = KeyPair;
+
+    fn next(&mut self) -> Option<KeyPair> {
+        while self.next_index < HARDENED {
+            let index = self.next_index;
+            self.next_index += 1;
+            if let Some(child) = self.chain.child(index) {
+                return Some(key_pair_from_secret(&child.secret_key));
+            }
+        }
+        None
     }
+}
 
-    // Convert digits to characters
-    for &digit in num.iter().rev() {
-        result.push(BASE58_ALPHABET[digit as usize] as char);
+impl Zeroize for DerivationScanner {
+    fn zeroize(&mut self) {
+        self.chain.zeroize();
     }
+}
+
+impl ZeroizeOnDrop for DerivationScanner {}
+
+fn key_pair_from_secret(secret_key: &SecretKey) -> KeyPair {
+    let public_key = PublicKey::from_secret_key(SECP256K1, secret_key);
+    let public_key_hex = hex::encode(public_key.serialize_uncompressed());
+    let address = get_address(&public_key_hex);
 
-    result
+    KeyPair {
+        private_key: hex::encode(secret_key.secret_bytes()),
+        public_key: public_key_hex,
+        address,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_public_key_from_spki() {
+        let key_pair = generate_key_pair();
+        let public_key =
+            PublicKey::from_slice(&hex::decode(&key_pair.public_key).unwrap()).unwrap();
+
+        let uncompressed = format!("{PKCS_PREFIX}{}", key_pair.public_key);
+        assert_eq!(
+            public_key_from_spki(&uncompressed).unwrap(),
+            key_pair.public_key
+        );
+        assert_eq!(
+            public_key_from_spki(&uncompressed.to_uppercase()).unwrap(),
+            key_pair.public_key
+        );
+
+        let compressed = format!(
+            "{PKCS_PREFIX_COMPRESSED}{}",
+            hex::encode(public_key.serialize())
+        );
+        assert_eq!(
+            public_key_from_spki(&compressed).unwrap(),
+            key_pair.public_key
+        );
+
+        // Wrong prefix, truncated key, off-curve point
+        assert!(public_key_from_spki(&key_pair.public_key).is_err());
+        assert!(public_key_from_spki(&uncompressed[..uncompressed.len() - 2]).is_err());
+        assert!(public_key_from_spki(&format!("{PKCS_PREFIX}04{}", "00".repeat(64))).is_err());
+    }
+
+    #[test]
+    fn test_generate_matching() {
+        let key_pair = generate_matching(|address| address.ends_with('z'), 100_000).unwrap();
+        assert!(key_pair.address.ends_with('z'));
+        assert_eq!(
+            key_pair_from_private_key(&key_pair.private_key)
+                .unwrap()
+                .address,
+            key_pair.address
+        );
+
+        assert!(matches!(
+            generate_matching(|_| false, 10),
+            Err(SdkError::SearchExhausted(10))
+        ));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_generate_matching_parallel() {
+        let key_pair =
+            generate_matching_parallel(|address| address.starts_with("DAG0"), 100_000).unwrap();
+        assert!(key_pair.address.starts_with("DAG0"));
+
+        let tried = std::sync::atomic::AtomicU64::new(0);
+        let result = generate_matching_parallel(
+            |_| {
+                tried.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                false
+            },
+            50,
+        );
+        assert!(matches!(result, Err(SdkError::SearchExhausted(50))));
+        assert_eq!(tried.into_inner(), 50);
+    }
+
+    #[test]
+    fn test_derivation_scanner() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let mut scanner = DerivationScanner::new(&seed, 0).unwrap();
+        assert_eq!(scanner.next_index(), 0);
+
+        let first: Vec<KeyPair> = scanner.by_ref().take(3).collect();
+        assert_eq!(scanner.next_index(), 3);
+        assert_eq!(first[0].private_key.len(), 64);
+        assert!(first[0].address.starts_with("DAG"));
+        assert_ne!(first[0].address, first[1].address);
+
+        // Deterministic and account-specific
+        let again: Vec<KeyPair> = DerivationScanner::new(&seed, 0).unwrap().take(3).collect();
+        assert_eq!(
+            again.iter().map(|k| &k.address).collect::<Vec<_>>(),
+            first.iter().map(|k| &k.address).collect::<Vec<_>>()
+        );
+        let other = DerivationScanner::new(&seed, 1).unwrap().next().unwrap();
+        assert_ne!(other.address, first[0].address);
+
+        assert!(DerivationScanner::new(&seed[..8], 0).is_err());
+        assert!(DerivationScanner::new(&seed, HARDENED).is_err());
+    }
+
+    #[test]
+    fn test_normalize_proof_ids() {
+        use crate::sign::sign;
+        use crate::types::Signed;
+        use crate::verify::verify;
+        use serde_json::json;
+
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let data = json!({"id": "test"});
+        let mut signed = Signed {
+            value: data.clone(),
+            proofs: vec![
+                sign(&data, &key1.private_key).unwrap(),
+                sign(&data, &key2.private_key).unwrap(),
+            ],
+        };
+        let original = signed.proofs.clone();
+
+        for (format, len) in [
+            (IdFormat::Compressed, 66),
+            (IdFormat::Prefixed, 130),
+            (IdFormat::Uncompressed, 128),
+        ] {
+            normalize_proof_ids(&mut signed.proofs, format).unwrap();
+            assert!(signed.proofs.iter().all(|p| p.id.len() == len));
+            assert!(verify(&signed, false).is_valid);
+        }
+        assert_eq!(signed.proofs, original);
+
+        // One bad id leaves every proof untouched
+        signed.proofs[1].id = "00".repeat(64);
+        let before = signed.proofs.clone();
+        let err = normalize_proof_ids(&mut signed.proofs, IdFormat::Compressed).unwrap_err();
+        assert!(err.to_string().contains("proof 1"));
+        assert_eq!(signed.proofs, before);
+    }
+
+    #[test]
+    fn test_key_pair_normalizes_hex_case() {
+        let key_pair = generate_key_pair();
+        let upper = key_pair_from_private_key(&key_pair.private_key.to_uppercase()).unwrap();
+        assert_eq!(upper, key_pair);
+        assert_eq!(
+            upper.fingerprint().unwrap(),
+            key_pair.fingerprint().unwrap()
+        );
+        assert_eq!(key_pair.fingerprint().unwrap().len(), 16);
+
+        // Compressed and uppercase forms of the key have the same fingerprint
+        let compressed = KeyPair {
+            public_key: get_public_key_hex(&key_pair.private_key, true).unwrap(),
+            ..key_pair.clone()
+        };
+        assert_eq!(
+            compressed.fingerprint().unwrap(),
+            key_pair.fingerprint().unwrap()
+        );
+
+        let garbage = KeyPair {
+            public_key: "not a key".to_string(),
+            ..key_pair
+        };
+        assert!(matches!(
+            garbage.fingerprint(),
+            Err(SdkError::InvalidPublicKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_normalize_public_key_forms() {
+        // Find keys whose compressed forms cover both 02 and 03 prefixes
+        let mut seen = [false; 2];
+        while seen != [true, true] {
+            let key_pair = generate_key_pair();
+            let compressed = get_public_key_hex(&key_pair.private_key, true).unwrap();
+            seen[usize::from(compressed.starts_with("03"))] = true;
+
+            assert_eq!(normalize_public_key(&compressed), key_pair.public_key);
+            assert_eq!(
+                normalize_public_key(&key_pair.public_key),
+                key_pair.public_key
+            );
+            assert_eq!(
+                normalize_public_key(&key_pair.public_key[2..]),
+                key_pair.public_key
+            );
+            assert_eq!(get_address(&compressed), key_pair.address);
+        }
+
+        // Unrecognized input is passed through for downstream parsing to reject
+        let off_curve = format!("02{}", "00".repeat(32));
+        assert_eq!(normalize_public_key(&off_curve), off_curve);
+        assert_eq!(normalize_public_key("abcd"), "abcd");
+    }
+
     #[test]
     fn test_generate_key_pair() {
         let key_pair = generate_key_pair();
@@ -261,6 +966,104 @@ mod tests {
         assert_eq!(derived.address, key_pair.address);
     }
 
+    #[cfg(feature = "recovery")]
+    #[test]
+    fn test_get_evm_address() {
+        let key_pair = key_pair_from_private_key(&format!("{:064x}", 1)).unwrap();
+        assert_eq!(
+            get_evm_address(&key_pair.public_key).unwrap(),
+            "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+        );
+    }
+
+    #[test]
+    fn test_address_from_proof_handles_compressed_ids() {
+        let key_pair = generate_key_pair();
+        let compressed = get_public_key_hex(&key_pair.private_key, true).unwrap();
+        for id in [
+            key_pair.public_key[2..].to_string(),
+            key_pair.public_key.clone(),
+            compressed,
+        ] {
+            let proof = SignatureProof {
+                id,
+                signature: String::new(),
+            };
+            assert_eq!(address_from_proof(&proof).unwrap(), key_pair.address);
+        }
+
+        let bad = SignatureProof {
+            id: "zz".to_string(),
+            signature: String::new(),
+        };
+        assert!(address_from_proof(&bad).is_err());
+    }
+
+    #[test]
+    fn test_address_from_bytes_accepts_all_encodings() {
+        let key_pair = generate_key_pair();
+        let uncompressed = hex::decode(&key_pair.public_key).unwrap();
+        let compressed =
+            hex::decode(get_public_key_hex(&key_pair.private_key, true).unwrap()).unwrap();
+
+        assert_eq!(address_from_bytes(&uncompressed).unwrap(), key_pair.address);
+        assert_eq!(
+            address_from_bytes(&uncompressed[1..]).unwrap(),
+            key_pair.address
+        );
+        assert_eq!(address_from_bytes(&compressed).unwrap(), key_pair.address);
+
+        assert!(address_from_bytes(&[]).is_err());
+        assert!(address_from_bytes(&uncompressed[..63]).is_err());
+        assert!(address_from_bytes(&[0u8; 33]).is_err());
+    }
+
+    #[test]
+    fn test_self_check() {
+        for _ in 0..5 {
+            self_check(&generate_key_pair().private_key).unwrap();
+        }
+        assert!(matches!(
+            self_check(&"g".repeat(64)),
+            Err(SdkError::InvalidPrivateKey(_))
+        ));
+        // Well-formed hex, but not a valid scalar
+        assert!(self_check(&"0".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn test_address_schemes() {
+        struct HexScheme;
+
+        impl AddressScheme for HexScheme {
+            fn from_public_key(public_key: &str) -> String {
+                format!("hex1{}", &public_key[public_key.len() - 40..])
+            }
+
+            fn validate(address: &str) -> bool {
+                address.starts_with("hex1") && address.len() == 44
+            }
+        }
+
+        let dag = generate_key_pair();
+        assert_eq!(
+            DagAddressScheme::from_public_key(&dag.public_key),
+            dag.address
+        );
+        assert!(DagAddressScheme::validate(&dag.address));
+
+        let key_pair =
+            key_pair_from_private_key_with_scheme::<HexScheme>(&dag.private_key).unwrap();
+        assert_eq!(key_pair.public_key, dag.public_key);
+        assert_eq!(key_pair.address, format!("hex1{}", &dag.public_key[90..]));
+        assert!(HexScheme::validate(&key_pair.address));
+        assert!(!DagAddressScheme::validate(&key_pair.address));
+
+        let generated = generate_key_pair_with_scheme::<HexScheme>();
+        assert!(HexScheme::validate(&generated.address));
+        assert!(!HexScheme::validate(&dag.address));
+    }
+
     #[test]
     fn test_is_valid_private_key() {
         assert!(is_valid_private_key(&"a".repeat(64)));
@@ -0,0 +1,296 @@
+//! BIP32 Hierarchical Deterministic Keys
+//!
+//! Private-key derivation as specified by BIP32: the master key comes from
+//! HMAC-SHA512 of the seed under the key `"Bitcoin seed"`, and each child
+//! from HMAC-SHA512 of the parent's public key (normal) or private key
+//! (hardened) under the parent's chain code. Public-key derivation and
+//! extended-key serialization are not needed by the SDK and are omitted.
+//!
+//! Extended keys hold secret material, so they cannot be cloned and are
+//! zeroized when dropped, as are the intermediate buffers built here.
+
+use secp256k1::{PublicKey, Scalar, SecretKey, SECP256K1};
+use sha2::{Digest, Sha512};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::types::{Result, SdkError};
+
+/// Offset of hardened child indices
+pub(crate) const HARDENED: u32 = 0x8000_0000;
+
+/// Extended private key: a secret key and its chain code
+pub(crate) struct ExtendedKey {
+    pub(crate) secret_key: SecretKey,
+    chain_code: [u8; 32],
+}
+
+impl ExtendedKey {
+    /// Derive the master key from a seed of 16 to 64 bytes
+    pub(crate) fn master(seed: &[u8]) -> Result<Self> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(SdkError::InvalidPrivateKey(format!(
+                "Seed must be 16 to 64 bytes, got {}",
+                seed.len()
+            )));
+        }
+        Self::from_hmac(&hmac_sha512(b"Bitcoin seed", seed)).ok_or_else(|| {
+            SdkError::InvalidPrivateKey("Seed derives an invalid master key".to_string())
+        })
+    }
+
+    fn from_hmac(i: &[u8; 64]) -> Option<Self> {
+        let secret_key = SecretKey::from_slice(&i[..32]).ok()?;
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(&i[32..]);
+        Some(Self {
+            secret_key,
+            chain_code,
+        })
+    }
+
+    /// Derive a child key; None for the indices BIP32 defines as invalid
+    pub(crate) fn child(&self, index: u32) -> Option<Self> {
+        let mut data = Zeroizing::new([0u8; 37]);
+        if index >= HARDENED {
+            data[1..33].copy_from_slice(&Zeroizing::new(self.secret_key.secret_bytes())[..]);
+        } else {
+            let public_key = PublicKey::from_secret_key(SECP256K1, &self.secret_key);
+            data[..33].copy_from_slice(&public_key.serialize());
+        }
+        data[33..].copy_from_slice(&index.to_be_bytes());
+
+        let i = Zeroizing::new(hmac_sha512(&self.chain_code, &data[..]));
+        let mut tweak = Zeroizing::new([0u8; 32]);
+        tweak.copy_from_slice(&i[..32]);
+        let tweak = Scalar::from_be_bytes(*tweak).ok()?;
+        let secret_key = self.secret_key.add_tweak(&tweak).ok()?;
+
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(&i[32..]);
+        Some(Self {
+            secret_key,
+            chain_code,
+        })
+    }
+}
+
+impl Zeroize for ExtendedKey {
+    fn zeroize(&mut self) {
+        self.secret_key.non_secure_erase();
+        self.chain_code.zeroize();
+    }
+}
+
+impl Drop for ExtendedKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for ExtendedKey {}
+
+/// HMAC-SHA512 (RFC 2104)
+pub(crate) fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    const BLOCK_SIZE: usize = 128;
+    let mut block = Zeroizing::new([0u8; BLOCK_SIZE]);
+    if key.len() > BLOCK_SIZE {
+        block[..64].copy_from_slice(&Sha512::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut pad = Zeroizing::new(block.map(|b| b ^ 0x36));
+    let mut inner = Sha512::new();
+    inner.update(&pad[..]);
+    inner.update(data);
+    *pad = block.map(|b| b ^ 0x5c);
+    let mut outer = Sha512::new();
+    outer.update(&pad[..]);
+    outer.update(inner.finalize());
+    outer.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Derive along a BIP32 test vector, checking the key and chain code of
+    /// every step; the first step is the master key
+    fn check_chain(seed: &str, steps: &[(Option<u32>, &str, &str)]) {
+        let mut key = ExtendedKey::master(&hex::decode(seed).unwrap()).unwrap();
+        for (index, secret_key, chain_code) in steps {
+            if let Some(index) = index {
+                key = key.child(*index).unwrap();
+            }
+            assert_eq!(hex::encode(key.secret_key.secret_bytes()), *secret_key);
+            assert_eq!(hex::encode(key.chain_code), *chain_code);
+        }
+    }
+
+    #[test]
+    fn test_vector_1() {
+        check_chain(
+            "000102030405060708090a0b0c0d0e0f",
+            &[
+                // m
+                (
+                    None,
+                    "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+                    "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+                ),
+                // m/0H
+                (
+                    Some(HARDENED),
+                    "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+                    "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+                ),
+                // m/0H/1
+                (
+                    Some(1),
+                    "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+                    "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+                ),
+                // m/0H/1/2H
+                (
+                    Some(HARDENED | 2),
+                    "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+                    "04466b9cc8e161e966409ca52986c584f07e9dc81f735db683c3ff6ec7b1503f",
+                ),
+                // m/0H/1/2H/2
+                (
+                    Some(2),
+                    "0f479245fb19a38a1954c5c7c0ebab2f9bdfd96a17563ef28a6a4b1a2a764ef4",
+                    "cfb71883f01676f587d023cc53a35bc7f88f724b1f8c2892ac1275ac822a3edd",
+                ),
+                // m/0H/1/2H/2/1000000000
+                (
+                    Some(1000000000),
+                    "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+                    "c783e67b921d2beb8f6b389cc646d7263b4145701dadd2161548a8b078e65e9e",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_vector_2() {
+        check_chain(
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+            &[
+                // m
+                (
+                    None,
+                    "4b03d6fc340455b363f51020ad3ecca4f0850280cf436c70c727923f6db46c3e",
+                    "60499f801b896d83179a4374aeb7822aaeaceaa0db1f85ee3e904c4defbd9689",
+                ),
+                // m/0
+                (
+                    Some(0),
+                    "abe74a98f6c7eabee0428f53798f0ab8aa1bd37873999041703c742f15ac7e1e",
+                    "f0909affaa7ee7abe5dd4e100598d4dc53cd709d5a5c2cac40e7412f232f7c9c",
+                ),
+                // m/0/2147483647H
+                (
+                    Some(HARDENED | 2147483647),
+                    "877c779ad9687164e9c2f4f0f4ff0340814392330693ce95a58fe18fd52e6e93",
+                    "be17a268474a6bb9c61e1d720cf6215e2a88c5406c4aee7b38547f585c9a37d9",
+                ),
+                // m/0/2147483647H/1
+                (
+                    Some(1),
+                    "704addf544a06e5ee4bea37098463c23613da32020d604506da8c0518e1da4b7",
+                    "f366f48f1ea9f2d1d3fe958c95ca84ea18e4c4ddb9366c336c927eb246fb38cb",
+                ),
+                // m/0/2147483647H/1/2147483646H
+                (
+                    Some(HARDENED | 2147483646),
+                    "f1c7c871a54a804afe328b4c83a1c33b8e5ff48f5087273f04efa83b247d6a2d",
+                    "637807030d55d01f9a0cb3a7839515d796bd07706386a6eddf06cc29a65a0e29",
+                ),
+                // m/0/2147483647H/1/2147483646H/2
+                (
+                    Some(2),
+                    "bb7d39bdb83ecf58f2fd82b6d918341cbef428661ef01ab97c28a4842125ac23",
+                    "9452b549be8cea3ecb7a84bec10dcfd94afe4d129ebfd3b3cb58eedf394ed271",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_vector_3() {
+        check_chain(
+            "4b381541583be4423346c643850da4b320e46a87ae3d2a4e6da11eba819cd4acba45d239319ac14f863b8d5ab5a0d0c64d2e8a1e7d1457df2e5a3c51c73235be",
+            &[
+                // m
+                (
+                    None,
+                    "00ddb80b067e0d4993197fe10f2657a844a384589847602d56f0c629c81aae32",
+                    "01d28a3e53cffa419ec122c968b3259e16b65076495494d97cae10bbfec3c36f",
+                ),
+                // m/0H
+                (
+                    Some(HARDENED),
+                    "491f7a2eebc7b57028e0d3faa0acda02e75c33b03c48fb288c41e2ea44e1daef",
+                    "e5fea12a97b927fc9dc3d2cb0d1ea1cf50aa5a1fdc1f933e8906bb38df3377bd",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_master_rejects_seed_length() {
+        assert!(ExtendedKey::master(&[0u8; 15]).is_err());
+        assert!(ExtendedKey::master(&[0u8; 65]).is_err());
+        assert!(ExtendedKey::master(&[0u8; 16]).is_ok());
+    }
+
+    #[test]
+    fn test_zeroize() {
+        let mut key = ExtendedKey::master(&[7u8; 32]).unwrap();
+        let secret = key.secret_key.secret_bytes();
+        key.zeroize();
+        assert_eq!(key.chain_code, [0u8; 32]);
+        assert_ne!(key.secret_key.secret_bytes(), secret);
+    }
+
+    /// RFC 4231 test cases 1-4, 6 and 7 (case 5 tests truncated output)
+    #[test]
+    fn test_hmac_sha512_rfc4231() {
+        let cases: [(Vec<u8>, &[u8], &str); 6] = [
+            (
+                vec![0x0b; 20],
+                b"Hi There",
+                "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+            ),
+            (
+                b"Jefe".to_vec(),
+                b"what do ya want for nothing?",
+                "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+            ),
+            (
+                vec![0xaa; 20],
+                &[0xdd; 50],
+                "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb",
+            ),
+            (
+                (1..=25).collect(),
+                &[0xcd; 50],
+                "b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3dba91ca5c11aa25eb4d679275cc5788063a5f19741120c4f2de2adebeb10a298dd",
+            ),
+            (
+                vec![0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First",
+                "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+            ),
+            (
+                vec![0xaa; 131],
+                b"This is a test using a larger than block-size key and a larger than block-size data. The key needs to be hashed before being used by the HMAC algorithm.",
+                "e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58",
+            ),
+        ];
+        for (key, data, expected) in cases {
+            assert_eq!(hex::encode(hmac_sha512(&key, data)), expected);
+        }
+    }
+}
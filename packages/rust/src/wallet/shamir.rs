@@ -0,0 +1,318 @@
+//! Shamir Secret Sharing
+//!
+//! Split a private key into `n` shares so that any `threshold` of them
+//! reconstruct it and fewer reveal nothing about it. Each byte of the key is
+//! shared independently with a random polynomial over GF(256) (the AES field,
+//! reduction polynomial `x^8 + x^4 + x^3 + x + 1`), evaluated at the share
+//! index.
+//!
+//! Reconstruct the key only to sign, then drop it: secret bytes and
+//! polynomial coefficients are zeroized here, and `Share` zeroizes its value
+//! when dropped, but the returned key string is the caller's to wipe (e.g.
+//! with `zeroize::Zeroize`).
+
+use std::fmt;
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+use secp256k1::SecretKey;
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, Zeroizing};
+
+use super::is_valid_private_key;
+use crate::types::{Result, SdkError};
+
+/// Length of a private key in bytes
+const KEY_LEN: usize = 32;
+
+/// One share of a split private key
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Share {
+    /// Evaluation point of the share (1 to 255); shares of one key have
+    /// distinct indexes
+    pub index: u8,
+    /// Share value in hex format (64 characters)
+    pub value: String,
+}
+
+impl fmt::Debug for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("index", &self.index)
+            .field("value", &"<redacted>")
+            .finish()
+    }
+}
+
+impl Drop for Share {
+    fn drop(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+/// Split a private key into shares
+///
+/// # Arguments
+/// * `private_key` - Private key in hex format (64 characters)
+/// * `threshold` - Number of shares needed to reconstruct the key
+/// * `shares` - Number of shares to create, at least `threshold`
+///
+/// # Returns
+/// Shares with indexes 1 to `shares`, or `SdkError::SecretSharing` if
+/// `threshold` is 0 or greater than `shares`
+///
+/// # Example
+/// ```
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use constellation_sdk::wallet::shamir::{combine, split};
+///
+/// let key_pair = generate_key_pair();
+/// let shares = split(&key_pair.private_key, 3, 5).unwrap();
+///
+/// // Any three shares reconstruct the key
+/// let key = combine(&[shares[4].clone(), shares[0].clone(), shares[2].clone()]).unwrap();
+/// assert_eq!(key, key_pair.private_key);
+/// ```
+pub fn split(private_key: &str, threshold: u8, shares: u8) -> Result<Vec<Share>> {
+    if threshold == 0 || threshold > shares {
+        return Err(SdkError::SecretSharing(format!(
+            "threshold must be between 1 and the number of shares ({shares}), got {threshold}"
+        )));
+    }
+    if !is_valid_private_key(private_key) {
+        return Err(SdkError::InvalidPrivateKey(
+            "Invalid private key format".to_string(),
+        ));
+    }
+    let secret = Zeroizing::new(hex::decode(private_key)?);
+    SecretKey::from_slice(&secret)?;
+
+    // Coefficients of x^1 .. x^(threshold - 1) for each secret byte
+    let degree = usize::from(threshold - 1);
+    let mut coefficients = Zeroizing::new(vec![0u8; degree * KEY_LEN]);
+    OsRng.fill_bytes(&mut coefficients);
+
+    Ok((1..=shares)
+        .map(|x| {
+            let mut value = Zeroizing::new(vec![0u8; KEY_LEN]);
+            for (i, byte) in value.iter_mut().enumerate() {
+                // Horner's rule from the highest coefficient down
+                let mut y = 0;
+                for d in (0..degree).rev() {
+                    y = gf_mul(y, x) ^ coefficients[d * KEY_LEN + i];
+                }
+                *byte = gf_mul(y, x) ^ secret[i];
+            }
+            Share {
+                index: x,
+                value: hex::encode(&*value),
+            }
+        })
+        .collect())
+}
+
+/// Reconstruct a private key from shares
+///
+/// Shares can be given in any order. Fewer than the threshold give a wrong
+/// key rather than an error, since the threshold isn't recorded in the
+/// shares; check the key's address before using it.
+///
+/// # Arguments
+/// * `shares` - At least `threshold` shares of the same key
+///
+/// # Returns
+/// Private key in hex format, or `SdkError::SecretSharing` if there are no
+/// shares, an index is 0 or repeated, or a value is not 32 bytes of hex
+pub fn combine(shares: &[Share]) -> Result<String> {
+    if shares.is_empty() {
+        return Err(SdkError::SecretSharing("no shares given".to_string()));
+    }
+    for (i, share) in shares.iter().enumerate() {
+        if share.index == 0 {
+            return Err(SdkError::SecretSharing(
+                "share index 0 is invalid".to_string(),
+            ));
+        }
+        if shares[..i].iter().any(|other| other.index == share.index) {
+            return Err(SdkError::SecretSharing(format!(
+                "share index {} is repeated",
+                share.index
+            )));
+        }
+    }
+    let values = shares
+        .iter()
+        .map(|share| match hex::decode(&share.value) {
+            Ok(value) if value.len() == KEY_LEN => Ok(Zeroizing::new(value)),
+            _ => Err(SdkError::SecretSharing(format!(
+                "share {} is not {KEY_LEN} bytes of hex",
+                share.index
+            ))),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Lagrange interpolation at x = 0; subtraction is XOR in GF(256)
+    let mut secret = Zeroizing::new(vec![0u8; KEY_LEN]);
+    for (j, share) in shares.iter().enumerate() {
+        let basis = shares
+            .iter()
+            .filter(|other| other.index != share.index)
+            .fold(1, |acc, other| {
+                gf_mul(acc, gf_div(other.index, other.index ^ share.index))
+            });
+        for (byte, y) in secret.iter_mut().zip(values[j].iter()) {
+            *byte ^= gf_mul(*y, basis);
+        }
+    }
+
+    SecretKey::from_slice(&secret).map_err(|_| {
+        SdkError::SecretSharing("shares do not reconstruct a valid private key".to_string())
+    })?;
+    Ok(hex::encode(&*secret))
+}
+
+/// Multiply in GF(256) without data-dependent branches
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    for _ in 0..8 {
+        product ^= a & (b & 1).wrapping_neg();
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (carry & 0x1b);
+        b >>= 1;
+    }
+    product
+}
+
+/// Divide in GF(256); `b` must be non-zero
+fn gf_div(a: u8, b: u8) -> u8 {
+    // b^254 = b^-1, since the multiplicative group has order 255
+    let mut inverse = 1;
+    let mut power = b;
+    for bit in 0..8 {
+        if (254 >> bit) & 1 == 1 {
+            inverse = gf_mul(inverse, power);
+        }
+        power = gf_mul(power, power);
+    }
+    gf_mul(a, inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::generate_key_pair;
+
+    #[test]
+    fn test_gf_arithmetic() {
+        // FIPS 197 examples
+        assert_eq!(gf_mul(0x57, 0x83), 0xc1);
+        assert_eq!(gf_mul(0x57, 0x13), 0xfe);
+        assert_eq!(gf_mul(0x53, 0xca), 0x01);
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(gf_div(1, a), a), 1);
+        }
+    }
+
+    #[test]
+    fn test_combine_known_polynomial() {
+        // f(x) = secret + 0x02 x: share x holds secret ^ (0x02 * x)
+        let secret = [0x11u8; KEY_LEN];
+        let share = |x: u8| Share {
+            index: x,
+            value: hex::encode(secret.map(|s| s ^ gf_mul(0x02, x))),
+        };
+        assert_eq!(
+            combine(&[share(3), share(200)]).unwrap(),
+            hex::encode(secret)
+        );
+    }
+
+    #[test]
+    fn test_any_threshold_subset_reconstructs() {
+        let key_pair = generate_key_pair();
+        let shares = split(&key_pair.private_key, 3, 5).unwrap();
+        assert_eq!(
+            shares.iter().map(|s| s.index).collect::<Vec<_>>(),
+            [1, 2, 3, 4, 5]
+        );
+
+        for a in 0..5 {
+            for b in a + 1..5 {
+                for c in b + 1..5 {
+                    let subset = [shares[c].clone(), shares[a].clone(), shares[b].clone()];
+                    assert_eq!(combine(&subset).unwrap(), key_pair.private_key);
+                }
+            }
+        }
+        assert_eq!(combine(&shares).unwrap(), key_pair.private_key);
+
+        // Below the threshold the key is not recovered
+        let partial = combine(&shares[..2]);
+        assert!(partial.map_or(true, |key| key != key_pair.private_key));
+    }
+
+    #[test]
+    fn test_threshold_edge_cases() {
+        let key_pair = generate_key_pair();
+
+        let single = split(&key_pair.private_key, 1, 3).unwrap();
+        assert!(single.iter().all(|s| s.value == key_pair.private_key));
+
+        let all = split(&key_pair.private_key, 4, 4).unwrap();
+        assert_eq!(combine(&all).unwrap(), key_pair.private_key);
+
+        let max = split(&key_pair.private_key, 2, 255).unwrap();
+        assert_eq!(
+            combine(&[max[254].clone(), max[0].clone()]).unwrap(),
+            key_pair.private_key
+        );
+
+        for (threshold, shares) in [(0, 3), (4, 3)] {
+            assert!(matches!(
+                split(&key_pair.private_key, threshold, shares),
+                Err(SdkError::SecretSharing(_))
+            ));
+        }
+        assert!(matches!(
+            split(&"g".repeat(64), 2, 3),
+            Err(SdkError::InvalidPrivateKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_combine_rejects_malformed_shares() {
+        let key_pair = generate_key_pair();
+        let shares = split(&key_pair.private_key, 2, 3).unwrap();
+
+        let repeated = [shares[0].clone(), shares[0].clone()];
+        let zero = [
+            Share {
+                index: 0,
+                value: shares[0].value.clone(),
+            },
+            shares[1].clone(),
+        ];
+        let short = [
+            Share {
+                index: 1,
+                value: "ab".to_string(),
+            },
+            shares[1].clone(),
+        ];
+        for bad in [&repeated[..], &zero, &short, &[]] {
+            assert!(matches!(combine(bad), Err(SdkError::SecretSharing(_))));
+        }
+    }
+
+    #[test]
+    fn test_share_debug_is_redacted() {
+        let key_pair = generate_key_pair();
+        let share = &split(&key_pair.private_key, 2, 2).unwrap()[0];
+        let debug = format!("{share:?}");
+        assert!(!debug.contains(&share.value));
+        assert!(debug.contains("redacted"));
+
+        let json = serde_json::to_string(share).unwrap();
+        assert_eq!(&serde_json::from_str::<Share>(&json).unwrap(), share);
+    }
+}
@@ -0,0 +1,231 @@
+//! Stealth Addresses
+//!
+//! One-time payment addresses that only the recipient can find and spend.
+//! The sender combines the recipient's public key `B` with a fresh
+//! ephemeral key `r` and pays to a DAG address no one else can link to `B`:
+//!
+//! ```text
+//! S     = r·B                       (ECDH; the recipient computes b·R)
+//! tweak = SHA-256(S || 0x00)
+//! tag   = SHA-256(S || 0x01)[..4]
+//! P     = B + tweak·G               (payment public key)
+//! p     = b + tweak                 (payment private key, recipient only)
+//! ```
+//!
+//! `S` is the compressed shared point. The sender publishes the ephemeral
+//! public key `R = r·G` and the tag with the payment. The recipient runs
+//! `scan` on each announcement: a tag mismatch rules the payment out after
+//! one ECDH, and a match yields the payment address. `recover` then derives
+//! the key that spends it.
+//!
+//! The same recipient key both scans and spends, so scanning can't be
+//! delegated without also handing over spending. Use a fresh ephemeral key
+//! for every payment; reusing one pays the same recipient at the same
+//! address again.
+
+use secp256k1::{PublicKey, Scalar, SecretKey, SECP256K1};
+use sha2::{Digest, Sha256};
+
+use super::{get_address, is_valid_private_key, key_pair_from_secret, normalize_public_key};
+use crate::types::{KeyPair, Result, SdkError};
+
+/// Length of the scanning tag in bytes
+const TAG_LEN: usize = 4;
+
+/// Derive a one-time payment address for a recipient
+///
+/// # Arguments
+/// * `recipient_public_key` - Recipient's public key in hex (compressed,
+///   uncompressed, or without the 04 prefix)
+/// * `ephemeral_private_key` - Fresh private key for this payment only
+///
+/// # Returns
+/// `(address, tag)`: the DAG address to pay and the scanning tag in hex, to
+/// publish with the ephemeral public key (`get_public_key_hex`)
+///
+/// # Example
+/// ```
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use constellation_sdk::wallet::stealth::{derive_payment_address, recover, scan};
+///
+/// let recipient = generate_key_pair();
+/// let ephemeral = generate_key_pair();
+///
+/// // Sender: pay to a one-time address, publish the ephemeral public key and tag
+/// let (address, tag) = derive_payment_address(&recipient.public_key, &ephemeral.private_key).unwrap();
+/// assert_ne!(address, recipient.address);
+///
+/// // Recipient: find the payment and derive the key that spends it
+/// let found = scan(&recipient.private_key, &ephemeral.public_key, &tag).unwrap();
+/// assert_eq!(found.as_deref(), Some(address.as_str()));
+/// assert_eq!(recover(&recipient.private_key, &ephemeral.public_key).unwrap().address, address);
+/// ```
+pub fn derive_payment_address(
+    recipient_public_key: &str,
+    ephemeral_private_key: &str,
+) -> Result<(String, String)> {
+    let recipient = parse_public_key(recipient_public_key)?;
+    let ephemeral = parse_private_key(ephemeral_private_key)?;
+
+    let secret = shared_secret(&recipient, &ephemeral)?;
+    let payment_key = recipient.add_exp_tweak(SECP256K1, &tweak(&secret)?)?;
+
+    Ok((
+        get_address(&hex::encode(payment_key.serialize_uncompressed())),
+        tag(&secret),
+    ))
+}
+
+/// Check whether an announced payment is for this recipient
+///
+/// # Arguments
+/// * `recipient_private_key` - Recipient's private key in hex format
+/// * `ephemeral_public_key` - Ephemeral public key published by the sender
+/// * `tag_hex` - Scanning tag published by the sender
+///
+/// # Returns
+/// The payment address if the tag matches, otherwise None. Tags are 4
+/// bytes, so about one in 2^32 unrelated payments also matches; confirm the
+/// address holds funds before relying on it.
+pub fn scan(
+    recipient_private_key: &str,
+    ephemeral_public_key: &str,
+    tag_hex: &str,
+) -> Result<Option<String>> {
+    let recipient = parse_private_key(recipient_private_key)?;
+    let ephemeral = parse_public_key(ephemeral_public_key)?;
+
+    let secret = shared_secret(&ephemeral, &recipient)?;
+    if !tag(&secret).eq_ignore_ascii_case(tag_hex) {
+        return Ok(None);
+    }
+
+    let payment_key = PublicKey::from_secret_key(SECP256K1, &recipient)
+        .add_exp_tweak(SECP256K1, &tweak(&secret)?)?;
+    Ok(Some(get_address(&hex::encode(
+        payment_key.serialize_uncompressed(),
+    ))))
+}
+
+/// Derive the key pair that spends a payment to a one-time address
+///
+/// # Arguments
+/// * `recipient_private_key` - Recipient's private key in hex format
+/// * `ephemeral_public_key` - Ephemeral public key published by the sender
+///
+/// # Returns
+/// Key pair whose address is the payment address
+pub fn recover(recipient_private_key: &str, ephemeral_public_key: &str) -> Result<KeyPair> {
+    let recipient = parse_private_key(recipient_private_key)?;
+    let ephemeral = parse_public_key(ephemeral_public_key)?;
+
+    let secret = shared_secret(&ephemeral, &recipient)?;
+    let payment_key = recipient.add_tweak(&tweak(&secret)?)?;
+
+    Ok(key_pair_from_secret(&payment_key))
+}
+
+/// Compressed ECDH point shared by sender and recipient
+fn shared_secret(public_key: &PublicKey, private_key: &SecretKey) -> Result<[u8; 33]> {
+    let point = public_key.mul_tweak(SECP256K1, &Scalar::from(*private_key))?;
+    Ok(point.serialize())
+}
+
+fn tweak(secret: &[u8; 33]) -> Result<Scalar> {
+    Scalar::from_be_bytes(derive(secret, 0x00))
+        .map_err(|_| SdkError::CryptoError("stealth tweak out of range".to_string()))
+}
+
+fn tag(secret: &[u8; 33]) -> String {
+    hex::encode(&derive(secret, 0x01)[..TAG_LEN])
+}
+
+fn derive(secret: &[u8; 33], label: u8) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(secret);
+    hasher.update([label]);
+    hasher.finalize().into()
+}
+
+fn parse_private_key(private_key: &str) -> Result<SecretKey> {
+    if !is_valid_private_key(private_key) {
+        return Err(SdkError::InvalidPrivateKey(
+            "Invalid private key format".to_string(),
+        ));
+    }
+    Ok(SecretKey::from_slice(&hex::decode(private_key)?)?)
+}
+
+fn parse_public_key(public_key: &str) -> Result<PublicKey> {
+    let bytes = hex::decode(normalize_public_key(public_key))?;
+    PublicKey::from_slice(&bytes).map_err(|e| SdkError::InvalidPublicKey(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sign::sign;
+    use crate::verify::verify_signature;
+    use crate::wallet::{generate_key_pair, get_public_key_hex};
+    use serde_json::json;
+
+    #[test]
+    fn test_payment_round_trip() {
+        let recipient = generate_key_pair();
+        let ephemeral = generate_key_pair();
+        let (address, tag) =
+            derive_payment_address(&recipient.public_key, &ephemeral.private_key).unwrap();
+        assert_eq!(tag.len(), 2 * TAG_LEN);
+
+        // Compressed keys work on both sides
+        let compressed = get_public_key_hex(&recipient.private_key, true).unwrap();
+        let ephemeral_compressed = get_public_key_hex(&ephemeral.private_key, true).unwrap();
+        assert_eq!(
+            derive_payment_address(&compressed, &ephemeral.private_key).unwrap(),
+            (address.clone(), tag.clone())
+        );
+        assert_eq!(
+            scan(&recipient.private_key, &ephemeral_compressed, &tag).unwrap(),
+            Some(address.clone())
+        );
+
+        let spender = recover(&recipient.private_key, &ephemeral.public_key).unwrap();
+        assert_eq!(spender.address, address);
+        let data = json!({"spend": 1});
+        let proof = sign(&data, &spender.private_key).unwrap();
+        assert!(verify_signature(&data, &proof, false).unwrap());
+    }
+
+    #[test]
+    fn test_payments_are_unlinkable() {
+        let recipient = generate_key_pair();
+        let (first, first_tag) =
+            derive_payment_address(&recipient.public_key, &generate_key_pair().private_key)
+                .unwrap();
+        let (second, second_tag) =
+            derive_payment_address(&recipient.public_key, &generate_key_pair().private_key)
+                .unwrap();
+
+        assert_ne!(first, second);
+        assert_ne!(first_tag, second_tag);
+        assert_ne!(first, recipient.address);
+    }
+
+    #[test]
+    fn test_scan_rejects_other_payments() {
+        let recipient = generate_key_pair();
+        let other = generate_key_pair();
+        let ephemeral = generate_key_pair();
+        let (_, tag) = derive_payment_address(&other.public_key, &ephemeral.private_key).unwrap();
+
+        assert_eq!(
+            scan(&recipient.private_key, &ephemeral.public_key, &tag).unwrap(),
+            None
+        );
+        assert!(matches!(
+            scan(&"g".repeat(64), &ephemeral.public_key, &tag),
+            Err(SdkError::InvalidPrivateKey(_))
+        ));
+        assert!(derive_payment_address(&"a".repeat(10), &ephemeral.private_key).is_err());
+    }
+}
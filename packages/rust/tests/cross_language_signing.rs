@@ -0,0 +1,114 @@
+//! Cross-language signature production tests
+//!
+//! `cross_language.rs` checks that signatures from every SDK verify. This
+//! locks the other direction: signing the shared vectors' data must keep
+//! producing the same bytes, which catches drift in the digest or in the
+//! RFC 6979 nonce derivation that verification alone cannot see.
+//!
+//! The shared vectors come from randomized-nonce signers and carry no
+//! private key, so the expected bytes are pinned from this SDK rather than
+//! reproduced from another one.
+
+use constellation_sdk::{
+    get_public_key_id, sign, sign_data_update, sign_hash, sign_transaction_hash, verify_hash,
+};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Signing key for the pinned signatures (the currency vectors' key)
+const TEST_PRIVATE_KEY: &str = "1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+
+/// Signatures of each shared vector's data under `TEST_PRIVATE_KEY`
+///
+/// Recorded from this SDK's own RFC 6979 (libsecp256k1) output, keyed by the
+/// vector data's `id`. They pin current behaviour against regressions; they
+/// are not an independent reference, and any intentional change to signing
+/// must update them.
+const PINNED_SIGNATURES: &[(&str, &str)] = &[
+    ("python-test-data-001", "304402206e1113ac7ce946c657c834c7c51ad533be1131e9d54137145cf102382eeb28fd0220597fed4f21ef97640eaa11c68bda748ea72fdf000b94a862a834b703244861b3"),
+    ("python-test-update-001", "3045022100e7f2be1620750bbe85428eb4b86a15e6379574618ea7c1ba916c554e2e58d54c02201413443999667be3bb14b3ea4afc2b297cd21fa46a9a27c8db388d92b360a83e"),
+    ("python-test-data-002", "3045022100f2e36ea83e3150c4f85e07a97c198462871a1543dd89da65d8f02167c421f0f9022077622b972fcddfe517271be918dd4df9157ff2dcf892ddfbdf1c9176d5297e7f"),
+    ("python-test-update-002", "3045022100fe3172453fe46b9fede4a991e27832de94987cdfc454a76692d4e03bb5f4e22702202141fda8a5c4bd2ea7ae361b7e9356b6f0134a2316c43284eb6b0dc1ef40c00c"),
+    ("javascript-test-data-001", "3045022100c4eea0fd3be606fdaeffc20c5b3592f2f7242bc811a97aeb481d4186a9ce139902200c0c8c441839dd2e3378bf81419bbc6b26d5a480a8ba2048d323b250ae76f0a9"),
+    ("javascript-test-update-001", "3045022100fb1982817d464151f90717be2e456802492df72672addfa0ed60de8e8f3c352302207f32b1f37c561e13cc5d4f75b03df5d0667774fddc9970334e36df7a8e51b56b"),
+    ("javascript-test-data-002", "3045022100c56db90f7a5d325c2692e90efe932fa4f324e6f86e89500ca502ad2ca27280fc02205f4fa06f5b3bc03e2423c19d9b8986295b4f228371ed2bc6965a48d0b9df17b5"),
+    ("javascript-test-update-002", "3045022100816545b6360ad8c117e514868dc09ced77e5e4f86514ab21cfea54e2d16dbf3b02203df986e9ce8803a5b81172389d5ed2202c7f3d11bd3d529c2a601223f10e00c3"),
+    ("rust-test-data-001", "3045022100ce230b2cbeda2e67c93f29cc8006a2af647ddb960476d9d4a7bbecd497a63c82022001ad4f62e29b0aca24f8fcce8df5f1ac5a03d6464256c1d4f2752f6ea545d6ae"),
+    ("rust-test-update-001", "3045022100acc6fd8b39f3f7bbb9f9794b29a45f554e6824bae3ab2ae9a11b1c72e774131d022058352bb4b03f35e3d86ee0dcc68ffa82eec4b512375d5164472713e834202868"),
+    ("rust-test-data-002", "304402200feeba36f7f9aeb9f61d9203016d2c7615baabeb35b180ab2264ca2f835ecd9f02204c4a574975d1e81868721e7b6c729228d3c888d3d23fe3f8302148f4a7c21993"),
+    ("rust-test-update-002", "304402204c13d4f6eca10292501874ecd1a3bbb6dd795ca7eca12c6997673e9ac4f0100602204a240f0dfca75e9251f1993729929b44fc8d742e3bb6a22b351c89e1f803cc00"),
+    ("go-test-data-001", "3045022100eca9f790132863f616f1bffe14bd99d46e6dd910110ac431d72bda54169c28ed02203fe0a2bda91117830603bb0e79181525b7c795e5ec74f15e63bd06e4e0ac46b2"),
+    ("go-test-update-001", "3045022100d482ed1f82c801d3f59dadb08f5f27d9d2235e7690b63bfd888eb57c035f7a23022057333a38c9da32f12bfedfe160688afbd33c021cfb1e0a7f11042201b8bb2c26"),
+    ("go-test-data-002", "3044022004b191d68327cb9dc77cb697fefeb415d0eb899fa984fff5a2ca12f7b9001f7402203acc0fa249dd51b8064a2ed7964a56b0730df35242de4f0a511bf0ff960776dc"),
+    ("go-test-update-002", "3044022003ff081e175544793228b705cac55a3501955a0840a2388a9aec45f75dd888ed02201b538ecd9ddf985b11c91e097ab50088e996a8629317feb0eb911c5acb18d6e4"),
+];
+
+#[derive(Debug, Deserialize)]
+struct TestVector {
+    #[serde(rename = "type")]
+    test_type: String,
+    data: serde_json::Value,
+    sha256_hash_hex: String,
+}
+
+fn load_test_vectors() -> Vec<TestVector> {
+    let vectors_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("shared")
+        .join("test_vectors.json");
+
+    let content = fs::read_to_string(&vectors_path)
+        .unwrap_or_else(|_| panic!("Failed to read test vectors from {vectors_path:?}"));
+
+    serde_json::from_str(&content).expect("Failed to parse test vectors")
+}
+
+fn sign_vector(vector: &TestVector, private_key: &str) -> String {
+    let proof = if vector.test_type == "TestDataUpdate" {
+        sign_data_update(&vector.data, private_key)
+    } else {
+        sign(&vector.data, private_key)
+    };
+    proof.unwrap().signature
+}
+
+#[test]
+fn produces_pinned_signatures_for_all_vectors() {
+    let vectors = load_test_vectors();
+    assert_eq!(vectors.len(), PINNED_SIGNATURES.len());
+    let public_key_id = get_public_key_id(TEST_PRIVATE_KEY).unwrap();
+
+    for vector in &vectors {
+        let id = vector.data["id"].as_str().unwrap();
+        let expected = PINNED_SIGNATURES
+            .iter()
+            .find(|(pinned_id, _)| *pinned_id == id)
+            .unwrap_or_else(|| panic!("No pinned signature for vector {id}"))
+            .1;
+
+        let signature = sign_vector(vector, TEST_PRIVATE_KEY);
+        assert_eq!(signature, expected, "Signature drift for vector {id}");
+
+        // The hash-level entry point agrees with the data-level one
+        let from_hash = sign_hash(&vector.sha256_hash_hex, TEST_PRIVATE_KEY).unwrap();
+        assert_eq!(from_hash, expected, "sign_hash drift for vector {id}");
+        assert!(verify_hash(&vector.sha256_hash_hex, &signature, &public_key_id).unwrap());
+    }
+}
+
+#[test]
+fn produces_pinned_currency_transaction_signature() {
+    // basicTransaction from currency_transaction_vectors.json. Its reference
+    // signature came from a randomized signer, so it differs from this one;
+    // both verify.
+    let transaction_hash = "5b7e930be16d49adaf75ee5e5c63ac27f61a4a47058ab54ff10e9095f3bf6409";
+    let proof = sign_transaction_hash(transaction_hash, TEST_PRIVATE_KEY).unwrap();
+    assert_eq!(
+        proof.signature,
+        "3044022044b3e73975bdb74a511fb3c915e5df9f85b8ce7aed92d209a740c264ebc5a8990220151c53bbb82d95dc7bf7605c64add687035125cd1ceb7259724ee2083601f83c"
+    );
+}
@@ -1,11 +1,21 @@
 //! Tests for currency transaction functionality
 
 use constellation_sdk::{
-    create_currency_transaction, create_currency_transaction_batch, encode_currency_transaction,
-    generate_key_pair, get_transaction_reference, hash_currency_transaction, is_valid_dag_address,
-    sign_currency_transaction, token_to_units, units_to_token, verify_currency_transaction,
-    SignatureProof, TransactionReference, TransferParams, TOKEN_DECIMALS,
+    addresses_equal, build_fanout, check_encoding_compatibility, create_currency_transaction,
+    create_currency_transaction_batch, create_currency_transaction_exact,
+    create_currency_transaction_safe, create_currency_transaction_with_nonce,
+    create_delegated_transaction, decode_encoded_string, deterministic_salt,
+    encode_currency_transaction, encoded_size, generate_key_pair, get_transaction_reference,
+    hash_currency_transaction, hash_with_reference, is_valid_dag_address,
+    key_pair_from_private_key, minimum_transfer, preflight, sign_currency_transaction,
+    sign_transaction_hash, token_to_units, truncation_loss, units_to_token, verify_chain,
+    verify_currency_transaction, verify_currency_transaction_with_options, verify_multisig,
+    verify_transaction_hash, CurrencyTransaction, InvalidReason, SdkError, SignatureProof,
+    TokenAmount, TransactionReference, TransactionVersion, TransferParams, VerifyOptions,
+    TOKEN_DECIMALS, TOKEN_DECIMAL_PLACES,
 };
+use secp256k1::constants::CURVE_ORDER;
+use secp256k1::ecdsa::Signature;
 
 #[cfg(test)]
 mod utility_functions {
@@ -25,11 +35,56 @@ mod utility_functions {
         assert_eq!(units_to_token(100000000), 1.0);
     }
 
+    #[test]
+    fn test_truncation_loss_reports_floored_precision() {
+        assert_eq!(truncation_loss(100.5), 0.0);
+        assert_eq!(truncation_loss(0.00000001), 0.0);
+        assert!((truncation_loss(0.123456789) - 9e-9).abs() < 1e-12);
+        // 0.29 * 1e8 floors to 28999999
+        assert_eq!(token_to_units(0.29), 28_999_999);
+        assert!((truncation_loss(0.29) - 1e-8).abs() < 1e-12);
+    }
+
     #[test]
     fn test_token_decimals_constant() {
         assert_eq!(TOKEN_DECIMALS, 1e-8);
     }
 
+    #[test]
+    fn test_minimum_transfer_follows_decimals() {
+        assert_eq!(minimum_transfer(TOKEN_DECIMAL_PLACES), TOKEN_DECIMALS);
+        assert_eq!(minimum_transfer(6), 1e-6);
+        assert_eq!(format!("{:e}", minimum_transfer(6)), "1e-6");
+        assert_eq!(minimum_transfer(0), 1.0);
+    }
+
+    #[test]
+    fn test_addresses_equal_trims_but_preserves_case() {
+        let address = generate_key_pair().address;
+        let other = generate_key_pair().address;
+
+        assert!(addresses_equal(&address, &address));
+        assert!(addresses_equal(&format!("  {address}\t"), &address));
+        assert!(!addresses_equal(&address, &other));
+
+        // Flipping the case of a base58 letter yields a different address
+        let (i, c) = address
+            .char_indices()
+            .skip(4)
+            // Skip letters whose other case is not in the base58 alphabet
+            .find(|&(_, c)| c.is_ascii_alphabetic() && !"iloL".contains(c))
+            .unwrap();
+        let flipped_char = if c.is_ascii_lowercase() {
+            c.to_ascii_uppercase()
+        } else {
+            c.to_ascii_lowercase()
+        };
+        let flipped = format!("{}{}{}", &address[..i], flipped_char, &address[i + 1..]);
+        assert!(!addresses_equal(&address, &flipped));
+        assert!(!addresses_equal("", ""));
+        assert!(!addresses_equal("DAG", "DAG"));
+    }
+
     #[test]
     fn test_is_valid_dag_address_validates_addresses() {
         let key_pair = generate_key_pair();
@@ -40,6 +95,67 @@ mod utility_functions {
     }
 }
 
+#[cfg(test)]
+mod token_amount {
+    use super::*;
+
+    #[test]
+    fn test_constructors_and_conversions() {
+        assert_eq!(TokenAmount::from_tokens(100.5).units(), 10050000000);
+        assert_eq!(TokenAmount::from_units(10050000000).to_tokens(), 100.5);
+        assert_eq!(TokenAmount::from(1.0), TokenAmount::from_units(100000000));
+        assert_eq!(TokenAmount::default(), TokenAmount::ZERO);
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let one = TokenAmount::from_units(1);
+        assert_eq!(one.checked_add(one), Some(TokenAmount::from_units(2)));
+        assert_eq!(
+            TokenAmount::ZERO.checked_sub(one),
+            Some(TokenAmount::from_units(-1))
+        );
+        assert_eq!(one.checked_mul(5), Some(TokenAmount::from_units(5)));
+        assert_eq!(TokenAmount::from_units(i64::MAX).checked_add(one), None);
+        assert_eq!(TokenAmount::from_units(i64::MIN).checked_sub(one), None);
+        assert_eq!(TokenAmount::from_units(i64::MAX).checked_mul(2), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(TokenAmount::from_units(10050000000).to_string(), "100.5");
+        assert_eq!(TokenAmount::from_units(100000000).to_string(), "1");
+        assert_eq!(TokenAmount::from_units(1).to_string(), "0.00000001");
+        assert_eq!(TokenAmount::from_units(-150000000).to_string(), "-1.5");
+    }
+
+    #[test]
+    fn test_transfer_params_with_token_amount_is_exact() {
+        let key_pair = generate_key_pair();
+        let key_pair2 = generate_key_pair();
+        let last_ref = TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 0,
+        };
+
+        // 0.29 tokens cannot be represented exactly as f64; units are exact
+        let tx = create_currency_transaction(
+            TransferParams {
+                destination: key_pair2.address.clone(),
+                amount: TokenAmount::from_units(29_000_000),
+                fee: TokenAmount::from_units(1),
+            },
+            &key_pair.private_key,
+            last_ref,
+        )
+        .unwrap();
+
+        assert_eq!(tx.value.amount, 29_000_000);
+        assert_eq!(tx.value.fee, 1);
+        assert!(verify_currency_transaction(&tx).is_valid);
+    }
+}
+
 #[cfg(test)]
 mod transaction_creation {
     use super::*;
@@ -178,6 +294,329 @@ mod transaction_creation {
     }
 }
 
+#[cfg(test)]
+mod safe_transaction_creation {
+    use super::*;
+
+    #[test]
+    fn test_exact_creation_rejects_sub_unit_precision() {
+        let key_pair = generate_key_pair();
+        let destination = generate_key_pair().address;
+        let last_ref = || TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 0,
+        };
+
+        let tx = create_currency_transaction_exact(
+            params(&destination, 0.29, 0.00000001),
+            &key_pair.private_key,
+            last_ref(),
+        )
+        .unwrap();
+        assert_eq!(tx.value.amount, 29_000_000);
+        assert_eq!(tx.value.fee, 1);
+
+        for (amount, fee) in [(0.123456789, 0.0), (1.0, 1e-9), (f64::NAN, 0.0)] {
+            assert!(matches!(
+                create_currency_transaction_exact(
+                    params(&destination, amount, fee),
+                    &key_pair.private_key,
+                    last_ref()
+                ),
+                Err(SdkError::InvalidAmount(_))
+            ));
+        }
+    }
+
+    fn params(destination: &str, amount: f64, fee: f64) -> TransferParams {
+        TransferParams {
+            destination: destination.to_string(),
+            amount,
+            fee,
+        }
+    }
+
+    #[test]
+    fn test_fee_equal_to_amount_is_allowed() {
+        let key_pair = generate_key_pair();
+        let key_pair2 = generate_key_pair();
+        let last_ref = TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 0,
+        };
+
+        let tx = create_currency_transaction_safe(
+            params(&key_pair2.address, 1.0, 1.0),
+            &key_pair.private_key,
+            last_ref,
+        )
+        .unwrap();
+        assert_eq!(tx.value.amount, tx.value.fee);
+    }
+
+    #[test]
+    fn test_fee_exceeding_amount_is_rejected() {
+        let key_pair = generate_key_pair();
+        let key_pair2 = generate_key_pair();
+        let last_ref = TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 0,
+        };
+
+        let result = create_currency_transaction_safe(
+            TransferParams {
+                destination: key_pair2.address.clone(),
+                amount: TokenAmount::from_units(100),
+                fee: TokenAmount::from_units(101),
+            },
+            &key_pair.private_key,
+            last_ref.clone(),
+        );
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("exceeds transfer amount"));
+
+        // Without safe mode the same transfer is accepted
+        assert!(create_currency_transaction(
+            params(&key_pair2.address, 0.000001, 0.00000101),
+            &key_pair.private_key,
+            last_ref,
+        )
+        .is_ok());
+    }
+}
+
+#[cfg(test)]
+mod deterministic_salts {
+    use super::*;
+
+    fn transfer(destination: &str) -> TransferParams {
+        TransferParams {
+            destination: destination.to_string(),
+            amount: 10.0,
+            fee: 0.0,
+        }
+    }
+
+    fn last_ref() -> TransactionReference {
+        TransactionReference {
+            hash: "b".repeat(64),
+            ordinal: 7,
+        }
+    }
+
+    #[test]
+    fn test_same_nonce_gives_same_hash() {
+        let key_pair = generate_key_pair();
+        let destination = generate_key_pair().address;
+
+        let tx1 = create_currency_transaction_with_nonce(
+            transfer(&destination),
+            &key_pair.private_key,
+            last_ref(),
+            1,
+        )
+        .unwrap();
+        let tx2 = create_currency_transaction_with_nonce(
+            transfer(&destination),
+            &key_pair.private_key,
+            last_ref(),
+            1,
+        )
+        .unwrap();
+        let tx3 = create_currency_transaction_with_nonce(
+            transfer(&destination),
+            &key_pair.private_key,
+            last_ref(),
+            2,
+        )
+        .unwrap();
+
+        assert_eq!(tx1.value.salt, tx2.value.salt);
+        assert_eq!(
+            hash_currency_transaction(&tx1).unwrap(),
+            hash_currency_transaction(&tx2).unwrap()
+        );
+        assert_ne!(tx1.value.salt, tx3.value.salt);
+        assert!(verify_currency_transaction(&tx1).is_valid);
+    }
+
+    #[test]
+    fn test_salt_ignores_existing_salt_and_respects_floor() {
+        let key_pair = generate_key_pair();
+        let tx = create_currency_transaction(
+            transfer(&generate_key_pair().address),
+            &key_pair.private_key,
+            last_ref(),
+        )
+        .unwrap();
+
+        let mut value = tx.value.clone();
+        let salt = deterministic_salt(&value, 0);
+        value.salt = "1".to_string();
+        assert_eq!(deterministic_salt(&value, 0), salt);
+        assert!(salt >= (1u64 << 53) - (1u64 << 48));
+
+        value.fee += 1;
+        assert_ne!(deterministic_salt(&value, 0), salt);
+    }
+}
+
+#[cfg(test)]
+mod preflight_checks {
+    use super::*;
+
+    fn valid_transaction() -> constellation_sdk::CurrencyTransaction {
+        create_currency_transaction(
+            TransferParams {
+                destination: generate_key_pair().address,
+                amount: 5.0,
+                fee: 0.1,
+            },
+            &generate_key_pair().private_key,
+            TransactionReference {
+                hash: "c".repeat(64),
+                ordinal: 4,
+            },
+        )
+        .unwrap()
+    }
+
+    fn failures(tx: &constellation_sdk::CurrencyTransaction) -> Vec<String> {
+        match preflight(tx) {
+            Err(constellation_sdk::SdkError::PreflightFailed(failures)) => failures,
+            other => panic!("expected PreflightFailed, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_created_transactions_pass() {
+        let tx = valid_transaction();
+        assert!(preflight(&tx).is_ok());
+
+        // Co-signed transactions pass too
+        let cosigned = sign_currency_transaction(&tx, &generate_key_pair().private_key).unwrap();
+        assert!(preflight(&cosigned).is_ok());
+    }
+
+    #[test]
+    fn test_reports_every_failed_rule() {
+        let mut tx = valid_transaction();
+        tx.value.destination = "DAGinvalid".to_string();
+        tx.value.amount = 0;
+        tx.value.fee = -1;
+        tx.value.parent.hash = "xyz".to_string();
+        tx.value.salt = "12345".to_string();
+
+        let failures = failures(&tx);
+        assert_eq!(failures.len(), 5, "{failures:?}");
+        assert!(failures[0].contains("destination"));
+        assert!(failures[4].contains("salt"));
+    }
+
+    #[test]
+    fn test_rejects_salts_that_cannot_be_encoded() {
+        let mut tx = valid_transaction();
+        tx.value.salt = format!("+{}", tx.value.salt);
+
+        assert_eq!(
+            failures(&tx),
+            vec![format!("salt `{}` is not a decimal integer", tx.value.salt)]
+        );
+        assert!(hash_currency_transaction(&tx).is_err());
+    }
+
+    #[test]
+    fn test_checks_proof_structure() {
+        let mut tx = valid_transaction();
+        let proof = tx.proofs[0].clone();
+
+        tx.proofs.clear();
+        assert_eq!(failures(&tx), vec!["transaction has no proofs"]);
+
+        tx.proofs.push(SignatureProof {
+            id: format!("04{}", proof.id),
+            signature: "3000".to_string(),
+        });
+        let failures_found = failures(&tx);
+        assert!(failures_found[0].contains("128-character"));
+        assert!(failures_found[1].contains("proof 0"));
+        assert!(failures_found[2].contains("source"));
+
+        // A well-formed proof from someone other than the source
+        let other = valid_transaction();
+        tx.proofs = other.proofs;
+        assert_eq!(failures(&tx), vec!["no proof is from the source address"]);
+    }
+}
+
+#[cfg(test)]
+mod delegated_transactions {
+    use super::*;
+
+    #[test]
+    fn test_encodes_owner_and_signs_with_delegate() {
+        let owner = generate_key_pair();
+        let delegate = generate_key_pair();
+        let destination = generate_key_pair();
+
+        let tx = create_delegated_transaction(
+            &owner.address,
+            TransferParams {
+                destination: destination.address.clone(),
+                amount: 3.0,
+                fee: 0.0,
+            },
+            &delegate.private_key,
+            TransactionReference {
+                hash: "d".repeat(64),
+                ordinal: 9,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(tx.value.source, owner.address);
+        assert_eq!(tx.proofs[0].id, delegate.public_key[2..]);
+        assert!(verify_currency_transaction(&tx).is_valid);
+        assert!(verify_multisig(&tx, 1, &[&tx.proofs[0].id]).is_valid);
+        assert!(preflight(&tx)
+            .unwrap_err()
+            .to_string()
+            .contains("no proof is from the source address"));
+    }
+
+    #[test]
+    fn test_rejects_invalid_source() {
+        let delegate = generate_key_pair();
+        let destination = generate_key_pair();
+        let params = || TransferParams {
+            destination: destination.address.clone(),
+            amount: 1.0,
+            fee: 0.0,
+        };
+        let last_ref = TransactionReference {
+            hash: "d".repeat(64),
+            ordinal: 0,
+        };
+
+        assert!(create_delegated_transaction(
+            "DAGnotanaddress",
+            params(),
+            &delegate.private_key,
+            last_ref.clone()
+        )
+        .is_err());
+        assert!(create_delegated_transaction(
+            &destination.address,
+            params(),
+            &delegate.private_key,
+            last_ref
+        )
+        .is_err());
+    }
+}
+
 #[cfg(test)]
 mod batch_transactions {
     use super::*;
@@ -225,6 +664,118 @@ mod batch_transactions {
         assert_eq!(txns[1].value.parent.ordinal, 6);
         assert_eq!(txns[2].value.parent.ordinal, 7);
     }
+
+    #[test]
+    fn test_verify_chain_reports_break_index() {
+        let key_pair = generate_key_pair();
+        let transfers = (1..=4)
+            .map(|i| TransferParams {
+                destination: generate_key_pair().address,
+                amount: i as f64,
+                fee: 0.0,
+            })
+            .collect();
+        let last_ref = TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 5,
+        };
+        let txns =
+            create_currency_transaction_batch(transfers, &key_pair.private_key, last_ref).unwrap();
+
+        verify_chain(&txns).unwrap();
+        verify_chain(&txns[1..]).unwrap();
+        verify_chain(&txns[..1]).unwrap();
+        verify_chain(&[]).unwrap();
+
+        let break_index = |txns: &[CurrencyTransaction]| match verify_chain(txns) {
+            Err(SdkError::ChainBroken { index, .. }) => index,
+            other => panic!("expected ChainBroken, got {other:?}"),
+        };
+
+        // Dropping a link breaks the hash reference
+        let gap = [txns[0].clone(), txns[2].clone(), txns[3].clone()];
+        assert_eq!(break_index(&gap), 1);
+
+        // Right hash, wrong ordinal
+        let mut skipped = txns.clone();
+        skipped[3].value.parent.ordinal += 1;
+        assert_eq!(break_index(&skipped), 3);
+
+        // A link from another source
+        let other = generate_key_pair();
+        let mut foreign = txns.clone();
+        foreign[2] = create_currency_transaction(
+            TransferParams {
+                destination: key_pair.address.clone(),
+                amount: 1.0,
+                fee: 0.0,
+            },
+            &other.private_key,
+            txns[2].value.parent.clone(),
+        )
+        .unwrap();
+        assert_eq!(break_index(&foreign), 2);
+    }
+
+    #[test]
+    fn test_build_fanout_chains_transfers() {
+        let source = generate_key_pair();
+        let destinations: Vec<(String, f64)> = (1..=3)
+            .map(|i| (generate_key_pair().address, i as f64))
+            .collect();
+        let last_ref = TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 5,
+        };
+
+        let txns = build_fanout(&source.private_key, &destinations, 0.5, last_ref).unwrap();
+
+        assert_eq!(txns.len(), 3);
+        for (i, tx) in txns.iter().enumerate() {
+            assert_eq!(tx.value.source, source.address);
+            assert_eq!(tx.value.destination, destinations[i].0);
+            assert_eq!(tx.value.amount, token_to_units(destinations[i].1));
+            assert_eq!(tx.value.fee, 50000000);
+            assert_eq!(tx.value.parent.ordinal, 5 + i as i64);
+            assert!(verify_currency_transaction(tx).is_valid);
+        }
+        assert_eq!(
+            txns[1].value.parent.hash,
+            hash_currency_transaction(&txns[0]).unwrap().value
+        );
+    }
+
+    #[test]
+    fn test_build_fanout_fails_atomically() {
+        let source = generate_key_pair();
+        let last_ref = || TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 0,
+        };
+        let good = (generate_key_pair().address, 1.0);
+
+        let bad_address = vec![good.clone(), ("DAGinvalid".to_string(), 1.0)];
+        let err = build_fanout(&source.private_key, &bad_address, 0.0, last_ref()).unwrap_err();
+        assert!(matches!(err, SdkError::InvalidAddress(_)));
+        assert!(err.to_string().contains("index 1"));
+
+        let to_self = vec![good.clone(), (source.address.clone(), 1.0)];
+        assert!(matches!(
+            build_fanout(&source.private_key, &to_self, 0.0, last_ref()),
+            Err(SdkError::InvalidAddress(_))
+        ));
+
+        let zero_amount = vec![good.clone(), (generate_key_pair().address, 0.0)];
+        assert!(matches!(
+            build_fanout(&source.private_key, &zero_amount, 0.0, last_ref()),
+            Err(SdkError::InvalidAmount(_))
+        ));
+
+        assert!(matches!(
+            build_fanout(&source.private_key, &[good], -1.0, last_ref()),
+            Err(SdkError::InvalidAmount(_))
+        ));
+    }
 }
 
 #[cfg(test)]
@@ -289,36 +840,154 @@ mod transaction_verification {
         assert!(!result.is_valid);
         assert_eq!(result.valid_proofs.len(), 0);
         assert_eq!(result.invalid_proofs.len(), 1);
+        assert!(matches!(
+            result.invalid_reasons[0],
+            InvalidReason::Malformed(_)
+        ));
     }
-}
-
-#[cfg(test)]
-mod multi_signature_support {
-    use super::*;
 
     #[test]
-    fn test_sign_currency_transaction_adds_additional_signature() {
-        let key_pair1 = generate_key_pair();
+    fn test_verify_currency_transaction_off_curve_id() {
+        let key_pair = generate_key_pair();
         let key_pair2 = generate_key_pair();
-        let recipient = generate_key_pair();
         let last_ref = TransactionReference {
             hash: "a".repeat(64),
             ordinal: 0,
         };
 
-        // Create transaction with first signature
-        let tx = create_currency_transaction(
+        let mut tx = create_currency_transaction(
             TransferParams {
-                destination: recipient.address.clone(),
-                amount: 100.0,
+                destination: key_pair2.address,
+                amount: 1.0,
                 fee: 0.0,
             },
-            &key_pair1.private_key,
+            &key_pair.private_key,
             last_ref,
         )
         .unwrap();
+        tx.proofs[0].id = "00".repeat(64);
 
-        assert_eq!(tx.proofs.len(), 1);
+        let result = verify_currency_transaction(&tx);
+        assert!(!result.is_valid);
+        assert!(matches!(
+            result.invalid_reasons[0],
+            InvalidReason::MalformedPublicKey(_)
+        ));
+    }
+
+    #[test]
+    fn test_verify_currency_transaction_high_s_rejection_is_opt_in() {
+        let key_pair = generate_key_pair();
+        let key_pair2 = generate_key_pair();
+        let last_ref = TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 0,
+        };
+
+        let mut tx = create_currency_transaction(
+            TransferParams {
+                destination: key_pair2.address.clone(),
+                amount: 100.0,
+                fee: 0.0,
+            },
+            &key_pair.private_key,
+            last_ref,
+        )
+        .unwrap();
+
+        // Replace s with its high-S twin (n - s)
+        let der = hex::decode(&tx.proofs[0].signature).unwrap();
+        let mut compact = Signature::from_der(&der).unwrap().serialize_compact();
+        let mut borrow = 0i16;
+        for i in (0..32).rev() {
+            let diff = CURVE_ORDER[i] as i16 - compact[32 + i] as i16 - borrow;
+            borrow = i16::from(diff < 0);
+            compact[32 + i] = (diff + 256 * borrow) as u8;
+        }
+        tx.proofs[0].signature =
+            hex::encode(Signature::from_compact(&compact).unwrap().serialize_der());
+
+        assert!(verify_currency_transaction(&tx).is_valid);
+
+        let strict = VerifyOptions {
+            reject_high_s: true,
+            ..Default::default()
+        };
+        let result = verify_currency_transaction_with_options(&tx, &strict);
+        assert!(!result.is_valid);
+        assert_eq!(result.invalid_reasons, vec![InvalidReason::HighS]);
+    }
+}
+
+#[cfg(test)]
+mod detached_hash_signing {
+    use super::*;
+
+    #[test]
+    fn test_sign_transaction_hash_produces_valid_proof() {
+        let key_pair = generate_key_pair();
+        let key_pair2 = generate_key_pair();
+        let last_ref = TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 0,
+        };
+        let tx = create_currency_transaction(
+            TransferParams {
+                destination: key_pair2.address.clone(),
+                amount: 10.0,
+                fee: 0.0,
+            },
+            &key_pair.private_key,
+            last_ref,
+        )
+        .unwrap();
+
+        // Hash on one side, sign on another, then attach
+        let hash = hash_currency_transaction(&tx).unwrap();
+        let proof = sign_transaction_hash(&hash.value, &key_pair2.private_key).unwrap();
+        assert!(verify_transaction_hash(&hash.value, &proof));
+
+        let mut multi = tx.clone();
+        multi.proofs.push(proof);
+        assert!(verify_currency_transaction(&multi).is_valid);
+    }
+
+    #[test]
+    fn test_verify_transaction_hash_rejects_wrong_hash() {
+        let key_pair = generate_key_pair();
+        let proof = sign_transaction_hash(&"a".repeat(64), &key_pair.private_key).unwrap();
+        assert!(!verify_transaction_hash(&"b".repeat(64), &proof));
+        assert!(sign_transaction_hash(&"a".repeat(64), "not hex").is_err());
+    }
+}
+
+#[cfg(test)]
+mod multi_signature_support {
+    use super::*;
+
+    #[test]
+    fn test_sign_currency_transaction_adds_additional_signature() {
+        let key_pair1 = generate_key_pair();
+        let key_pair2 = generate_key_pair();
+        let recipient = generate_key_pair();
+        let last_ref = TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 0,
+        };
+
+        // Create transaction with first signature
+        let tx = create_currency_transaction(
+            TransferParams {
+                destination: recipient.address.clone(),
+                amount: 100.0,
+                fee: 0.0,
+            },
+            &key_pair1.private_key,
+            last_ref,
+        )
+        .unwrap();
+
+        assert_eq!(tx.proofs.len(), 1);
 
         // Add second signature
         let tx = sign_currency_transaction(&tx, &key_pair2.private_key).unwrap();
@@ -332,12 +1001,222 @@ mod multi_signature_support {
         assert_eq!(result.valid_proofs.len(), 2);
         assert_eq!(result.invalid_proofs.len(), 0);
     }
+
+    #[test]
+    fn test_duplicate_signer_counts_once() {
+        let key_pair1 = generate_key_pair();
+        let key_pair2 = generate_key_pair();
+        let recipient = generate_key_pair();
+        let last_ref = TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 0,
+        };
+
+        let tx = create_currency_transaction(
+            TransferParams {
+                destination: recipient.address.clone(),
+                amount: 100.0,
+                fee: 0.0,
+            },
+            &key_pair1.private_key,
+            last_ref,
+        )
+        .unwrap();
+
+        // A second signature from the same key, plus a copy with an uppercase id
+        let tx = sign_currency_transaction(&tx, &key_pair1.private_key).unwrap();
+        let mut tx = sign_currency_transaction(&tx, &key_pair2.private_key).unwrap();
+        tx.proofs.push(SignatureProof {
+            id: tx.proofs[2].id.to_ascii_uppercase(),
+            signature: tx.proofs[2].signature.clone(),
+        });
+
+        let result = verify_currency_transaction(&tx);
+        assert!(!result.is_valid);
+        assert_eq!(
+            result.valid_proofs,
+            vec![tx.proofs[0].clone(), tx.proofs[2].clone()]
+        );
+        assert_eq!(
+            result.invalid_reasons,
+            vec![
+                InvalidReason::DuplicateSigner,
+                InvalidReason::DuplicateSigner
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod unsigned_transactions {
+    use super::*;
+
+    #[test]
+    fn test_unsigned_transaction_reports_no_proofs() {
+        let key_pair = generate_key_pair();
+        let key_pair2 = generate_key_pair();
+        let mut tx = create_currency_transaction(
+            TransferParams {
+                destination: key_pair2.address,
+                amount: 1.0,
+                fee: 0.0,
+            },
+            &key_pair.private_key,
+            TransactionReference {
+                hash: "a".repeat(64),
+                ordinal: 0,
+            },
+        )
+        .unwrap();
+        tx.proofs.clear();
+
+        let result = verify_currency_transaction(&tx);
+        assert!(!result.is_valid);
+        assert!(result.is_unsigned());
+        assert_eq!(result.invalid_reason(), Some(InvalidReason::NoProofs));
+    }
+}
+
+#[cfg(test)]
+mod multisig_threshold {
+    use super::*;
+
+    #[test]
+    fn test_verify_multisig_counts_authorized_signers() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let key3 = generate_key_pair();
+        let outsider = generate_key_pair();
+        let recipient = generate_key_pair();
+        let last_ref = TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 0,
+        };
+
+        let tx = create_currency_transaction(
+            TransferParams {
+                destination: recipient.address.clone(),
+                amount: 10.0,
+                fee: 0.0,
+            },
+            &key1.private_key,
+            last_ref,
+        )
+        .unwrap();
+        let tx = sign_currency_transaction(&tx, &outsider.private_key).unwrap();
+        let authorized = [
+            key1.public_key.as_str(),
+            &key2.public_key[2..],
+            key3.public_key.as_str(),
+        ];
+
+        // 1 authorized signer so far
+        let result = verify_multisig(&tx, 2, &authorized);
+        assert!(!result.is_valid);
+        assert_eq!(result.valid_proofs.len(), 1);
+        assert_eq!(result.invalid_reasons, vec![InvalidReason::Unauthorized]);
+
+        // Duplicate proofs from the same signer count once
+        let mut duplicated = tx.clone();
+        duplicated.proofs.push(tx.proofs[0].clone());
+        assert!(!verify_multisig(&duplicated, 2, &authorized).is_valid);
+
+        // A second authorized signer reaches the threshold, even with a bad proof
+        let tx = sign_currency_transaction(&tx, &key2.private_key).unwrap();
+        let mut tx = tx;
+        tx.proofs.push(SignatureProof {
+            id: key3.public_key[2..].to_string(),
+            signature: tx.proofs[0].signature.clone(),
+        });
+        let result = verify_multisig(&tx, 2, &authorized);
+        assert!(result.is_valid);
+        assert_eq!(result.valid_proofs.len(), 2);
+        assert_eq!(
+            result.invalid_reasons,
+            vec![
+                InvalidReason::SignatureMismatch,
+                InvalidReason::Unauthorized
+            ]
+        );
+        assert!(!verify_multisig(&tx, 3, &authorized).is_valid);
+    }
 }
 
 #[cfg(test)]
 mod transaction_hashing {
     use super::*;
 
+    #[test]
+    fn test_decode_encoded_string_round_trips_and_reverifies() {
+        // Fixed keys and salts: a random salt can occasionally make the
+        // encoding split into fields in more than one way
+        let private_key = "1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+        let destination = key_pair_from_private_key(&"ab".repeat(32)).unwrap().address;
+        for (amount, fee, ordinal) in [(0.00000001, 0.0, 0), (12.5, 0.1, 42), (1e8, 1.0, 999_999)] {
+            let tx = create_currency_transaction_with_nonce(
+                TransferParams {
+                    destination: destination.clone(),
+                    amount,
+                    fee,
+                },
+                private_key,
+                TransactionReference {
+                    hash: "0".repeat(64),
+                    ordinal,
+                },
+                7,
+            )
+            .unwrap();
+
+            let value = decode_encoded_string(&encode_currency_transaction(&tx).unwrap()).unwrap();
+            assert_eq!(value, tx.value);
+
+            // Logged encoded string plus the proofs are enough to re-verify
+            let rebuilt = CurrencyTransaction {
+                value,
+                proofs: tx.proofs.clone(),
+            };
+            assert!(verify_currency_transaction(&rebuilt).is_valid);
+        }
+    }
+
+    #[test]
+    fn test_decode_encoded_string_rejects_malformed_input() {
+        let key_pair = generate_key_pair();
+        let tx = create_currency_transaction(
+            TransferParams {
+                destination: generate_key_pair().address,
+                amount: 1.0,
+                fee: 0.0,
+            },
+            &key_pair.private_key,
+            TransactionReference {
+                hash: "a".repeat(64),
+                ordinal: 3,
+            },
+        )
+        .unwrap();
+        let encoded = encode_currency_transaction(&tx).unwrap();
+
+        for bad in [
+            String::new(),
+            encoded.replacen('2', "1", 1),
+            encoded[..encoded.len() - 1].to_string(),
+            format!("{encoded}0"),
+            encoded.replacen("240DAG", "2040DAG", 1),
+            encoded.replacen("240DAG", "2999999DAG", 1),
+            encoded.replacen(&"a".repeat(64), &"A".repeat(64), 1),
+            "2é".to_string(),
+            // Splits into well-formed fields in three different ways
+            "28122313221213121113312311211111011".to_string(),
+        ] {
+            assert!(
+                matches!(decode_encoded_string(&bad), Err(SdkError::EncodingError(_))),
+                "{bad}"
+            );
+        }
+    }
+
     #[test]
     fn test_hash_currency_transaction_produces_consistent_hashes() {
         let key_pair = generate_key_pair();
@@ -358,14 +1237,137 @@ mod transaction_hashing {
         )
         .unwrap();
 
-        let hash1 = hash_currency_transaction(&tx);
-        let hash2 = hash_currency_transaction(&tx);
+        let hash1 = hash_currency_transaction(&tx).unwrap();
+        let hash2 = hash_currency_transaction(&tx).unwrap();
 
         assert_eq!(hash1.value, hash2.value);
         assert_eq!(hash1.value.len(), 64); // SHA-256 hex string
         assert_eq!(hash1.bytes.len(), 32); // 32 bytes
     }
 
+    #[test]
+    fn test_negative_amount_is_rejected_when_encoding() {
+        let key_pair = generate_key_pair();
+        let mut tx = create_currency_transaction(
+            TransferParams {
+                destination: generate_key_pair().address,
+                amount: 1.0,
+                fee: 0.0,
+            },
+            &key_pair.private_key,
+            TransactionReference {
+                hash: "a".repeat(64),
+                ordinal: 0,
+            },
+        )
+        .unwrap();
+        tx.value.amount = -1;
+
+        assert!(matches!(
+            encode_currency_transaction(&tx),
+            Err(SdkError::InvalidAmount(_))
+        ));
+        assert!(matches!(
+            hash_currency_transaction(&tx),
+            Err(SdkError::InvalidAmount(_))
+        ));
+        assert!(matches!(
+            get_transaction_reference(&tx, 1),
+            Err(SdkError::InvalidAmount(_))
+        ));
+        assert!(matches!(
+            sign_currency_transaction(&tx, &key_pair.private_key),
+            Err(SdkError::InvalidAmount(_))
+        ));
+
+        let result = verify_currency_transaction(&tx);
+        assert!(!result.is_valid);
+        assert!(matches!(
+            result.invalid_reasons[0],
+            InvalidReason::Serialization(_)
+        ));
+    }
+
+    #[test]
+    fn test_non_decimal_salt_is_rejected_when_encoding() {
+        let key_pair = generate_key_pair();
+        let tx = create_currency_transaction(
+            TransferParams {
+                destination: generate_key_pair().address,
+                amount: 1.0,
+                fee: 0.0,
+            },
+            &key_pair.private_key,
+            TransactionReference {
+                hash: "a".repeat(64),
+                ordinal: 0,
+            },
+        )
+        .unwrap();
+
+        for salt in ["abc", "", "+5", "1_000", "0x10"] {
+            let mut json = serde_json::to_value(&tx).unwrap();
+            json["value"]["salt"] = serde_json::json!(salt);
+            let tx: CurrencyTransaction = serde_json::from_value(json).unwrap();
+
+            assert!(
+                matches!(
+                    hash_currency_transaction(&tx),
+                    Err(SdkError::EncodingError(_))
+                ),
+                "salt {salt:?}"
+            );
+            let result = verify_currency_transaction(&tx);
+            assert!(!result.is_valid);
+            assert!(matches!(
+                result.invalid_reasons[0],
+                InvalidReason::Serialization(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_hash_with_reference_precomputes_chain() {
+        let key_pair = generate_key_pair();
+        let head = TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 4,
+        };
+        let transfers = (0..3)
+            .map(|i| TransferParams {
+                destination: generate_key_pair().address,
+                amount: 1.0 + i as f64,
+                fee: 0.0,
+            })
+            .collect();
+        let txns =
+            create_currency_transaction_batch(transfers, &key_pair.private_key, head.clone())
+                .unwrap();
+
+        // Walk the chain from the head using only the values
+        let mut reference = head;
+        for tx in &txns {
+            assert_eq!(tx.value.parent, reference);
+            let hash = hash_with_reference(&tx.value, &reference).unwrap();
+            assert_eq!(hash, hash_currency_transaction(tx).unwrap());
+            reference = TransactionReference {
+                hash: hash.value,
+                ordinal: reference.ordinal + 1,
+            };
+        }
+
+        // A different head changes the hash
+        let other = TransactionReference {
+            hash: "b".repeat(64),
+            ordinal: 4,
+        };
+        let swapped = hash_with_reference(&txns[0].value, &other).unwrap();
+        assert_ne!(swapped, hash_currency_transaction(&txns[0]).unwrap());
+        let mut rebased = txns[0].clone();
+        rebased.value.parent = other;
+        assert_eq!(swapped, hash_currency_transaction(&rebased).unwrap());
+    }
+
     #[test]
     fn test_get_transaction_reference_creates_correct_reference() {
         let key_pair = generate_key_pair();
@@ -386,7 +1388,7 @@ mod transaction_hashing {
         )
         .unwrap();
 
-        let ref_result = get_transaction_reference(&tx, 1);
+        let ref_result = get_transaction_reference(&tx, 1).unwrap();
 
         assert_eq!(ref_result.ordinal, 1);
         assert_eq!(ref_result.hash.len(), 64);
@@ -412,8 +1414,193 @@ mod transaction_hashing {
         )
         .unwrap();
 
-        let encoded = encode_currency_transaction(&tx);
+        let encoded = encode_currency_transaction(&tx).unwrap();
 
         assert!(!encoded.is_empty());
     }
 }
+
+#[cfg(test)]
+mod transaction_size {
+    use super::*;
+
+    #[test]
+    fn test_encoded_size_counts_value_and_proofs() {
+        let key_pair = generate_key_pair();
+        let key_pair2 = generate_key_pair();
+        let last_ref = TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 0,
+        };
+
+        let tx = create_currency_transaction(
+            TransferParams {
+                destination: key_pair2.address.clone(),
+                amount: 100.0,
+                fee: 0.0,
+            },
+            &key_pair.private_key,
+            last_ref,
+        )
+        .unwrap();
+
+        // Kryo header (1 byte) + length varint (2 bytes) + encoded string
+        let value_size = 3 + encode_currency_transaction(&tx).unwrap().len();
+        let proof_size = 64 + tx.proofs[0].signature.len() / 2;
+        assert_eq!(encoded_size(&tx).unwrap(), value_size + proof_size);
+
+        let signed_twice = sign_currency_transaction(&tx, &key_pair.private_key).unwrap();
+        assert!(encoded_size(&signed_twice).unwrap() > encoded_size(&tx).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod encoding_compatibility {
+    use super::*;
+
+    #[test]
+    fn test_check_encoding_compatibility_detects_versions() {
+        let encoded = b"2abc";
+        let length = (encoded.len() + 1) as u8 | 0x80;
+
+        let mut v2 = vec![0x03, length];
+        v2.extend_from_slice(encoded);
+        assert_eq!(
+            check_encoding_compatibility(&v2).unwrap(),
+            TransactionVersion::V2
+        );
+
+        let mut v1 = vec![0x03, 0x01, length];
+        v1.extend_from_slice(encoded);
+        assert_eq!(
+            check_encoding_compatibility(&v1).unwrap(),
+            TransactionVersion::V1
+        );
+    }
+
+    #[test]
+    fn test_check_encoding_compatibility_rejects_unknown_headers() {
+        assert!(check_encoding_compatibility(&[]).is_err());
+        assert!(check_encoding_compatibility(&[0x03]).is_err());
+        assert!(check_encoding_compatibility(&[0x04, 0x85, 0x32]).is_err());
+        assert!(check_encoding_compatibility(&[0x03, 0x02, 0x85]).is_err());
+    }
+}
+
+#[cfg(test)]
+mod transaction_reference {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_serializes_with_node_field_names_and_order() {
+        let reference = TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 7,
+        };
+        assert_eq!(
+            serde_json::to_string(&reference).unwrap(),
+            format!(r#"{{"hash":"{}","ordinal":7}}"#, "a".repeat(64))
+        );
+    }
+
+    #[test]
+    fn test_from_node_json_accepts_bare_and_enveloped_shapes() {
+        let expected = TransactionReference {
+            hash: "b".repeat(64),
+            ordinal: 42,
+        };
+        let hash = "b".repeat(64);
+
+        for body in [
+            json!({"hash": hash, "ordinal": 42}),
+            json!({"data": {"hash": hash, "ordinal": 42}}),
+            json!({"result": {"lastReference": {"Hash": hash, "ORDINAL": "42"}}}),
+        ] {
+            assert_eq!(
+                TransactionReference::from_node_json(&body.to_string()).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_node_json_rejects_other_shapes() {
+        assert!(TransactionReference::from_node_json("not json").is_err());
+        assert!(TransactionReference::from_node_json(r#"{"hash": "abc"}"#).is_err());
+        assert!(
+            TransactionReference::from_node_json(r#"{"hash": "abc", "ordinal": "x"}"#).is_err()
+        );
+    }
+}
+
+#[cfg(test)]
+mod replay_guard {
+    use super::*;
+    use constellation_sdk::{CurrencyTransaction, ReplayGuard, ReplayKey, ReplayStore};
+
+    fn transfer(private_key: &str, last_ref: TransactionReference) -> CurrencyTransaction {
+        create_currency_transaction(
+            TransferParams {
+                destination: generate_key_pair().address,
+                amount: 1.0,
+                fee: 0.0,
+            },
+            private_key,
+            last_ref,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_detects_resubmission() {
+        let key_pair = generate_key_pair();
+        let last_ref = TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 0,
+        };
+        let tx = transfer(&key_pair.private_key, last_ref.clone());
+        let mut guard = ReplayGuard::new();
+
+        guard.check_and_record(&tx).unwrap();
+        assert!(matches!(
+            guard.check_and_record(&tx),
+            Err(SdkError::ReplayDetected(_))
+        ));
+
+        // Re-signing the same value is still a replay
+        let resigned = sign_currency_transaction(&tx, &generate_key_pair().private_key).unwrap();
+        assert!(guard.check_and_record(&resigned).is_err());
+
+        // A new transaction on the same parent has a fresh salt
+        let other = transfer(&key_pair.private_key, last_ref);
+        guard.check_and_record(&other).unwrap();
+        assert_eq!(guard.store().len(), 2);
+    }
+
+    #[test]
+    fn test_uses_custom_store() {
+        #[derive(Default)]
+        struct FailingStore;
+
+        impl ReplayStore for FailingStore {
+            fn insert(&mut self, _key: ReplayKey) -> constellation_sdk::Result<bool> {
+                Err(SdkError::EncodingError("store unavailable".to_string()))
+            }
+        }
+
+        let key_pair = generate_key_pair();
+        let tx = transfer(
+            &key_pair.private_key,
+            TransactionReference {
+                hash: "a".repeat(64),
+                ordinal: 0,
+            },
+        );
+        let mut guard = ReplayGuard::with_store(FailingStore);
+        assert!(matches!(
+            guard.check_and_record(&tx),
+            Err(SdkError::EncodingError(_))
+        ));
+    }
+}
@@ -3,7 +3,9 @@
 //! Validates Rust implementation against reference test vectors from tessellation
 
 use constellation_sdk::currency_transaction::*;
-use constellation_sdk::currency_types::{TransactionReference, TransferParams};
+use constellation_sdk::currency_types::{
+    CurrencyTransactionValue, PartialTransactionValue, TransactionReference, TransferParams,
+};
 use constellation_sdk::types::{SignatureProof, Signed};
 use constellation_sdk::wallet::get_address;
 use secp256k1::{Secp256k1, SecretKey};
@@ -198,7 +200,7 @@ fn test_encoding_format() {
     tx.value.salt = tx_data["salt"].as_i64().unwrap().to_string();
     tx.proofs = vec![];
 
-    let encoded = encode_currency_transaction(&tx);
+    let encoded = encode_currency_transaction(&tx).unwrap();
     assert_eq!(encoded, basic.encoded_string);
 }
 
@@ -254,7 +256,7 @@ fn test_transaction_hash() {
     tx.value.salt = tx_data["salt"].as_i64().unwrap().to_string();
     tx.proofs = vec![];
 
-    let hash = hash_currency_transaction(&tx);
+    let hash = hash_currency_transaction(&tx).unwrap();
     assert_eq!(hash.value, basic.transaction_hash);
 }
 
@@ -375,3 +377,43 @@ fn test_kryo_header() {
     assert!(kryo_hex.starts_with("03"));
     assert!(!kryo_hex.starts_with("0301")); // No reference flag for v2
 }
+
+#[test]
+fn test_verify_claimed_hash() {
+    let vectors = load_test_vectors();
+    let basic = &vectors.test_vectors.basic_transaction;
+    let tx: Signed<CurrencyTransactionValue> = serde_json::from_value(serde_json::json!({
+        "value": basic.transaction,
+        "proofs": []
+    }))
+    .unwrap();
+
+    let partial = PartialTransactionValue {
+        source: tx.value.source.clone(),
+        destination: tx.value.destination.clone(),
+        amount: tx.value.amount,
+        fee: tx.value.fee,
+        parent: tx.value.parent.clone(),
+    };
+
+    assert!(verify_claimed_hash(
+        &partial,
+        &tx.value.salt,
+        &basic.transaction_hash
+    ));
+    assert!(verify_claimed_hash(
+        &partial,
+        &tx.value.salt,
+        &basic.transaction_hash.to_uppercase()
+    ));
+    assert!(!verify_claimed_hash(
+        &partial,
+        "12345",
+        &basic.transaction_hash
+    ));
+    assert!(!verify_claimed_hash(
+        &partial,
+        "not-a-number",
+        &basic.transaction_hash
+    ));
+}
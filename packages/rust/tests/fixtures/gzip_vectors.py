@@ -0,0 +1,90 @@
+"""Generate gzip_vectors.json with the reference zlib implementation.
+
+The vectors cover every DEFLATE block type (stored, fixed and dynamic
+Huffman), the zlib strategies, small windows, sync flushes that leave empty
+stored blocks mid-stream, and gzip headers with optional fields. Each vector
+records the stream and the size and SHA-256 of its plaintext.
+
+Run from this directory: python3 gzip_vectors.py
+"""
+
+import gzip
+import hashlib
+import json
+import random
+import zlib
+
+rng = random.Random(1134)
+words = [b"alpha", b"beta", b"gamma", b"delta", b"sensor", b"reading", b"\"id\":", b"{", b"}", b",", b" "]
+
+plaintexts = {
+    "empty": b"",
+    "short text": b"hello hello hello",
+    "json records": b"".join(
+        b'{"id":"item-%d","value":%d,"tags":["alpha","beta","gamma"]}' % (i, i * i) for i in range(200)
+    ),
+    "random bytes": bytes(rng.getrandbits(8) for _ in range(1500)),
+    "long run": b"a" * 40000,
+    "mixed text": b"".join(rng.choice(words) for _ in range(3000)),
+}
+
+configs = [
+    ("level 1", dict(level=1)),
+    ("level 6", dict(level=6)),
+    ("level 9", dict(level=9)),
+    ("fixed Huffman", dict(level=6, strategy=zlib.Z_FIXED)),
+    ("Huffman only", dict(level=6, strategy=zlib.Z_HUFFMAN_ONLY)),
+    ("RLE", dict(level=6, strategy=zlib.Z_RLE)),
+    ("filtered", dict(level=6, strategy=zlib.Z_FILTERED)),
+    ("512 byte window", dict(level=9, wbits=16 + 9)),
+]
+
+
+def compress(data, level, strategy=zlib.Z_DEFAULT_STRATEGY, wbits=31, flush_every=None):
+    c = zlib.compressobj(level, zlib.DEFLATED, wbits, 8, strategy)
+    if flush_every is None:
+        return c.compress(data) + c.flush()
+    out = b""
+    for i in range(0, len(data), flush_every):
+        out += c.compress(data[i : i + flush_every]) + c.flush(zlib.Z_SYNC_FLUSH)
+    return out + c.flush()
+
+
+vectors = []
+
+
+def add(description, data, stream):
+    assert zlib.decompress(stream, 31) == data
+    vectors.append(
+        {
+            "source": "zlib " + zlib.ZLIB_RUNTIME_VERSION,
+            "description": description,
+            "gzip_hex": stream.hex(),
+            "size": len(data),
+            "sha256": hashlib.sha256(data).hexdigest(),
+        }
+    )
+
+
+for name, data in plaintexts.items():
+    for config, kwargs in configs:
+        add(f"{name}, {config}", data, compress(data, **kwargs))
+    add(f"{name}, sync flush every 1000 bytes", data, compress(data, 6, flush_every=1000))
+
+# Stored blocks: a single one, and several split by sync flushes
+add("short text, stored", plaintexts["short text"], compress(plaintexts["short text"], 0))
+add(
+    "json records, stored with sync flushes",
+    plaintexts["json records"],
+    compress(plaintexts["json records"], 0, flush_every=4000),
+)
+
+# gzip module output sets FNAME and an mtime
+header = gzip.compress(plaintexts["json records"], mtime=1700000000)
+named = bytearray(header[:3]) + bytes([0x08]) + header[4:10] + b"records.json\x00" + header[10:]
+add("json records, FNAME header", plaintexts["json records"], bytes(named))
+
+with open("gzip_vectors.json", "w") as f:
+    json.dump(vectors, f, indent=2)
+    f.write("\n")
+print(len(vectors), "vectors")
@@ -0,0 +1,215 @@
+//! Robustness tests for parsing untrusted input
+//!
+//! Every malformed input must produce an `SdkError`, never a panic. Each
+//! target replays the committed regression corpus under `tests/fuzz_corpus/`
+//! and then runs a seeded mutation fuzzer over valid seed inputs.
+
+use std::fs;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+
+use constellation_sdk::{
+    create_currency_transaction, create_signed_object, decode_data_update,
+    decode_data_update_compressed, encode_data_update, encode_data_update_compressed,
+    generate_key_pair, hash_currency_transaction, parse_signed, verify_currency_transaction,
+    Compression, CurrencyTransaction, Result, TransactionReference, TransferParams,
+    VerificationResult,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::{json, Value};
+
+/// Mutated inputs generated per seed
+const ITERATIONS: usize = 5000;
+
+/// Load every file in `tests/fuzz_corpus/<target>/`
+fn corpus(target: &str) -> Vec<(String, Vec<u8>)> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fuzz_corpus")
+        .join(target);
+    let mut entries: Vec<_> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("missing corpus {}: {e}", dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    entries.sort();
+    entries
+        .into_iter()
+        .map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            (name, fs::read(&path).unwrap())
+        })
+        .collect()
+}
+
+/// Apply a few random byte-level mutations
+fn mutate(rng: &mut StdRng, seed: &[u8]) -> Vec<u8> {
+    let mut data = seed.to_vec();
+    for _ in 0..rng.gen_range(1..=4) {
+        match rng.gen_range(0..5) {
+            0 if !data.is_empty() => {
+                let i = rng.gen_range(0..data.len());
+                data[i] ^= 1 << rng.gen_range(0..8);
+            }
+            1 if !data.is_empty() => {
+                let i = rng.gen_range(0..data.len());
+                data[i] = rng.gen();
+            }
+            2 if !data.is_empty() => {
+                let len = rng.gen_range(0..=data.len());
+                data.truncate(len);
+            }
+            3 => {
+                let i = rng.gen_range(0..=data.len());
+                data.insert(i, rng.gen());
+            }
+            _ if !data.is_empty() => {
+                let i = rng.gen_range(0..data.len());
+                data.remove(i);
+            }
+            _ => data.push(rng.gen()),
+        }
+    }
+    data
+}
+
+/// Run `parse` over the corpus and mutations of `seeds`, failing on any panic
+fn fuzz<R>(target: &str, seeds: &[Vec<u8>], parse: impl Fn(&[u8]) -> R) {
+    let check = |label: &str, input: &[u8]| {
+        if catch_unwind(AssertUnwindSafe(|| parse(input))).is_err() {
+            panic!("{target}: panicked on {label}: {}", hex::encode(input));
+        }
+    };
+
+    for (name, input) in corpus(target) {
+        check(&name, &input);
+    }
+
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+    for seed in seeds {
+        check("seed", seed);
+        for _ in 0..ITERATIONS {
+            check("mutation", &mutate(&mut rng, seed));
+        }
+    }
+}
+
+fn sample_data() -> Value {
+    json!({"id": "sensor-1", "readings": [1, 2, 3], "meta": {"unit": "C"}})
+}
+
+fn sample_transaction() -> CurrencyTransaction {
+    let key_pair = generate_key_pair();
+    let destination = generate_key_pair();
+    create_currency_transaction(
+        TransferParams {
+            destination: destination.address,
+            amount: 12.5,
+            fee: 0.0,
+        },
+        &key_pair.private_key,
+        TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 3,
+        },
+    )
+    .unwrap()
+}
+
+/// Parse a transaction, then hash and verify it as a receiver would
+fn check_transaction(input: &[u8]) -> Result<VerificationResult> {
+    let transaction: CurrencyTransaction = serde_json::from_slice(input)?;
+    hash_currency_transaction(&transaction)?;
+    Ok(verify_currency_transaction(&transaction))
+}
+
+mod decode_data_update_robustness {
+    use super::*;
+
+    #[test]
+    fn never_panics() {
+        let seeds = vec![encode_data_update(&sample_data()).unwrap()];
+        fuzz("decode_data_update", &seeds, |input| {
+            decode_data_update::<Value>(input)
+        });
+    }
+
+    #[test]
+    fn corpus_inputs_are_rejected() {
+        for (name, input) in corpus("decode_data_update") {
+            assert!(
+                decode_data_update::<Value>(&input).is_err(),
+                "{name} should be rejected"
+            );
+        }
+    }
+}
+
+mod decode_data_update_compressed_robustness {
+    use super::*;
+
+    #[test]
+    fn never_panics() {
+        let seeds = vec![
+            encode_data_update_compressed(&sample_data(), Compression::Gzip).unwrap(),
+            encode_data_update_compressed(&json!({"blob": "x".repeat(500)}), Compression::Gzip)
+                .unwrap(),
+        ];
+        fuzz("decode_data_update_compressed", &seeds, |input| {
+            decode_data_update_compressed::<Value>(input)
+        });
+    }
+
+    #[test]
+    fn corpus_inputs_are_rejected() {
+        for (name, input) in corpus("decode_data_update_compressed") {
+            assert!(
+                decode_data_update_compressed::<Value>(&input).is_err(),
+                "{name} should be rejected"
+            );
+        }
+    }
+}
+
+mod parse_signed_robustness {
+    use super::*;
+
+    #[test]
+    fn never_panics() {
+        let key_pair = generate_key_pair();
+        let signed = create_signed_object(&sample_data(), &key_pair.private_key, false).unwrap();
+        let seeds = vec![serde_json::to_vec(&signed).unwrap()];
+        fuzz("parse_signed", &seeds, |input| {
+            parse_signed(&String::from_utf8_lossy(input))
+        });
+    }
+
+    #[test]
+    fn corpus_inputs_are_rejected() {
+        for (name, input) in corpus("parse_signed") {
+            assert!(
+                parse_signed(&String::from_utf8_lossy(&input)).is_err(),
+                "{name} should be rejected"
+            );
+        }
+    }
+}
+
+mod currency_transaction_json_robustness {
+    use super::*;
+
+    #[test]
+    fn never_panics() {
+        let seeds = vec![serde_json::to_vec(&sample_transaction()).unwrap()];
+        fuzz("currency_transaction_json", &seeds, check_transaction);
+    }
+
+    #[test]
+    fn corpus_inputs_are_rejected() {
+        for (name, input) in corpus("currency_transaction_json") {
+            assert!(
+                check_transaction(&input).is_err(),
+                "{name} should be rejected"
+            );
+        }
+    }
+}
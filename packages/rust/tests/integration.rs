@@ -1,11 +1,13 @@
 //! Integration tests for the Constellation Metagraph SDK
 
 use constellation_sdk::{
-    add_signature, batch_sign, canonicalize, create_signed_object, decode_data_update,
-    encode_data_update, generate_key_pair, hash_data, key_pair_from_private_key, sign,
-    sign_data_update, to_bytes, verify, verify_signature, Signed,
+    add_signature, batch_sign, canonicalize, create_signed_object, create_timestamped,
+    decode_data_update, encode_data_update, generate_key_pair, get_public_key_hex, hash_data,
+    key_pair_from_private_key, sign, sign_data_update, split_proofs, to_bytes, validate_proof_set,
+    verify, verify_auto, verify_ordered, verify_signature, verify_signature_against, verify_tagged,
+    verify_with_max_age, SdkError, Signed, SigningMode, TaggedProof, ALGORITHM, SIGNED_AT_FIELD,
 };
-use serde_json::json;
+use serde_json::{json, Value};
 
 mod key_generation {
     use super::*;
@@ -84,6 +86,49 @@ mod regular_signing {
         assert!(verify_signature(&data, &proof2, false).unwrap());
     }
 
+    #[test]
+    fn verifies_against_any_public_key_form() {
+        let key_pair = generate_key_pair();
+        let other = generate_key_pair();
+        let data = json!({"id": "trusted"});
+        let proof = sign(&data, &key_pair.private_key).unwrap();
+        let update_proof = sign_data_update(&data, &key_pair.private_key).unwrap();
+
+        let forms = [
+            key_pair.public_key[2..].to_string(),
+            key_pair.public_key.clone(),
+            get_public_key_hex(&key_pair.private_key, true).unwrap(),
+        ];
+        for key in &forms {
+            assert!(verify_signature_against(&data, &proof.signature, key, false).unwrap());
+            assert!(verify_signature_against(&data, &update_proof.signature, key, true).unwrap());
+            assert!(!verify_signature_against(&data, &proof.signature, key, true).unwrap());
+            assert!(!verify_signature_against(
+                &json!({"id": "other"}),
+                &proof.signature,
+                key,
+                false
+            )
+            .unwrap());
+        }
+
+        let other_compressed = get_public_key_hex(&other.private_key, true).unwrap();
+        for key in [&other.public_key, &other_compressed] {
+            assert!(!verify_signature_against(&data, &proof.signature, key, false).unwrap());
+        }
+
+        for bad in [
+            "04".to_string(),
+            "05".to_string() + &key_pair.public_key[2..],
+            "02".to_string() + &"f".repeat(64),
+        ] {
+            assert!(matches!(
+                verify_signature_against(&data, &proof.signature, &bad, false),
+                Err(SdkError::InvalidPublicKey(_))
+            ));
+        }
+    }
+
     #[test]
     fn signature_contains_public_key_id() {
         let key_pair = generate_key_pair();
@@ -125,6 +170,11 @@ mod data_update_signing {
         // Verify as regular (should fail)
         let result = verify(&signed, false);
         assert!(!result.is_valid);
+
+        // verify_auto detects the mode instead
+        let result = verify_auto(&signed);
+        assert!(result.is_valid);
+        assert_eq!(result.matched_mode, Some(SigningMode::DataUpdate));
     }
 
     #[test]
@@ -145,6 +195,88 @@ mod data_update_signing {
 mod multi_signature {
     use super::*;
 
+    #[test]
+    fn validate_proof_set_reports_structural_violations() {
+        let keys: Vec<_> = (0..3).map(|_| generate_key_pair()).collect();
+        let outsider = generate_key_pair();
+        let data = json!({"proposal": 42});
+        let proof = |private_key: &str| sign(&data, private_key).unwrap();
+        let (p0, p1, p2) = (
+            proof(&keys[0].private_key),
+            proof(&keys[1].private_key),
+            proof(&keys[2].private_key),
+        );
+
+        // Authorized keys in mixed forms
+        let compressed = get_public_key_hex(&keys[1].private_key, true).unwrap();
+        let authorized = [
+            keys[0].public_key.as_str(),
+            compressed.as_str(),
+            &keys[2].public_key[2..],
+        ];
+
+        validate_proof_set(&[p0.clone(), p1.clone()], &authorized, 3).unwrap();
+        validate_proof_set(&[p2.clone(), p0.clone(), p1.clone()], &authorized, 3).unwrap();
+        validate_proof_set(&[], &authorized, 3).unwrap();
+
+        assert!(matches!(
+            validate_proof_set(
+                &[p0.clone(), p1.clone(), p2.clone(), p0.clone()],
+                &authorized,
+                3
+            ),
+            Err(SdkError::TooManyProofs { count: 4, max: 3 })
+        ));
+
+        // The same key with a compressed id is still a duplicate
+        let mut p0_compressed = p0.clone();
+        p0_compressed.id = get_public_key_hex(&keys[0].private_key, true).unwrap();
+        match validate_proof_set(
+            &[p0.clone(), p1.clone(), p0_compressed.clone()],
+            &authorized,
+            3,
+        ) {
+            Err(SdkError::DuplicateSigner(id)) => assert_eq!(id, p0_compressed.id),
+            other => panic!("expected DuplicateSigner, got {other:?}"),
+        }
+
+        let intruder = proof(&outsider.private_key);
+        match validate_proof_set(&[p0, intruder.clone()], &authorized, 3) {
+            Err(SdkError::UnauthorizedSigner(id)) => assert_eq!(id, intruder.id),
+            other => panic!("expected UnauthorizedSigner, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn claimed_signers_skip_verification() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let data = json!({"route": "a"});
+
+        let mut signed = batch_sign(&data, &[&key1.private_key, &key2.private_key], false).unwrap();
+        // Same key again with a 04-prefixed id, and a signature that does not verify
+        let mut duplicate = signed.proofs[0].clone();
+        duplicate.id = key1.public_key.clone();
+        duplicate.signature = signed.proofs[1].signature.clone();
+        signed.proofs.push(duplicate);
+
+        assert_eq!(
+            signed.claimed_signers(),
+            vec![
+                key1.public_key[2..].to_string(),
+                key2.public_key[2..].to_string(),
+                key1.public_key.clone(),
+            ]
+        );
+        assert_eq!(
+            signed.claimed_addresses().unwrap(),
+            vec![key1.address, key2.address]
+        );
+
+        signed.proofs[0].id = "not-hex".to_string();
+        assert!(signed.claimed_addresses().is_err());
+    }
+
     #[test]
     fn adds_signature_to_existing_object() {
         let key1 = generate_key_pair();
@@ -193,11 +325,111 @@ mod multi_signature {
         assert_ne!(signed.proofs[0].id, signed.proofs[1].id);
         assert_ne!(signed.proofs[0].signature, signed.proofs[1].signature);
     }
+
+    #[test]
+    fn splits_into_independently_verifying_objects() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let data = json!({"id": "test"});
+
+        let mut signed = batch_sign(&data, &[&key1.private_key, &key2.private_key], true).unwrap();
+        signed.proofs[1].signature = signed.proofs[0].signature.clone();
+
+        let parts = split_proofs(&signed);
+        assert_eq!(parts.len(), 2);
+        assert!(parts.iter().all(|part| part.value == data));
+        assert_eq!(parts[0].proofs, vec![signed.proofs[0].clone()]);
+        assert!(verify(&parts[0], true).is_valid);
+        assert!(!verify(&parts[1], true).is_valid);
+
+        signed.proofs.clear();
+        assert!(split_proofs(&signed).is_empty());
+    }
+
+    #[test]
+    fn enforces_signing_order() {
+        let manager = generate_key_pair();
+        let director = generate_key_pair();
+        let outsider = generate_key_pair();
+        let data = json!({"expense": 42});
+
+        let signed = create_signed_object(&data, &manager.private_key, false).unwrap();
+        let signed = add_signature(signed, &director.private_key, false).unwrap();
+
+        let manager_id = &manager.public_key[2..];
+        assert!(verify_ordered(&signed, &[manager_id, &director.public_key], false).unwrap());
+        assert!(!verify_ordered(&signed, &[&director.public_key, manager_id], false).unwrap());
+        assert!(!verify_ordered(&signed, &[manager_id], false).unwrap());
+        assert!(!verify_ordered(
+            &signed,
+            &[manager_id, &director.public_key, &outsider.public_key],
+            false
+        )
+        .unwrap());
+        assert!(!verify_ordered(&signed, &[manager_id, &director.public_key], true).unwrap());
+        assert!(verify_ordered(&signed, &[manager_id, "zz"], false).is_err());
+
+        let mut tampered = signed.clone();
+        tampered.proofs[1].signature = tampered.proofs[0].signature.clone();
+        assert!(!verify_ordered(&tampered, &[manager_id, &director.public_key], false).unwrap());
+    }
+
+    #[test]
+    fn quorum_counts_each_signer_once() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let key3 = generate_key_pair();
+        let data = json!({"proposal": 1});
+
+        let mut signed = batch_sign(&data, &[&key1.private_key, &key2.private_key], false).unwrap();
+        // key1 again, with a prefixed id
+        let mut duplicate = signed.proofs[0].clone();
+        duplicate.id = key1.public_key.clone();
+        signed.proofs.push(duplicate);
+        // key3 with a signature that does not match
+        let mut bad = signed.proofs[1].clone();
+        bad.id = key3.public_key[2..].to_string();
+        signed.proofs.push(bad);
+
+        let result = verify(&signed, false);
+        assert_eq!(result.valid_proofs.len(), 3);
+        assert_eq!(result.valid_count(), 2);
+        assert_eq!(result.total_count(), 3);
+        assert!((result.quorum_fraction() - 2.0 / 3.0).abs() < 1e-12);
+        assert!(result.meets_quorum(2));
+        assert!(!result.meets_quorum(3));
+
+        let unsigned = verify(
+            &Signed {
+                value: data,
+                proofs: vec![],
+            },
+            false,
+        );
+        assert_eq!(unsigned.quorum_fraction(), 0.0);
+        assert!(!unsigned.meets_quorum(0));
+    }
 }
 
 mod tamper_detection {
     use super::*;
 
+    #[test]
+    fn constant_time_proof_comparison_matches_eq() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let data = json!({"id": "test"});
+
+        let proof1 = sign(&data, &key1.private_key).unwrap();
+        let proof2 = sign(&data, &key2.private_key).unwrap();
+        let mut truncated = proof1.clone();
+        truncated.signature.pop();
+
+        for other in [&proof1, &proof2, &truncated] {
+            assert_eq!(proof1.ct_eq(other), proof1 == *other);
+        }
+    }
+
     #[test]
     fn detects_modified_value() {
         let key_pair = generate_key_pair();
@@ -218,6 +450,36 @@ mod tamper_detection {
         assert_eq!(result.invalid_proofs.len(), 1);
     }
 
+    #[test]
+    fn into_result_reports_proof_counts() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let data = json!({"amount": 100});
+
+        let mut signed = batch_sign(&data, &[&key1.private_key, &key2.private_key], false).unwrap();
+        assert_eq!(
+            verify(&signed, false).into_result().unwrap().valid_count(),
+            2
+        );
+
+        signed.value = json!({"amount": 999});
+        signed
+            .proofs
+            .push(sign(&signed.value, &key1.private_key).unwrap());
+        let err = verify(&signed, false).into_result().unwrap_err();
+        assert!(matches!(
+            err,
+            SdkError::VerificationFailed {
+                valid: 1,
+                invalid: 2
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Verification failed: 1 valid and 2 invalid proofs"
+        );
+    }
+
     #[test]
     fn detects_modified_signature() {
         let key_pair = generate_key_pair();
@@ -352,4 +614,384 @@ mod error_handling {
         let result = batch_sign::<serde_json::Value>(&data, &[], false);
         assert!(matches!(result, Err(SdkError::NoPrivateKeys)));
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn converts_io_errors_with_question_mark() {
+        fn read_key(path: &str) -> constellation_sdk::Result<String> {
+            let private_key = std::fs::read_to_string(path)?;
+            Ok(key_pair_from_private_key(private_key.trim())?.address)
+        }
+
+        let result = read_key("/nonexistent/constellation/key.hex");
+        assert!(matches!(result, Err(SdkError::IoError(_))));
+    }
+}
+
+#[cfg(feature = "std")]
+mod ndjson {
+    use super::*;
+    use constellation_sdk::signed_object::{read_ndjson, write_ndjson};
+
+    #[test]
+    fn round_trips_and_still_verifies() {
+        let key_pair = generate_key_pair();
+        let objects: Vec<Signed<Value>> = (0..3)
+            .map(|i| {
+                create_signed_object(
+                    &json!({"seq": i, "note": "line\nbreak"}),
+                    &key_pair.private_key,
+                    false,
+                )
+                .unwrap()
+            })
+            .collect();
+
+        let mut buffer = Vec::new();
+        write_ndjson(&objects, &mut buffer).unwrap();
+        let text = String::from_utf8(buffer.clone()).unwrap();
+        assert_eq!(text.lines().count(), 3);
+        assert!(text.ends_with('\n'));
+
+        let read: Vec<Signed<Value>> = read_ndjson(buffer.as_slice())
+            .collect::<constellation_sdk::Result<_>>()
+            .unwrap();
+        assert_eq!(read, objects);
+        assert!(read.iter().all(|signed| verify(signed, false).is_valid));
+    }
+
+    #[test]
+    fn skips_blank_lines_and_reports_bad_line_numbers() {
+        let key_pair = generate_key_pair();
+        let signed = create_signed_object(&json!({"id": 1}), &key_pair.private_key, false).unwrap();
+        let line = serde_json::to_string(&signed).unwrap();
+        let input = format!("{line}\n\n{{not json}}\n{line}\n");
+
+        let results: Vec<_> = read_ndjson::<_, Value>(input.as_bytes()).collect();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap(), &signed);
+        match &results[1] {
+            Err(SdkError::SerializationError(msg)) => assert!(msg.starts_with("line 3:")),
+            other => panic!("expected SerializationError, got {other:?}"),
+        }
+        assert_eq!(results[2].as_ref().unwrap(), &signed);
+    }
+}
+
+#[cfg(feature = "msgpack")]
+mod msgpack_signing {
+    use super::*;
+    use constellation_sdk::binary::to_bytes_msgpack;
+    use constellation_sdk::sign::sign_msgpack;
+    use constellation_sdk::verify::verify_msgpack;
+    use constellation_sdk::{hash_bytes, MSGPACK_ALGORITHM};
+
+    const TEST_PRIVATE_KEY: &str =
+        "1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+
+    /// Pinned encoding, hash and RFC 6979 signature, cross-checked against an
+    /// independent MessagePack encoder and signer. No other SDK supports
+    /// MessagePack yet, so this locks the Rust encoding instead.
+    const VECTOR_MSGPACK: &str = "86a6616d6f756e74ce59682f00a3666565fea26964aa6d73677061636b2d7631\
+        a66e657374656482a162c3a17ac0a5726174696fcb3fd0000000000000a47461677392a161a2c3a9";
+    const VECTOR_HASH: &str = "5fd47f88e3d381d2420da24783b14937d6714813a9c7a001509ac90c63eb68cc";
+    const VECTOR_SIGNATURE: &str =
+        "304402200f9f089e1e487645cca1cd70adc2382e8f0304f8bc64aac6a75a8f7b\
+        591209ab02204da8e83c6d31a0a6a5f99c2df46fb8b13463c7322918ef997c132e0c29127e0f";
+
+    fn vector() -> Value {
+        json!({
+            "id": "msgpack-v1",
+            "amount": 1500000000,
+            "fee": -2,
+            "ratio": 0.25,
+            "tags": ["a", "\u{e9}"],
+            "nested": {"z": null, "b": true}
+        })
+    }
+
+    #[test]
+    fn matches_pinned_vector() {
+        let bytes = to_bytes_msgpack(&vector(), false).unwrap();
+        assert_eq!(hex::encode(&bytes), VECTOR_MSGPACK);
+        assert_eq!(hash_bytes(&bytes).value, VECTOR_HASH);
+
+        let proof = sign_msgpack(&vector(), TEST_PRIVATE_KEY, false).unwrap();
+        assert_eq!(proof.signature, VECTOR_SIGNATURE);
+        assert!(verify_msgpack(&vector(), &proof, false).unwrap());
+    }
+
+    #[test]
+    fn encoding_ignores_field_order() {
+        #[derive(serde::Serialize)]
+        struct Nested {
+            z: Option<u8>,
+            b: bool,
+        }
+        #[derive(serde::Serialize)]
+        struct Payload {
+            tags: Vec<&'static str>,
+            ratio: f64,
+            nested: Nested,
+            id: &'static str,
+            fee: i64,
+            amount: u64,
+        }
+
+        let typed = Payload {
+            tags: vec!["a", "\u{e9}"],
+            ratio: 0.25,
+            nested: Nested { z: None, b: true },
+            id: "msgpack-v1",
+            fee: -2,
+            amount: 1_500_000_000,
+        };
+        assert_eq!(
+            hex::encode(to_bytes_msgpack(&typed, false).unwrap()),
+            VECTOR_MSGPACK
+        );
+        for _ in 0..10 {
+            assert_eq!(
+                to_bytes_msgpack(&vector(), true).unwrap(),
+                to_bytes_msgpack(&typed, true).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn proofs_are_bound_to_mode_and_algorithm() {
+        let key_pair = generate_key_pair();
+        let data = vector();
+        let proof = sign_msgpack(&data, &key_pair.private_key, true).unwrap();
+
+        assert!(verify_msgpack(&data, &proof, true).unwrap());
+        assert!(!verify_msgpack(&data, &proof, false).unwrap());
+        // Not a canonical JSON signature
+        assert!(!verify_signature(&data, &proof, true).unwrap());
+
+        let tagged = TaggedProof::new(proof, MSGPACK_ALGORITHM);
+        assert!(verify_tagged(&data, &tagged, true).unwrap());
+    }
+}
+
+mod ordering {
+    use super::*;
+    use constellation_sdk::{Hash, SignatureProof};
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn hashes_sort_by_hex_value() {
+        let hashes: BTreeSet<Hash> = [json!(1), json!(2), json!(3), json!(1)]
+            .iter()
+            .map(|data| hash_data(data, false).unwrap())
+            .collect();
+
+        let values: Vec<&String> = hashes.iter().map(|h| &h.value).collect();
+        let mut sorted = values.clone();
+        sorted.sort();
+        assert_eq!(hashes.len(), 3);
+        assert_eq!(values, sorted);
+    }
+
+    #[test]
+    fn proofs_sort_by_id_then_signature() {
+        let proof = |id: &str, signature: &str| SignatureProof {
+            id: id.to_string(),
+            signature: signature.to_string(),
+        };
+        let mut proofs = vec![proof("b", "1"), proof("a", "2"), proof("a", "1")];
+        proofs.sort();
+        assert_eq!(
+            proofs,
+            vec![proof("a", "1"), proof("a", "2"), proof("b", "1")]
+        );
+    }
+}
+
+mod serde_attributes {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Clone, Serialize)]
+    struct Metadata {
+        zone: String,
+        revision: u32,
+    }
+
+    #[derive(Clone, Serialize)]
+    struct Reading {
+        sensor: String,
+        #[serde(flatten)]
+        metadata: Metadata,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
+        value: f64,
+    }
+
+    fn reading(note: Option<&str>) -> Reading {
+        Reading {
+            sensor: "s-1".to_string(),
+            metadata: Metadata {
+                zone: "north".to_string(),
+                revision: 3,
+            },
+            note: note.map(str::to_string),
+            value: 21.5,
+        }
+    }
+
+    #[test]
+    fn flattened_struct_hashes_like_equivalent_json() {
+        let expected = json!({
+            "sensor": "s-1",
+            "zone": "north",
+            "revision": 3,
+            "note": "calibrated",
+            "value": 21.5
+        });
+        let data = reading(Some("calibrated"));
+
+        assert_eq!(
+            canonicalize(&data).unwrap(),
+            canonicalize(&expected).unwrap()
+        );
+        for is_data_update in [false, true] {
+            assert_eq!(
+                hash_data(&data, is_data_update).unwrap(),
+                hash_data(&expected, is_data_update).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn skipped_none_field_hashes_like_absent_key() {
+        let expected = json!({
+            "sensor": "s-1",
+            "zone": "north",
+            "revision": 3,
+            "value": 21.5
+        });
+        let data = reading(None);
+
+        assert_eq!(
+            hash_data(&data, false).unwrap(),
+            hash_data(&expected, false).unwrap()
+        );
+        // An explicit null is a different document
+        let mut with_null = expected.clone();
+        with_null["note"] = Value::Null;
+        assert_ne!(
+            hash_data(&data, false).unwrap(),
+            hash_data(&with_null, false).unwrap()
+        );
+    }
+
+    #[test]
+    fn struct_signature_verifies_as_json_value() {
+        let key_pair = generate_key_pair();
+        let signed = create_signed_object(&reading(None), &key_pair.private_key, false).unwrap();
+
+        let as_value = Signed {
+            value: serde_json::to_value(&signed.value).unwrap(),
+            proofs: signed.proofs.clone(),
+        };
+        assert!(verify(&signed, false).is_valid);
+        assert!(verify(&as_value, false).is_valid);
+    }
+}
+
+mod tagged_proofs {
+    use super::*;
+
+    #[test]
+    fn untagged_proof_defaults_to_json_algorithm() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test"});
+        let proof = sign(&data, &key_pair.private_key).unwrap();
+
+        let wire = serde_json::to_value(&proof).unwrap();
+        let tagged: TaggedProof = serde_json::from_value(wire).unwrap();
+        assert_eq!(tagged.algorithm, ALGORITHM);
+        assert_eq!(tagged.proof, proof);
+        assert!(verify_tagged(&data, &tagged, false).unwrap());
+
+        let round_trip = serde_json::to_value(&tagged).unwrap();
+        assert_eq!(round_trip["algorithm"], ALGORITHM);
+        assert_eq!(round_trip["id"], proof.id.as_str());
+    }
+
+    #[test]
+    fn dispatches_on_algorithm() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test"});
+        let proof = TaggedProof::new(sign(&data, &key_pair.private_key).unwrap(), ALGORITHM);
+
+        assert!(verify_tagged(&data, &proof, false).unwrap());
+        assert!(!verify_tagged(&json!({}), &proof, false).unwrap());
+
+        let unknown = TaggedProof::new(proof.proof, "ED25519");
+        assert!(matches!(
+            verify_tagged(&data, &unknown, false),
+            Err(SdkError::UnsupportedAlgorithm(a)) if a == "ED25519"
+        ));
+    }
+}
+
+mod timestamped_signing {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn fresh_timestamp_verifies() {
+        let key_pair = generate_key_pair();
+        let signed = create_timestamped(&json!({"id": "a"}), &key_pair.private_key, true).unwrap();
+
+        assert_eq!(signed.value["id"], "a");
+        let result =
+            verify_with_max_age(&signed, SIGNED_AT_FIELD, Duration::from_secs(60), true).unwrap();
+        assert!(result.is_valid);
+    }
+
+    #[test]
+    fn timestamp_is_signed_content() {
+        let key_pair = generate_key_pair();
+        let mut signed =
+            create_timestamped(&json!({"id": "a"}), &key_pair.private_key, false).unwrap();
+        signed.value[SIGNED_AT_FIELD] = json!("2099-01-01T00:00:00.000Z");
+        assert!(!verify(&signed, false).is_valid);
+    }
+
+    #[test]
+    fn rejects_old_and_future_timestamps() {
+        let key_pair = generate_key_pair();
+        let max_age = Duration::from_secs(3600);
+
+        for timestamp in ["2020-01-01T00:00:00Z", "2999-01-01T00:00:00Z"] {
+            let data = json!({"id": "a", "_signedAt": timestamp});
+            let signed = create_signed_object(&data, &key_pair.private_key, false).unwrap();
+            assert!(matches!(
+                verify_with_max_age(&signed, SIGNED_AT_FIELD, max_age, false),
+                Err(SdkError::StaleTimestamp(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_missing_or_malformed_timestamps() {
+        let key_pair = generate_key_pair();
+        let max_age = Duration::from_secs(60);
+
+        for data in [json!({"id": "a"}), json!({"_signedAt": 1700000000})] {
+            let signed = create_signed_object(&data, &key_pair.private_key, false).unwrap();
+            assert!(matches!(
+                verify_with_max_age(&signed, SIGNED_AT_FIELD, max_age, false),
+                Err(SdkError::InvalidEnvelope(_))
+            ));
+        }
+
+        assert!(matches!(
+            create_timestamped(&json!([1, 2]), &key_pair.private_key, false),
+            Err(SdkError::InvalidEnvelope(_))
+        ));
+    }
 }
@@ -2,7 +2,14 @@
 
 #[cfg(feature = "network")]
 mod network_tests {
-    use constellation_sdk::network::{CurrencyL1Client, DataL1Client, NetworkConfig, NetworkError};
+    use constellation_sdk::network::{
+        CurrencyL1Client, DataL1Client, NetworkConfig, NetworkError, NodeInfo, SendTransferError,
+    };
+    use constellation_sdk::{
+        create_signed_object, generate_key_pair, verify, verify_currency_transaction,
+        CurrencyTransaction, TransferParams,
+    };
+    use serde_json::json;
 
     mod currency_l1_client {
         use super::*;
@@ -30,6 +37,39 @@ mod network_tests {
             assert!(result.is_ok());
         }
 
+        #[test]
+        fn is_cloneable_and_shareable_across_tasks() {
+            fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+            assert_shareable::<CurrencyL1Client>();
+
+            let config = NetworkConfig {
+                l1_url: Some("http://localhost:9010".to_string()),
+                ..Default::default()
+            };
+            let client = CurrencyL1Client::new(config).unwrap();
+            let workers: Vec<_> = (0..2)
+                .map(|_| {
+                    let client = client.clone();
+                    std::thread::spawn(move || drop(client))
+                })
+                .collect();
+            for worker in workers {
+                worker.join().unwrap();
+            }
+        }
+
+        #[test]
+        fn accepts_connection_pool_settings() {
+            let config = NetworkConfig {
+                l1_url: Some("http://localhost:9010".to_string()),
+                pool_max_idle_per_host: Some(64),
+                pool_idle_timeout: Some(30),
+                tcp_keepalive: Some(15),
+                ..Default::default()
+            };
+            assert!(CurrencyL1Client::new(config).is_ok());
+        }
+
         #[test]
         fn accepts_optional_timeout() {
             let config = NetworkConfig {
@@ -40,6 +80,194 @@ mod network_tests {
             let result = CurrencyL1Client::new(config);
             assert!(result.is_ok());
         }
+
+        /// Answer one request per response in order, returning the URL and
+        /// a handle yielding the raw requests received
+        async fn serve(
+            responses: Vec<(&'static str, String)>,
+        ) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+            use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let server = tokio::spawn(async move {
+                let mut requests = Vec::new();
+                for (status, body) in responses {
+                    let (mut socket, _) = listener.accept().await.unwrap();
+                    let mut request = Vec::new();
+                    let mut buf = [0u8; 4096];
+                    loop {
+                        let n = socket.read(&mut buf).await.unwrap();
+                        request.extend_from_slice(&buf[..n]);
+                        let text = String::from_utf8_lossy(&request);
+                        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                            let length = head
+                                .lines()
+                                .find_map(|line| {
+                                    let (name, value) = line.split_once(':')?;
+                                    name.eq_ignore_ascii_case("content-length")
+                                        .then(|| value.trim().parse::<usize>().ok())?
+                                })
+                                .unwrap_or(0);
+                            if n == 0 || body.len() >= length {
+                                break;
+                            }
+                        }
+                    }
+                    requests.push(String::from_utf8(request).unwrap());
+
+                    let response = format!(
+                        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    socket.write_all(response.as_bytes()).await.unwrap();
+                }
+                requests
+            });
+            (url, server)
+        }
+
+        fn client_for(url: String) -> CurrencyL1Client {
+            CurrencyL1Client::new(NetworkConfig {
+                l1_url: Some(url),
+                ..Default::default()
+            })
+            .unwrap()
+        }
+
+        fn last_reference() -> (&'static str, String) {
+            (
+                "200 OK",
+                json!({"hash": "a".repeat(64), "ordinal": 7}).to_string(),
+            )
+        }
+
+        #[tokio::test]
+        async fn send_transfer_fetches_builds_and_submits() {
+            let source = generate_key_pair();
+            let destination = generate_key_pair();
+            let (url, server) = serve(vec![
+                last_reference(),
+                ("200 OK", json!({"hash": "b".repeat(64)}).to_string()),
+            ])
+            .await;
+
+            let params = TransferParams {
+                destination: destination.address.clone(),
+                amount: 2.5,
+                fee: 0.0,
+            };
+            let response = client_for(url)
+                .send_transfer(params, &source.private_key)
+                .await
+                .unwrap();
+            assert_eq!(response.hash, "b".repeat(64));
+
+            let requests = server.await.unwrap();
+            assert!(requests[0].starts_with(&format!(
+                "GET /transactions/last-reference/{}",
+                source.address
+            )));
+            assert!(requests[1].starts_with("POST /transactions"));
+
+            let body = requests[1].split_once("\r\n\r\n").unwrap().1;
+            let tx: CurrencyTransaction = serde_json::from_str(body).unwrap();
+            assert_eq!(tx.value.parent.hash, "a".repeat(64));
+            assert_eq!(tx.value.parent.ordinal, 7);
+            assert_eq!(tx.value.destination, destination.address);
+            assert!(verify_currency_transaction(&tx).is_valid);
+        }
+
+        #[tokio::test]
+        async fn send_transfer_reports_failing_step() {
+            let source = generate_key_pair();
+            let params = || TransferParams {
+                destination: generate_key_pair().address,
+                amount: 1.0,
+                fee: 0.0,
+            };
+
+            let (url, _) = serve(vec![("500 Internal Server Error", String::new())]).await;
+            let result = client_for(url)
+                .send_transfer(params(), &source.private_key)
+                .await;
+            assert!(
+                matches!(result, Err(SendTransferError::Reference(ref e)) if e.status_code() == Some(500))
+            );
+
+            let (url, _) = serve(vec![last_reference()]).await;
+            let invalid = TransferParams {
+                destination: "DAGinvalid".to_string(),
+                ..params()
+            };
+            let result = client_for(url)
+                .send_transfer(invalid, &source.private_key)
+                .await;
+            assert!(matches!(result, Err(SendTransferError::Build(_))));
+
+            let (url, _) = serve(vec![
+                last_reference(),
+                ("400 Bad Request", r#"{"error":"rejected"}"#.to_string()),
+            ])
+            .await;
+            let result = client_for(url)
+                .send_transfer(params(), &source.private_key)
+                .await;
+            let err = result.unwrap_err();
+            assert!(
+                matches!(err, SendTransferError::Submit(ref e) if e.status_code() == Some(400))
+            );
+            assert!(err.to_string().starts_with("Failed to submit transaction"));
+        }
+
+        #[tokio::test]
+        async fn dropping_post_transaction_closes_connection() {
+            use constellation_sdk::{create_currency_transaction, TransactionReference};
+            use std::time::Duration;
+            use tokio::io::AsyncReadExt;
+
+            // A node that reads the request and never answers
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let server = tokio::spawn(async move {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 8192];
+                let _ = socket.read(&mut buf).await.unwrap();
+                // Drain until the client closes the connection
+                while socket.read(&mut buf).await.unwrap() > 0 {}
+            });
+
+            let source = generate_key_pair();
+            let destination = generate_key_pair();
+            let tx = create_currency_transaction(
+                TransferParams {
+                    destination: destination.address,
+                    amount: 1.0,
+                    fee: 0.0,
+                },
+                &source.private_key,
+                TransactionReference {
+                    hash: "a".repeat(64),
+                    ordinal: 0,
+                },
+            )
+            .unwrap();
+            let client = CurrencyL1Client::new(NetworkConfig {
+                l1_url: Some(url),
+                ..Default::default()
+            })
+            .unwrap();
+
+            let submission =
+                tokio::time::timeout(Duration::from_secs(1), client.post_transaction(&tx)).await;
+            assert!(submission.is_err());
+
+            // The dropped request must not keep the connection open
+            tokio::time::timeout(Duration::from_secs(5), server)
+                .await
+                .expect("connection was not closed after cancellation")
+                .unwrap();
+        }
     }
 
     mod data_l1_client {
@@ -68,6 +296,66 @@ mod network_tests {
             assert!(result.is_ok());
         }
 
+        #[test]
+        fn is_cloneable_and_shareable_across_tasks() {
+            fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+            assert_shareable::<DataL1Client>();
+        }
+
+        /// Serve a single HTTP response on a local port and return its URL
+        async fn serve_once(status: &'static str, body: String) -> String {
+            use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            tokio::spawn(async move {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = [0u8; 4096];
+                let _ = socket.read(&mut request).await.unwrap();
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            });
+            url
+        }
+
+        fn client_for(url: String) -> DataL1Client {
+            DataL1Client::new(NetworkConfig {
+                data_l1_url: Some(url),
+                ..Default::default()
+            })
+            .unwrap()
+        }
+
+        #[tokio::test]
+        async fn fetches_stored_data_update() {
+            let key_pair = generate_key_pair();
+            let signed =
+                create_signed_object(&json!({"id": "update-1"}), &key_pair.private_key, true)
+                    .unwrap();
+            let url = serve_once("200 OK", serde_json::to_string(&signed).unwrap()).await;
+
+            let stored = client_for(url).get_data_update("abc").await.unwrap();
+            assert_eq!(stored, signed);
+            assert!(verify(&stored, true).is_valid);
+        }
+
+        #[tokio::test]
+        async fn rejects_malformed_envelope() {
+            let url = serve_once("200 OK", r#"{"value": {}}"#.to_string()).await;
+            let result = client_for(url).get_data_update("abc").await;
+            assert!(matches!(result, Err(NetworkError::SerializationError(_))));
+        }
+
+        #[tokio::test]
+        async fn reports_missing_data_update() {
+            let url = serve_once("404 Not Found", String::new()).await;
+            let result = client_for(url).get_data_update("abc").await;
+            assert_eq!(result.unwrap_err().status_code(), Some(404));
+        }
+
         #[test]
         fn accepts_optional_timeout() {
             let config = NetworkConfig {
@@ -80,6 +368,93 @@ mod network_tests {
         }
     }
 
+    mod node_info {
+        use super::*;
+
+        fn sample(version: &str) -> NodeInfo {
+            serde_json::from_value(serde_json::json!({
+                "state": "Ready",
+                "id": "e0c1ee6ec43510f0e16d2969a7a7c074a5c8cdb477c074fe9c32a9aad8cbc8ff1dff60bb81923e0db437d2686a9b65b86c403e6a21fa32b6acc4e61be4d70925",
+                "host": "127.0.0.1",
+                "publicPort": 9010,
+                "p2pPort": 9011,
+                "session": "1700000000000",
+                "clusterSession": "1700000000001",
+                "version": version
+            }))
+            .unwrap()
+        }
+
+        #[test]
+        fn deserializes_node_info_response() {
+            let info = sample("2.8.1");
+            assert!(info.is_ready());
+            assert_eq!(info.public_port, 9010);
+            assert_eq!(info.p2p_port, 9011);
+            assert_eq!(info.cluster_session.as_deref(), Some("1700000000001"));
+            assert_eq!(info.ordinal, None);
+        }
+
+        #[test]
+        fn parses_version_parts() {
+            assert_eq!(sample("2.8.1").version_parts(), Some((2, 8, 1)));
+            assert_eq!(sample("v3.0.0-rc.1").version_parts(), Some((3, 0, 0)));
+            assert_eq!(sample("unknown").version_parts(), None);
+            assert_eq!(sample("1.2").version_parts(), None);
+        }
+    }
+
+    mod submit_response {
+        use super::*;
+        use constellation_sdk::network::{SubmitResponse, TransactionStatus};
+
+        #[test]
+        fn deserializes_hash_only_response() {
+            let response: SubmitResponse =
+                serde_json::from_value(json!({"hash": "abc123"})).unwrap();
+            assert_eq!(response.hash, "abc123");
+            assert_eq!(response.ordinal, None);
+            assert_eq!(response.status, None);
+            assert_eq!(response.accepted_at, None);
+            assert!(response.extra.is_empty());
+        }
+
+        #[test]
+        fn deserializes_node_metadata() {
+            let response: SubmitResponse = serde_json::from_value(json!({
+                "hash": "abc123",
+                "ordinal": 42,
+                "status": "Waiting",
+                "acceptedAt": 1700000000000i64,
+                "peer": "node-1"
+            }))
+            .unwrap();
+            assert_eq!(response.ordinal, Some(42));
+            assert_eq!(response.status, Some(TransactionStatus::Waiting));
+            assert_eq!(response.accepted_at, Some(1700000000000));
+            assert_eq!(response.extra["peer"], "node-1");
+        }
+
+        #[test]
+        fn tolerates_unexpected_field_shapes() {
+            let response: SubmitResponse = serde_json::from_value(json!({
+                "hash": "abc123",
+                "ordinal": "not-a-number",
+                "status": "Rejected",
+                "timestamp": 1700000000000i64
+            }))
+            .unwrap();
+            assert_eq!(response.ordinal, None);
+            assert_eq!(response.status, None);
+            assert_eq!(response.accepted_at, Some(1700000000000));
+        }
+
+        #[test]
+        fn requires_hash() {
+            assert!(serde_json::from_value::<SubmitResponse>(json!({"ordinal": 1})).is_err());
+        }
+    }
+
     mod network_error {
         use super::*;
 
@@ -117,6 +492,7 @@ mod network_tests {
                 l1_url: Some("http://localhost:9010".to_string()),
                 data_l1_url: Some("http://localhost:8080".to_string()),
                 timeout: Some(30),
+                ..Default::default()
             };
 
             let l1_client = CurrencyL1Client::new(config.clone());